@@ -0,0 +1,144 @@
+/*
+ * Copyright 2023 sukawasatoru
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Blocking variants of the retrieve functions for the callers that have no async runtime such
+//! as the build scripts.
+//!
+//! The calls share a current-thread runtime of tokio so that the connections of the client are
+//! reused across the calls, and these must not be called from the async context.
+
+use crate::function::http_client::HttpClient;
+use crate::function::maven::{RepositoryConfig, POM};
+use crate::model::Coordinate;
+use crate::prelude::*;
+use futures::StreamExt;
+use once_cell::sync::Lazy;
+use tokio::runtime::Runtime;
+
+/// Same as [crate::retrieve_maven_lib] but blocks the current thread.
+pub fn retrieve_maven_lib_blocking(
+    client: impl Into<HttpClient>,
+    coordinate: &Coordinate,
+    repositories: &RepositoryConfig,
+) -> Fallible<POM> {
+    runtime()?.block_on(crate::retrieve_maven_lib(client, coordinate, repositories))
+}
+
+/// Retrieve the POMs of the `coordinates` with up to `concurrency` requests at the same time and
+/// returns the results in the same order as the `coordinates`.
+pub fn retrieve_maven_libs_blocking(
+    client: impl Into<HttpClient>,
+    coordinates: &[Coordinate],
+    repositories: &RepositoryConfig,
+    concurrency: usize,
+) -> Fallible<Vec<Fallible<POM>>> {
+    let client = client.into();
+    let results = runtime()?.block_on(
+        futures::stream::iter(coordinates)
+            .map(|coordinate| crate::retrieve_maven_lib(client.clone(), coordinate, repositories))
+            .buffered(concurrency.max(1))
+            .collect(),
+    );
+    Ok(results)
+}
+
+/// Runtime of all calls because the pooled connections of the client are bound to the runtime
+/// that opened them.
+static RUNTIME: Lazy<std::io::Result<Runtime>> = Lazy::new(|| {
+    tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+});
+
+fn runtime() -> Fallible<&'static Runtime> {
+    RUNTIME
+        .as_ref()
+        .map_err(|e| anyhow!("failed to create the runtime: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::function::mock_server::launch_maven_repository;
+
+    #[test]
+    fn retrieve_maven_libs_blocking_order() {
+        let root =
+            std::env::temp_dir().join(format!("oss-info-maven-blocking-{}", std::process::id()));
+        for (artifact_id, name) in [("first", "First"), ("second", "Second")] {
+            let dir = root.join("com/example").join(artifact_id);
+            std::fs::create_dir_all(dir.join("1.0.0")).unwrap();
+            std::fs::write(
+                dir.join("maven-metadata.xml"),
+                format!(
+                    r#"<metadata>
+  <groupId>com.example</groupId>
+  <artifactId>{artifact_id}</artifactId>
+  <versioning>
+    <release>1.0.0</release>
+    <versions>
+      <version>1.0.0</version>
+    </versions>
+  </versioning>
+</metadata>
+"#
+                ),
+            )
+            .unwrap();
+            std::fs::write(
+                dir.join("1.0.0").join(format!("{}-1.0.0.pom", artifact_id)),
+                format!(
+                    r#"<project>
+  <modelVersion>4.0.0</modelVersion>
+  <groupId>com.example</groupId>
+  <artifactId>{artifact_id}</artifactId>
+  <version>1.0.0</version>
+  <name>{name}</name>
+</project>
+"#
+                ),
+            )
+            .unwrap();
+        }
+
+        // the server runs on the other runtime because the blocking functions cannot be called
+        // in the async context.
+        let server_runtime = tokio::runtime::Runtime::new().unwrap();
+        let (handler, tx, port) = server_runtime.block_on(launch_maven_repository(&root));
+
+        let repositories = RepositoryConfig::single(format!("http://127.0.0.1:{}", *port));
+        let coordinates = ["com.example:second:1.0.0", "com.example:first:1.0.0"]
+            .map(|data| data.parse::<Coordinate>().unwrap());
+        // the calls share the connections of the client.
+        let client = HttpClient::from(reqwest::Client::new());
+        let actual = retrieve_maven_libs_blocking(client.clone(), &coordinates, &repositories, 2);
+        let single = retrieve_maven_lib_blocking(client.clone(), &coordinates[1], &repositories);
+        let again = retrieve_maven_lib_blocking(client, &coordinates[0], &repositories);
+
+        tx.send(()).unwrap();
+        server_runtime.block_on(handler).unwrap();
+        std::fs::remove_dir_all(&root).unwrap();
+
+        let actual = actual
+            .unwrap()
+            .into_iter()
+            .map(|data| data.unwrap().name.unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(vec!["Second", "First"], actual);
+        assert_eq!(Some("First".into()), single.unwrap().name);
+        assert_eq!(Some("Second".into()), again.unwrap().name);
+    }
+}
@@ -14,8 +14,47 @@
  * limitations under the License.
  */
 
+pub mod atomic_file;
+pub mod bazel;
+pub mod clearly_defined;
+pub mod compatibility;
+#[cfg(feature = "cli")]
+pub mod config;
+pub mod conflict;
+pub mod digest;
+pub mod exclude;
+pub mod formula;
 pub mod gradle;
+pub mod graph;
+pub mod group_license;
+pub mod http_cache;
+pub mod http_client;
+pub mod http_transport;
+pub mod ivy;
+pub mod jsonl;
+pub mod link;
+pub mod lock;
 pub mod maven;
+pub mod metrics;
+pub mod notice;
+pub mod obligation;
+pub mod osv;
+pub mod outdated;
+#[cfg(feature = "report")]
+pub mod overrides;
+pub mod progress;
+pub mod rate_limit;
+pub mod release_notes;
+pub mod repo_health;
+#[cfg(feature = "report")]
+pub mod report;
+pub mod state;
+pub mod summary;
+#[cfg(feature = "report")]
+pub mod template;
+pub mod text;
+#[cfg(feature = "report")]
+pub mod xlsx;
 
-#[cfg(test)]
+#[cfg(any(test, feature = "test-support"))]
 pub mod mock_server;
@@ -0,0 +1,183 @@
+/*
+ * Copyright 2023 sukawasatoru
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Output file that appears only after the whole content is written.
+
+use crate::prelude::*;
+use std::fs::{File, OpenOptions};
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+/// Writer of the temporary file next to `path` that is renamed to `path` by
+/// [`AtomicFile::commit`].
+///
+/// The temporary file is removed if the writer is dropped without committing so that a failed
+/// run never leaves a truncated report.
+#[derive(Debug)]
+pub struct AtomicFile {
+    path: PathBuf,
+    tmp_path: PathBuf,
+    writer: Option<BufWriter<File>>,
+}
+
+impl AtomicFile {
+    /// Create the temporary file. Returns an error if `path` already exists and `overwrite` is
+    /// `false`.
+    pub fn create(path: impl Into<PathBuf>, overwrite: bool) -> Fallible<Self> {
+        let path = path.into();
+        if !overwrite && path.exists() {
+            bail!(
+                "the output file already exists, use --force to overwrite it: {}",
+                path.display()
+            );
+        }
+
+        let tmp_path = tmp_path(&path)?;
+        let file = OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&tmp_path)
+            .with_context(|| format!("failed to create the file: {}", tmp_path.display()))?;
+
+        Ok(Self {
+            path,
+            tmp_path,
+            writer: Some(BufWriter::new(file)),
+        })
+    }
+
+    /// Flush the content and replace `path` with the temporary file.
+    pub fn commit(mut self) -> Fallible<()> {
+        let writer = self
+            .writer
+            .take()
+            .context("the file has already been committed")?;
+        let file = writer
+            .into_inner()
+            .map_err(|e| anyhow!("failed to flush the file: {}", e.error()))?;
+        file.sync_all()
+            .with_context(|| format!("failed to sync the file: {}", self.tmp_path.display()))?;
+        drop(file);
+
+        std::fs::rename(&self.tmp_path, &self.path)
+            .with_context(|| format!("failed to rename the file: {}", self.path.display()))?;
+
+        Ok(())
+    }
+
+    fn writer(&mut self) -> std::io::Result<&mut BufWriter<File>> {
+        self.writer.as_mut().ok_or_else(|| {
+            std::io::Error::new(std::io::ErrorKind::Other, "the file has been committed")
+        })
+    }
+}
+
+impl Write for AtomicFile {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.writer()?.write(buf)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.writer()?.flush()
+    }
+}
+
+impl Drop for AtomicFile {
+    fn drop(&mut self) {
+        if self.writer.take().is_some() {
+            if let Err(e) = std::fs::remove_file(&self.tmp_path) {
+                debug!(?e, path = %self.tmp_path.display(), "failed to remove the temporary file");
+            }
+        }
+    }
+}
+
+/// `.<file name>.<pid>.tmp` in the same directory so that the rename never crosses the file
+/// systems.
+fn tmp_path(path: &Path) -> Fallible<PathBuf> {
+    let file_name = path
+        .file_name()
+        .with_context(|| format!("the output path has no file name: {}", path.display()))?;
+    let mut tmp_name = std::ffi::OsString::from(".");
+    tmp_name.push(file_name);
+    tmp_name.push(format!(".{}.tmp", std::process::id()));
+
+    Ok(path.with_file_name(tmp_name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "oss-info-maven-atomic-{}-{}",
+            name,
+            std::process::id()
+        ));
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn commit() {
+        let dir = test_dir("commit");
+        let path = dir.join("report.csv");
+
+        let mut file = AtomicFile::create(&path, false).unwrap();
+        file.write_all(b"Dependency\n").unwrap();
+        assert!(!path.exists());
+        file.commit().unwrap();
+
+        let actual = std::fs::read_to_string(&path).unwrap();
+        let entries = std::fs::read_dir(&dir).unwrap().count();
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!("Dependency\n", actual);
+        assert_eq!(1, entries);
+    }
+
+    #[test]
+    fn create_existing() {
+        let dir = test_dir("existing");
+        let path = dir.join("report.csv");
+        std::fs::write(&path, "old").unwrap();
+
+        let refused = AtomicFile::create(&path, false).is_err();
+        let mut file = AtomicFile::create(&path, true).unwrap();
+        file.write_all(b"new").unwrap();
+        file.commit().unwrap();
+        let actual = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert!(refused);
+        assert_eq!("new", actual);
+    }
+
+    #[test]
+    fn drop_without_commit() {
+        let dir = test_dir("drop");
+        let path = dir.join("report.csv");
+
+        let mut file = AtomicFile::create(path, false).unwrap();
+        file.write_all(b"partial").unwrap();
+        drop(file);
+        let entries = std::fs::read_dir(&dir).unwrap().count();
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(0, entries);
+    }
+}
@@ -0,0 +1,235 @@
+/*
+ * Copyright 2023 sukawasatoru
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Inputs of the Bazel builds.
+
+use crate::model::Coordinate;
+use crate::prelude::*;
+use serde::Deserialize;
+use std::collections::{BTreeMap, BTreeSet};
+use std::io::Read;
+
+#[derive(Deserialize)]
+struct MavenInstall {
+    /// Version 1 of the lock file.
+    dependency_tree: Option<DependencyTree>,
+
+    /// Version 2 of the lock file.
+    artifacts: Option<BTreeMap<String, Artifact>>,
+}
+
+#[derive(Deserialize)]
+struct DependencyTree {
+    dependencies: Vec<TreeDependency>,
+}
+
+#[derive(Deserialize)]
+struct TreeDependency {
+    coord: String,
+}
+
+#[derive(Deserialize)]
+struct Artifact {
+    version: String,
+}
+
+/// Parse the `maven_install.json` that is written by `rules_jvm_external`.
+///
+/// The file has all resolved dependencies including the transitive ones. The sources and the
+/// javadoc artifacts are skipped because they are not shipped.
+///
+/// https://github.com/bazelbuild/rules_jvm_external
+pub fn parse_maven_install<R>(reader: R) -> Fallible<Vec<Coordinate>>
+where
+    R: Read,
+{
+    let maven_install = serde_json::from_reader::<_, MavenInstall>(reader)
+        .context("failed to parse maven_install.json")?;
+
+    let mut list = BTreeSet::new();
+    match maven_install {
+        MavenInstall {
+            dependency_tree: Some(tree),
+            ..
+        } => {
+            for dependency in tree.dependencies {
+                if let Some(coordinate) = parse_maven_coord(&dependency.coord)? {
+                    list.insert(coordinate);
+                }
+            }
+        }
+        MavenInstall {
+            artifacts: Some(artifacts),
+            ..
+        } => {
+            for (name, artifact) in artifacts {
+                // `group:artifact[:packaging]` and the version.
+                if let Some(coordinate) =
+                    parse_maven_coord(&format!("{}:{}", name, artifact.version))?
+                {
+                    list.insert(coordinate);
+                }
+            }
+        }
+        _ => bail!("maven_install.json has neither dependency_tree nor artifacts"),
+    }
+
+    Ok(list.into_iter().collect())
+}
+
+/// Parse the Maven style `group:artifact[:packaging[:classifier]]:version` that differs from
+/// the Gradle style of the [Coordinate].
+///
+/// Returns `None` for the sources and the javadoc.
+fn parse_maven_coord(coord: &str) -> Fallible<Option<Coordinate>> {
+    let segments = coord.split(':').collect::<Vec<_>>();
+    let (group_id, artifact_id, packaging, classifier, version) = match segments[..] {
+        [group_id, artifact_id, version] => (group_id, artifact_id, None, None, version),
+        [group_id, artifact_id, packaging, version] => {
+            (group_id, artifact_id, Some(packaging), None, version)
+        }
+        [group_id, artifact_id, packaging, classifier, version] => (
+            group_id,
+            artifact_id,
+            Some(packaging),
+            Some(classifier),
+            version,
+        ),
+        _ => bail!("unexpected coord: {}", coord),
+    };
+    ensure!(
+        !group_id.is_empty() && !artifact_id.is_empty() && !version.is_empty(),
+        "unexpected coord: {}",
+        coord
+    );
+
+    if matches!(classifier, Some("sources" | "javadoc")) {
+        debug!(%coord, "skip the sources or the javadoc");
+        return Ok(None);
+    }
+
+    let mut coordinate = Coordinate::new(group_id, artifact_id).with_version(version);
+    coordinate.classifier = classifier.map(Into::into);
+    // jar is the default of Gradle.
+    coordinate.packaging = packaging.filter(|data| *data != "jar").map(Into::into);
+
+    Ok(Some(coordinate))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_maven_install_v1() {
+        let source = r#"{
+    "dependency_tree": {
+        "__AUTOGENERATED_FILE_DO_NOT_MODIFY_THIS_FILE_MANUALLY": "THERE_IS_NO_DATA_ONLY_ZUUL",
+        "__INPUT_ARTIFACTS_HASH": 1153286658,
+        "conflict_resolution": {},
+        "dependencies": [
+            {
+                "coord": "androidx.core:core:aar:1.9.0",
+                "dependencies": [],
+                "directDependencies": [],
+                "file": "v1/https/maven.google.com/androidx/core/core/1.9.0/core-1.9.0.aar",
+                "url": "https://maven.google.com/androidx/core/core/1.9.0/core-1.9.0.aar"
+            },
+            {
+                "coord": "com.google.guava:guava:31.1-jre",
+                "dependencies": [],
+                "directDependencies": [],
+                "file": "v1/https/repo1.maven.org/maven2/com/google/guava/guava/31.1-jre/guava-31.1-jre.jar",
+                "url": "https://repo1.maven.org/maven2/com/google/guava/guava/31.1-jre/guava-31.1-jre.jar"
+            },
+            {
+                "coord": "com.google.guava:guava:jar:sources:31.1-jre",
+                "dependencies": [],
+                "directDependencies": [],
+                "file": "v1/https/repo1.maven.org/maven2/com/google/guava/guava/31.1-jre/guava-31.1-jre-sources.jar",
+                "url": "https://repo1.maven.org/maven2/com/google/guava/guava/31.1-jre/guava-31.1-jre-sources.jar"
+            }
+        ],
+        "version": "0.1.0"
+    }
+}"#;
+
+        let actual = parse_maven_install(source.as_bytes()).unwrap();
+        let expected = [
+            "androidx.core:core:1.9.0@aar",
+            "com.google.guava:guava:31.1-jre",
+        ]
+        .map(|data| data.parse::<Coordinate>().unwrap());
+
+        assert_eq!(expected.to_vec(), actual);
+    }
+
+    #[test]
+    fn parse_maven_install_v2() {
+        let source = r#"{
+  "__AUTOGENERATED_FILE_DO_NOT_MODIFY_THIS_FILE_MANUALLY": "THERE_IS_NO_DATA_ONLY_ZUUL",
+  "__INPUT_ARTIFACTS_HASH": -1013937543,
+  "__RESOLVED_ARTIFACTS_HASH": 1213938498,
+  "artifacts": {
+    "androidx.core:core:aar": {
+      "shasums": {
+        "jar": "0d6d2d1d0ee9c1e33f2f0e3e58e3b54d8d6c6b0f0d0a6e4e0f4b7c5c8a2d0f1e"
+      },
+      "version": "1.9.0"
+    },
+    "com.google.guava:guava": {
+      "shasums": {
+        "jar": "a42edc9cab792e39fe39bb94f3fca655ed157ff87a8af78e1d6ba5b07c4a00ab",
+        "sources": "8ab1853cdaf936ec88fe5e7ba4fb0ba0c5d7f6ac2c5bc8e5c8e0e5c5b8c1e2f3"
+      },
+      "version": "31.1-jre"
+    }
+  },
+  "dependencies": {},
+  "repositories": {
+    "https://repo1.maven.org/maven2/": [
+      "com.google.guava:guava"
+    ]
+  },
+  "version": "2"
+}"#;
+
+        let actual = parse_maven_install(source.as_bytes()).unwrap();
+        let expected = [
+            "androidx.core:core:1.9.0@aar",
+            "com.google.guava:guava:31.1-jre",
+        ]
+        .map(|data| data.parse::<Coordinate>().unwrap());
+
+        assert_eq!(expected.to_vec(), actual);
+    }
+
+    #[test]
+    fn parse_maven_coord_classifier() {
+        let mut expected = Coordinate::new("io.netty", "netty-transport-native-epoll")
+            .with_version("4.1.100.Final");
+        expected.classifier = Some("linux-x86_64".into());
+
+        assert_eq!(
+            Some(expected),
+            parse_maven_coord(
+                "io.netty:netty-transport-native-epoll:jar:linux-x86_64:4.1.100.Final"
+            )
+            .unwrap()
+        );
+        assert!(parse_maven_coord("com.google.guava:guava").is_err());
+    }
+}
@@ -0,0 +1,248 @@
+/*
+ * Copyright 2023 sukawasatoru
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! https://api.clearlydefined.io/api-docs/
+
+use crate::model::SPDX;
+use crate::prelude::*;
+use serde::Deserialize;
+
+/// Parse the response of the definitions endpoint into the licenses.
+///
+/// The declared license is preferred and the discovered licenses of the core facet are used as
+/// fallback. Returns an empty list if no license is asserted.
+pub fn parse_definition(json: &str) -> Fallible<Vec<SPDX>> {
+    let parsed = serde_json::from_str::<Definition>(json)?;
+    let licensed = match parsed.licensed {
+        Some(data) => data,
+        None => return Ok(vec![]),
+    };
+
+    let declared = match licensed.declared.as_deref().map(parse_expression) {
+        Some(Ok(data)) => data,
+        Some(Err(e)) => {
+            warn!(?e, "failed to parse the declared license");
+            vec![]
+        }
+        None => vec![],
+    };
+    if !declared.is_empty() {
+        return Ok(declared);
+    }
+
+    let mut discovered = vec![];
+    for expression in licensed
+        .facets
+        .and_then(|data| data.core)
+        .and_then(|data| data.discovered)
+        .map(|data| data.expressions)
+        .unwrap_or_default()
+    {
+        let licenses = match parse_expression(&expression) {
+            Ok(data) => data,
+            Err(e) => {
+                warn!(?e, "failed to parse the discovered license");
+                continue;
+            }
+        };
+        for license in licenses {
+            if !discovered.contains(&license) {
+                discovered.push(license);
+            }
+        }
+    }
+
+    Ok(discovered)
+}
+
+/// Parse the SPDX license expression such as `Apache-2.0 OR MIT` into the licenses.
+///
+/// `WITH` binds tighter than `AND` that binds tighter than `OR`, and the exception is kept with
+/// its license such as `GPL-2.0-only WITH Classpath-exception-2.0`.
+fn parse_expression(expression: &str) -> Fallible<Vec<SPDX>> {
+    let tokens = expression.replace('(', " ( ").replace(')', " ) ");
+    let mut tokens = tokens.split_whitespace().peekable();
+    let mut licenses = vec![];
+    parse_or(&mut tokens, &mut licenses)?;
+    if let Some(token) = tokens.next() {
+        bail!("unexpected token: {}: {}", token, expression);
+    }
+    Ok(licenses)
+}
+
+type Tokens<'a> = std::iter::Peekable<std::str::SplitWhitespace<'a>>;
+
+fn parse_or(tokens: &mut Tokens, licenses: &mut Vec<SPDX>) -> Fallible<()> {
+    parse_and(tokens, licenses)?;
+    while tokens.next_if_eq(&"OR").is_some() {
+        parse_and(tokens, licenses)?;
+    }
+    Ok(())
+}
+
+fn parse_and(tokens: &mut Tokens, licenses: &mut Vec<SPDX>) -> Fallible<()> {
+    parse_term(tokens, licenses)?;
+    while tokens.next_if_eq(&"AND").is_some() {
+        parse_term(tokens, licenses)?;
+    }
+    Ok(())
+}
+
+fn parse_term(tokens: &mut Tokens, licenses: &mut Vec<SPDX>) -> Fallible<()> {
+    match tokens.next() {
+        Some("(") => {
+            parse_or(tokens, licenses)?;
+            ensure!(
+                tokens.next() == Some(")"),
+                "missing the closing parenthesis"
+            );
+        }
+        Some(token) if is_operator(token) => bail!("unexpected token: {}", token),
+        Some(license) => {
+            let license = match tokens.next_if_eq(&"WITH") {
+                Some(_) => match tokens.next() {
+                    Some(exception) if !is_operator(exception) => {
+                        format!("{} WITH {}", license, exception)
+                    }
+                    Some(exception) => bail!("unexpected token: {}", exception),
+                    None => bail!("missing the exception of {}", license),
+                },
+                None => license.to_owned(),
+            };
+            if !matches!(license.as_str(), "NOASSERTION" | "NONE" | "OTHER") {
+                licenses.push(license.parse()?);
+            }
+        }
+        None => bail!("missing the license"),
+    }
+    Ok(())
+}
+
+fn is_operator(token: &str) -> bool {
+    matches!(token, "(" | ")" | "AND" | "OR" | "WITH")
+}
+
+#[derive(Deserialize)]
+struct Definition {
+    licensed: Option<Licensed>,
+}
+
+#[derive(Deserialize)]
+struct Licensed {
+    declared: Option<String>,
+    facets: Option<Facets>,
+}
+
+#[derive(Deserialize)]
+struct Facets {
+    core: Option<Facet>,
+}
+
+#[derive(Deserialize)]
+struct Facet {
+    discovered: Option<Discovered>,
+}
+
+#[derive(Deserialize)]
+struct Discovered {
+    #[serde(default)]
+    expressions: Vec<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_definition_declared() {
+        let json = r#"{
+  "coordinates": {
+    "type": "maven",
+    "provider": "mavencentral",
+    "namespace": "javax.inject",
+    "name": "javax.inject",
+    "revision": "1"
+  },
+  "licensed": {
+    "declared": "Apache-2.0",
+    "facets": {
+      "core": {
+        "discovered": {
+          "expressions": ["Apache-2.0"]
+        }
+      }
+    }
+  }
+}"#;
+
+        assert_eq!(vec![SPDX::Apache20], parse_definition(json).unwrap());
+    }
+
+    #[test]
+    fn parse_definition_expression() {
+        let json = r#"{"licensed": {"declared": "(EPL-2.0 OR GPL-2.0-only WITH Classpath-exception-2.0) AND MIT"}}"#;
+
+        let expected = vec![
+            SPDX::Other("EPL-2.0".into()),
+            SPDX::Other("GPL-2.0-only WITH Classpath-exception-2.0".into()),
+            SPDX::MIT,
+        ];
+        assert_eq!(expected, parse_definition(json).unwrap());
+    }
+
+    #[test]
+    fn parse_expression_malformed() {
+        assert!(parse_expression("(MIT OR Apache-2.0").is_err());
+        assert!(parse_expression("MIT AND").is_err());
+        assert!(parse_expression("GPL-2.0-only WITH").is_err());
+        assert!(parse_expression("MIT Apache-2.0").is_err());
+    }
+
+    #[test]
+    fn parse_definition_malformed_declared() {
+        let json = r#"{
+  "licensed": {
+    "declared": "MIT OR",
+    "facets": {"core": {"discovered": {"expressions": ["Apache-2.0"]}}}
+  }
+}"#;
+
+        assert_eq!(vec![SPDX::Apache20], parse_definition(json).unwrap());
+    }
+
+    #[test]
+    fn parse_definition_discovered() {
+        let json = r#"{
+  "licensed": {
+    "declared": "NOASSERTION",
+    "facets": {
+      "core": {
+        "discovered": {
+          "expressions": ["MIT", "BSD-3-Clause AND MIT"]
+        }
+      }
+    }
+  }
+}"#;
+
+        assert_eq!(vec![SPDX::MIT, SPDX::BSD3], parse_definition(json).unwrap(),);
+    }
+
+    #[test]
+    fn parse_definition_not_harvested() {
+        assert!(parse_definition("{}").unwrap().is_empty());
+    }
+}
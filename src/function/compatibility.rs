@@ -0,0 +1,272 @@
+/*
+ * Copyright 2023 sukawasatoru
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::model::SPDX;
+use std::fmt::{Display, Formatter};
+
+/// Category of the license for the first pass of the compatibility check.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum LicenseFamily {
+    Permissive,
+
+    /// The file-level or the library-level copyleft such as LGPL, MPL and EPL.
+    WeakCopyleft,
+
+    /// GPL that extends to the whole work.
+    StrongCopyleft,
+
+    /// AGPL that also extends to the users over the network.
+    NetworkCopyleft,
+
+    /// The license that is not open source such as [SPDX::AndroidSdk].
+    Proprietary,
+
+    Unknown,
+}
+
+impl LicenseFamily {
+    /// Categorize the license by the SPDX id or the name such as `GPL-3.0-only` and
+    /// `GNU Lesser General Public License`.
+    pub fn of(license: &SPDX) -> Self {
+        let name = match license {
+            SPDX::Apache20 | SPDX::BSD2 | SPDX::BSD3 | SPDX::ISC | SPDX::MIT => {
+                return Self::Permissive
+            }
+            SPDX::AndroidSdk => return Self::Proprietary,
+            SPDX::Other(data) => data.to_ascii_lowercase(),
+        };
+        let tokens = name
+            .split(|data: char| !data.is_ascii_alphanumeric())
+            .filter(|data| !data.is_empty())
+            .collect::<Vec<_>>();
+        let has_token = |prefix: &str| tokens.iter().any(|data| data.starts_with(prefix));
+
+        if name.contains("affero") || has_token("agpl") {
+            Self::NetworkCopyleft
+        } else if name.contains("lesser")
+            || name.contains("library general public")
+            || name.contains("classpath")
+            || has_token("lgpl")
+            || has_token("cpe")
+            || name.contains("mozilla public")
+            || has_token("mpl")
+            || name.contains("eclipse public")
+            || has_token("epl")
+            || name.contains("common development and distribution")
+            || has_token("cddl")
+        {
+            Self::WeakCopyleft
+        } else if name.contains("general public license") || has_token("gpl") {
+            Self::StrongCopyleft
+        } else if name.contains("eclipse distribution")
+            || name.contains("public domain")
+            || has_token("0bsd")
+            || has_token("bsd")
+            || has_token("zlib")
+            || has_token("unlicense")
+            || has_token("cc0")
+        {
+            Self::Permissive
+        } else {
+            Self::Unknown
+        }
+    }
+}
+
+/// Result of the first-pass compatibility check of the dependency with the project license.
+///
+/// This is not legal advice.
+#[derive(Clone, Debug, Eq, Ord, PartialEq, PartialOrd)]
+pub enum Compatibility {
+    Compatible,
+
+    /// The dependency needs the special handling or the manual review.
+    Review(String),
+
+    Incompatible(String),
+}
+
+impl Compatibility {
+    /// Explanation of [Compatibility::Review] and [Compatibility::Incompatible].
+    pub fn explanation(&self) -> &str {
+        match self {
+            Self::Compatible => "",
+            Self::Review(data) | Self::Incompatible(data) => data,
+        }
+    }
+}
+
+impl Display for Compatibility {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Compatible => f.write_str("compatible"),
+            Self::Review(_) => f.write_str("review"),
+            Self::Incompatible(_) => f.write_str("incompatible"),
+        }
+    }
+}
+
+/// Check the `licenses` of a dependency against the `project` license.
+///
+/// The best result of the licenses is used because the POM that lists multiple licenses allows
+/// the user to select any of them.
+///
+/// https://maven.apache.org/pom.html#Licenses
+pub fn check_compatibility(project: &SPDX, licenses: &[SPDX]) -> Compatibility {
+    licenses
+        .iter()
+        .map(|data| check_license(project, data))
+        .min()
+        .unwrap_or_else(|| Compatibility::Review("no license. review the terms manually".into()))
+}
+
+fn check_license(project: &SPDX, license: &SPDX) -> Compatibility {
+    let project_family = LicenseFamily::of(project);
+    match LicenseFamily::of(license) {
+        LicenseFamily::Permissive if *license == SPDX::Apache20 && is_gpl2_only(project) => {
+            Compatibility::Incompatible(
+                "Apache-2.0 is incompatible with GPL-2.0-only because of the patent terms".into(),
+            )
+        }
+        LicenseFamily::Permissive => Compatibility::Compatible,
+        LicenseFamily::WeakCopyleft => Compatibility::Review(format!(
+            "{} is weak copyleft. keep it as a separate library and offer the source of its modifications",
+            license
+        )),
+        LicenseFamily::StrongCopyleft => match project_family {
+            LicenseFamily::StrongCopyleft | LicenseFamily::NetworkCopyleft => {
+                Compatibility::Review(format!(
+                    "{} is strong copyleft. check that the GPL versions are compatible with {}",
+                    license, project
+                ))
+            }
+            _ => Compatibility::Incompatible(format!(
+                "{} is strong copyleft that requires the whole work to be distributed under GPL",
+                license
+            )),
+        },
+        LicenseFamily::NetworkCopyleft => match project_family {
+            LicenseFamily::NetworkCopyleft => Compatibility::Review(format!(
+                "{} is network copyleft. check that the versions are compatible with {}",
+                license, project
+            )),
+            _ => Compatibility::Incompatible(format!(
+                "{} is network copyleft that requires the source of the whole work for the users over the network",
+                license
+            )),
+        },
+        LicenseFamily::Proprietary => Compatibility::Review(format!(
+            "{} is not open source. review the terms",
+            license
+        )),
+        LicenseFamily::Unknown => Compatibility::Review(format!(
+            "{} is unknown. review the terms manually",
+            license
+        )),
+    }
+}
+
+/// `GPL-2.0-only` or `GPL-2.0` without `or later`.
+fn is_gpl2_only(license: &SPDX) -> bool {
+    if LicenseFamily::of(license) != LicenseFamily::StrongCopyleft {
+        return false;
+    }
+    let name = license.to_string().to_ascii_lowercase();
+    (name.contains("gpl-2.0") || name.contains("gplv2") || name.contains("version 2"))
+        && !name.contains("later")
+        && !name.contains('+')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn other(name: &str) -> SPDX {
+        SPDX::Other(name.into())
+    }
+
+    #[test]
+    fn license_family_names() {
+        assert_eq!(LicenseFamily::Permissive, LicenseFamily::of(&SPDX::MIT));
+        assert_eq!(
+            LicenseFamily::StrongCopyleft,
+            LicenseFamily::of(&other("GNU General Public License, version 2"))
+        );
+        assert_eq!(
+            LicenseFamily::StrongCopyleft,
+            LicenseFamily::of(&other("GPL-3.0-only"))
+        );
+        assert_eq!(
+            LicenseFamily::WeakCopyleft,
+            LicenseFamily::of(&other("GNU Lesser General Public License"))
+        );
+        assert_eq!(
+            LicenseFamily::WeakCopyleft,
+            LicenseFamily::of(&other("GPL2 w/ CPE"))
+        );
+        assert_eq!(
+            LicenseFamily::WeakCopyleft,
+            LicenseFamily::of(&other("Eclipse Public License - v 2.0"))
+        );
+        assert_eq!(
+            LicenseFamily::NetworkCopyleft,
+            LicenseFamily::of(&other("AGPL-3.0-or-later"))
+        );
+        assert_eq!(
+            LicenseFamily::Permissive,
+            LicenseFamily::of(&other("Eclipse Distribution License - v 1.0"))
+        );
+        assert_eq!(
+            LicenseFamily::Proprietary,
+            LicenseFamily::of(&SPDX::AndroidSdk)
+        );
+        assert_eq!(
+            LicenseFamily::Unknown,
+            LicenseFamily::of(&other("Custom License"))
+        );
+    }
+
+    #[test]
+    fn check_compatibility_apache_project() {
+        let project = SPDX::Apache20;
+        let check = |licenses: &[SPDX]| check_compatibility(&project, licenses).to_string();
+
+        assert_eq!("compatible", check(&[SPDX::MIT]));
+        assert_eq!("review", check(&[other("EPL-2.0")]));
+        assert_eq!("incompatible", check(&[other("GPL-2.0-only")]));
+        assert_eq!("incompatible", check(&[other("AGPL-3.0-only")]));
+        assert_eq!("review", check(&[]));
+        assert_eq!("review", check(&[other("Custom License")]));
+        // one of the licenses can be chosen.
+        assert_eq!("compatible", check(&[other("GPL-2.0-only"), SPDX::MIT]));
+        assert_eq!("review", check(&[other("GPL-2.0-only"), other("EPL-2.0")]));
+    }
+
+    #[test]
+    fn check_compatibility_gpl_project() {
+        let actual = check_compatibility(&other("GPL-2.0-only"), &[SPDX::Apache20]);
+        assert!(matches!(actual, Compatibility::Incompatible(_)));
+        assert_eq!(
+            Compatibility::Compatible,
+            check_compatibility(&other("GPL-3.0-or-later"), &[SPDX::Apache20])
+        );
+        assert_eq!(
+            "review",
+            check_compatibility(&other("GPL-3.0-or-later"), &[other("GPL-2.0-or-later")])
+                .to_string()
+        );
+    }
+}
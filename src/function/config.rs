@@ -0,0 +1,156 @@
+/*
+ * Copyright 2023 sukawasatoru
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Defaults of the command line options from the config file.
+//!
+//! The config file is TOML that has `option_name = value` such as `format = "notice"`. The keys
+//! of the table are prefixed with the name of the table so that `header` of `[notice]` is the
+//! name of `--notice-header`.
+
+use crate::prelude::*;
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ConfigValue {
+    Bool(bool),
+
+    /// The string or the number.
+    Str(String),
+
+    List(Vec<String>),
+}
+
+/// Parse the config file into the pairs of the option name and the value in the order of the
+/// file.
+///
+/// The `_` of the key is replaced with `-` so that `exclude_file` is the name of `--exclude-file`.
+pub fn parse_config(config: &str) -> Fallible<Vec<(String, ConfigValue)>> {
+    // `preserve_order` keeps the keys in the order of the file.
+    let table = toml::from_str::<toml::Table>(config)?;
+    let mut entries = vec![];
+    flatten_table(&mut entries, None, table)?;
+    Ok(entries)
+}
+
+fn flatten_table(
+    entries: &mut Vec<(String, ConfigValue)>,
+    prefix: Option<&str>,
+    table: toml::Table,
+) -> Fallible<()> {
+    for (key, value) in table {
+        let key = match prefix {
+            Some(prefix) => format!("{}-{}", prefix, key.replace('_', "-")),
+            None => key.replace('_', "-"),
+        };
+        let value = match value {
+            toml::Value::Table(data) => {
+                flatten_table(entries, Some(&key), data)?;
+                continue;
+            }
+            toml::Value::Boolean(data) => ConfigValue::Bool(data),
+            toml::Value::Array(data) => ConfigValue::List(
+                data.into_iter()
+                    .map(scalar_value)
+                    .collect::<Fallible<_>>()
+                    .with_context(|| format!("key: {}", key))?,
+            ),
+            data => ConfigValue::Str(scalar_value(data).with_context(|| format!("key: {}", key))?),
+        };
+        entries.push((key, value));
+    }
+    Ok(())
+}
+
+fn scalar_value(value: toml::Value) -> Fallible<String> {
+    Ok(match value {
+        toml::Value::String(data) => data,
+        toml::Value::Integer(data) => data.to_string(),
+        toml::Value::Float(data) => data.to_string(),
+        toml::Value::Boolean(data) => data.to_string(),
+        data => bail!("unexpected value: {}", data),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_config_values() {
+        let config = r#"
+# shared defaults
+format = "notice"
+timeout = 30
+prefer_ascii = true
+modules = false # not yet
+exclude = ["com.mycompany.*", 'regex:^com\.example(\.|:)']
+notice-var = "app_name=\"Example\""
+"#;
+
+        let expected = vec![
+            ("format".into(), ConfigValue::Str("notice".into())),
+            ("timeout".into(), ConfigValue::Str("30".into())),
+            ("prefer-ascii".into(), ConfigValue::Bool(true)),
+            ("modules".into(), ConfigValue::Bool(false)),
+            (
+                "exclude".into(),
+                ConfigValue::List(vec![
+                    "com.mycompany.*".into(),
+                    r"regex:^com\.example(\.|:)".into(),
+                ]),
+            ),
+            (
+                "notice-var".into(),
+                ConfigValue::Str(r#"app_name="Example""#.into()),
+            ),
+        ];
+        assert_eq!(expected, parse_config(config).unwrap());
+    }
+
+    #[test]
+    fn parse_config_tables_and_multiline_arrays() {
+        let config = r#"
+exclude = [
+    "com.mycompany.*",
+    "com.example.*",
+]
+
+[notice]
+header = "header.txt"
+"#;
+
+        let expected = vec![
+            (
+                "exclude".into(),
+                ConfigValue::List(vec!["com.mycompany.*".into(), "com.example.*".into()]),
+            ),
+            (
+                "notice-header".into(),
+                ConfigValue::Str("header.txt".into()),
+            ),
+        ];
+        assert_eq!(expected, parse_config(config).unwrap());
+    }
+
+    #[test]
+    fn parse_config_error() {
+        assert!(parse_config("format").is_err());
+        assert!(parse_config("format = notice").is_err());
+        assert!(parse_config("format = \"notice").is_err());
+        assert!(parse_config("exclude = [\"a\"").is_err());
+        assert!(parse_config("format = \"csv\" \"notice\"").is_err());
+        assert!(parse_config("exclude = [[\"a\"]]").is_err());
+    }
+}
@@ -0,0 +1,97 @@
+/*
+ * Copyright 2023 sukawasatoru
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::model::{Coordinate, Version};
+use std::collections::BTreeMap;
+
+/// Module that is requested with more than one version.
+#[derive(Debug, Eq, PartialEq)]
+pub struct Conflict {
+    /// `group:artifact`
+    pub module_id: String,
+
+    /// Requested versions in ascending order.
+    pub versions: Vec<String>,
+
+    /// Version that Gradle's conflict resolution picks, the highest one.
+    pub selected: String,
+}
+
+/// Find the modules that have multiple versions in the `coordinates`.
+///
+/// https://docs.gradle.org/current/userguide/dependency_resolution.html#sec:version-conflict
+pub fn find_conflicts<'a, I>(coordinates: I) -> Vec<Conflict>
+where
+    I: IntoIterator<Item = &'a Coordinate>,
+{
+    let mut modules = BTreeMap::<String, Vec<Version>>::new();
+    for coordinate in coordinates {
+        let version = match &coordinate.version {
+            Some(data) => Version::from(data.as_str()),
+            None => continue,
+        };
+        let versions = modules.entry(coordinate.module_id()).or_default();
+        if !versions
+            .iter()
+            .any(|data| data.as_str() == version.as_str())
+        {
+            versions.push(version);
+        }
+    }
+
+    modules
+        .into_iter()
+        .filter(|(_, versions)| 1 < versions.len())
+        .map(|(module_id, mut versions)| {
+            versions.sort();
+            let versions = versions
+                .into_iter()
+                .map(|data| data.to_string())
+                .collect::<Vec<_>>();
+            Conflict {
+                module_id,
+                selected: versions.last().cloned().unwrap_or_default(),
+                versions,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn find_conflicts_okhttp() {
+        let coordinates = [
+            "androidx.core:core-ktx:1.9.0",
+            "com.squareup.okhttp3:okhttp:4.9.3",
+            "com.squareup.okhttp3:okhttp:4.11.0",
+            "com.squareup.okhttp3:okhttp:4.11.0",
+            "com.squareup.okhttp3:okhttp:4.12.0-alpha01",
+            "com.squareup.okhttp3:okhttp",
+        ]
+        .map(|data| data.parse::<Coordinate>().unwrap());
+
+        let actual = find_conflicts(&coordinates);
+        let expected = vec![Conflict {
+            module_id: "com.squareup.okhttp3:okhttp".into(),
+            versions: vec!["4.9.3".into(), "4.11.0".into(), "4.12.0-alpha01".into()],
+            selected: "4.12.0-alpha01".into(),
+        }];
+        assert_eq!(expected, actual);
+    }
+}
@@ -0,0 +1,124 @@
+/*
+ * Copyright 2023 sukawasatoru
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::model::Coordinate;
+use crate::prelude::*;
+use regex::Regex;
+use std::io::BufRead;
+use std::str::FromStr;
+
+/// Pattern to exclude the dependencies from the resolution and the report.
+///
+/// The glob (`*` and `?`) such as `com.mycompany.*` or the regex with the `regex:` prefix such as
+/// `regex:^com\.mycompany\.` that matches with `group:artifact` or the whole coordinate.
+#[derive(Clone, Debug)]
+pub struct ExcludePattern(Regex);
+
+impl ExcludePattern {
+    pub fn is_match(&self, coordinate: &Coordinate) -> bool {
+        self.0.is_match(&coordinate.module_id()) || self.0.is_match(&coordinate.to_string())
+    }
+}
+
+impl FromStr for ExcludePattern {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        ensure!(!s.is_empty(), "empty pattern");
+
+        let regex = match s.strip_prefix("regex:") {
+            Some(data) => Regex::new(data)?,
+            None => {
+                let pattern = s
+                    .split('*')
+                    .map(|data| {
+                        data.split('?')
+                            .map(regex::escape)
+                            .collect::<Vec<_>>()
+                            .join(".")
+                    })
+                    .collect::<Vec<_>>()
+                    .join(".*");
+                Regex::new(&format!("^{}$", pattern))?
+            }
+        };
+
+        Ok(Self(regex))
+    }
+}
+
+/// Read the patterns per line. Blank lines and lines starting with `#` are ignored.
+pub fn read_exclude_patterns<R: BufRead>(reader: R) -> Fallible<Vec<ExcludePattern>> {
+    let mut patterns = vec![];
+    for line in reader.lines() {
+        let line = line?;
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        patterns.push(
+            line.parse()
+                .with_context(|| format!("unexpected pattern: {}", line))?,
+        );
+    }
+
+    Ok(patterns)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn is_match_glob() {
+        let pattern = "com.mycompany.*".parse::<ExcludePattern>().unwrap();
+        assert!(pattern.is_match(&"com.mycompany.core:core:1.0.0".parse().unwrap()));
+        assert!(!pattern.is_match(&"com.mycompany:core:1.0.0".parse().unwrap()));
+        assert!(!pattern.is_match(&"org.mycompany.core:core:1.0.0".parse().unwrap()));
+
+        let pattern = "androidx.*:*-ktx".parse::<ExcludePattern>().unwrap();
+        assert!(pattern.is_match(&"androidx.core:core-ktx:1.9.0".parse().unwrap()));
+        assert!(!pattern.is_match(&"androidx.core:core:1.9.0".parse().unwrap()));
+
+        let pattern = "com.example:lib:1.?.0".parse::<ExcludePattern>().unwrap();
+        assert!(pattern.is_match(&"com.example:lib:1.2.0".parse().unwrap()));
+        assert!(!pattern.is_match(&"com.example:lib:1.10.0".parse().unwrap()));
+    }
+
+    #[test]
+    fn is_match_regex() {
+        let pattern = r"regex:^com\.mycompany(\.|:)"
+            .parse::<ExcludePattern>()
+            .unwrap();
+        assert!(pattern.is_match(&"com.mycompany:core".parse().unwrap()));
+        assert!(pattern.is_match(&"com.mycompany.core:core".parse().unwrap()));
+        assert!(!pattern.is_match(&"com.mycompanyx:core".parse().unwrap()));
+    }
+
+    #[test]
+    fn read_exclude_patterns_comment() {
+        let source = r#"
+# internal artifacts
+com.mycompany.*
+
+regex:^project:
+"#;
+        let actual = read_exclude_patterns(source.as_bytes()).unwrap();
+        assert_eq!(2, actual.len());
+        assert!(actual[1].is_match(&"project:app".parse().unwrap()));
+    }
+}
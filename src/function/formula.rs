@@ -0,0 +1,56 @@
+/*
+ * Copyright 2023 sukawasatoru
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! https://owasp.org/www-community/attacks/CSV_Injection
+
+use std::borrow::Cow;
+
+/// Returns `true` if the spreadsheet may interpret the `field` as a formula.
+pub fn is_formula_like(field: &str) -> bool {
+    field.starts_with(['=', '+', '-', '@', '\t', '\r'])
+}
+
+/// Prefix the `field` that looks like a formula with `'` to be treated as a text.
+pub fn escape_formula(field: &str) -> Cow<str> {
+    if is_formula_like(field) {
+        Cow::Owned(format!("'{}", field))
+    } else {
+        Cow::Borrowed(field)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn is_formula_like_leading() {
+        assert!(is_formula_like("=HYPERLINK(\"http://example.com\")"));
+        assert!(is_formula_like("+1"));
+        assert!(is_formula_like("-2+3"));
+        assert!(is_formula_like("@SUM(A1:A2)"));
+        assert!(is_formula_like("\t=1"));
+        assert!(!is_formula_like("Glide"));
+        assert!(!is_formula_like("a=b"));
+        assert!(!is_formula_like(""));
+    }
+
+    #[test]
+    fn escape_formula_prefix() {
+        assert_eq!("'=1+2", escape_formula("=1+2"));
+        assert_eq!("Glide", escape_formula("Glide"));
+    }
+}
@@ -14,16 +14,34 @@
  * limitations under the License.
  */
 
+mod dependency_report;
+mod dependency_tree;
+mod insight;
+mod lockfile;
+mod module_metadata;
 mod parse_dependencies_string;
 mod parse_prettied_dependencies_string;
 
-pub use parse_dependencies_string::parse_dependencies_string;
-pub use parse_prettied_dependencies_string::parse_prettied_dependencies_string;
+pub use dependency_report::parse_dependency_report;
+pub use dependency_tree::{parse_dependency_tree, DependencyNode, DependencyTree};
+pub use insight::{parse_dependency_insight, InsightPath};
+pub use lockfile::parse_lockfile;
+pub use module_metadata::parse_module_metadata;
+pub use parse_dependencies_string::{
+    parse_dependencies_string, parse_module_dependencies_string, parse_tree_entries, LineError,
+    TreeEntry,
+};
+pub use parse_prettied_dependencies_string::{
+    parse_prettied_dependencies_string, parse_prettied_entries,
+};
 
-fn pretty_version(line: &str) -> String {
+use crate::model::{RichVersion, VersionConstraint};
+use crate::prelude::*;
+
+fn pretty_version(line: &str) -> Fallible<String> {
     let segments = line.split(':').collect::<Vec<_>>();
-    let group_id = segments.first().expect("missing group id");
-    let artifact_name = segments.get(1).expect("missing artifact name");
+    let group_id = segments.first().context("missing group id")?;
+    let artifact_name = segments.get(1).context("missing artifact name")?;
 
     match segments.len() {
         3 => {
@@ -32,7 +50,24 @@ fn pretty_version(line: &str) -> String {
             // - org.jetbrains.kotlin:kotlin-stdlib:1.6.21 -> 1.7.10 (*)
             // - androidx.profileinstaller:profileinstaller:1.3.0 (*)
 
-            let version = segments.get(2).expect("missing version");
+            let version = segments.get(2).context("missing version")?;
+            if let Some((rich_version, rest)) = version
+                .strip_prefix('{')
+                .and_then(|data| data.split_once('}'))
+            {
+                // - org.jetbrains.kotlin:kotlin-stdlib:{strictly 1.6.10} -> 1.6.10
+                // - org.jetbrains.kotlin:kotlin-stdlib:{strictly 1.6.10} -> 1.6.10 (c)
+                // - org.jetbrains.kotlin:kotlin-stdlib:{strictly 1.6.10}
+                let version = match rest.split_once("->") {
+                    Some((_, resolved)) => resolved.split_whitespace().next().map(str::to_owned),
+                    None => declared_version(rich_version),
+                };
+                return Ok(match version {
+                    Some(version) => format!("{}:{}:{}", group_id, artifact_name, version),
+                    // the latest version is retrieved for the range.
+                    None => format!("{}:{}", group_id, artifact_name),
+                });
+            }
             let version_segments = version.split(' ').collect::<Vec<_>>();
             let version = match version_segments.len() {
                 4 | 3 => {
@@ -41,7 +76,7 @@ fn pretty_version(line: &str) -> String {
                     // `1.6.21 -> 1.7.10`
                     version_segments
                         .get(2)
-                        .expect("unexpected format (v_seg.len == 3)")
+                        .context("unexpected format (v_seg.len == 3)")?
                 }
                 2 | 1 => {
                     // |0     |1  |
@@ -49,12 +84,16 @@ fn pretty_version(line: &str) -> String {
                     // `1.6.21`
                     version_segments
                         .first()
-                        .expect("unexpected format (v_seg.len 2 or 1)")
+                        .context("unexpected format (v_seg.len 2 or 1)")?
                 }
-                _ => todo!("3-{}: {}", segments.len(), line),
+                _ => bail!(
+                    "unexpected format (v_seg.len == {}): {}",
+                    version_segments.len(),
+                    line
+                ),
             };
 
-            format!("{}:{}:{}", group_id, artifact_name, version)
+            Ok(format!("{}:{}:{}", group_id, artifact_name, version))
         }
         2 => {
             // no version by bom. e.g:
@@ -64,12 +103,65 @@ fn pretty_version(line: &str) -> String {
             // |0       |1 |2    |3  |
             // `material -> 1.3.1 (*)`
             let mut segments = artifact_name.split(' ');
-            let artifact_name = segments.next().expect("missing artifact name (by bom)");
+            let artifact_name = segments.next().context("missing artifact name (by bom)")?;
             segments.next();
-            let version = segments.next().expect("missing version (by bom)");
+            let version = segments.next().context("missing version (by bom)")?;
 
-            format!("{}:{}:{}", group_id, artifact_name, version)
+            Ok(format!("{}:{}:{}", group_id, artifact_name, version))
         }
-        _ => todo!("{}: {}", segments.len(), line),
+        _ => bail!(
+            "unexpected format (seg.len == {}): {}",
+            segments.len(),
+            line
+        ),
+    }
+}
+
+/// Returns the single version of the rich version that has no resolved version such as the
+/// `prefer` or the exact `strictly`.
+fn declared_version(rich_version: &str) -> Option<String> {
+    let rich_version = format!("{{{}}}", rich_version)
+        .parse::<RichVersion>()
+        .ok()?;
+    if rich_version.prefer.is_some() {
+        return rich_version.prefer;
+    }
+
+    match rich_version.strictly.or(rich_version.require)? {
+        VersionConstraint::Exact(data) => Some(data),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pretty_version_rich_version() {
+        assert_eq!(
+            "org.jetbrains.kotlin:kotlin-stdlib:1.6.10",
+            pretty_version("org.jetbrains.kotlin:kotlin-stdlib:{strictly 1.6.10} -> 1.6.10 (c)")
+                .unwrap(),
+        );
+        assert_eq!(
+            "org.jetbrains.kotlin:kotlin-stdlib:1.6.21",
+            pretty_version(
+                "org.jetbrains.kotlin:kotlin-stdlib:{strictly [1.6, 1.7); prefer 1.6.21}"
+            )
+            .unwrap(),
+        );
+        assert_eq!(
+            "org.jetbrains.kotlin:kotlin-stdlib",
+            pretty_version("org.jetbrains.kotlin:kotlin-stdlib:{strictly [1.6, 1.7)}").unwrap(),
+        );
+    }
+
+    #[test]
+    fn pretty_version_unexpected_format() {
+        assert!(pretty_version("org.jetbrains.kotlin:kotlin-stdlib:1.6.10:jdk8:extra").is_err());
+        assert!(
+            pretty_version("org.jetbrains.kotlin:kotlin-stdlib:1.6.21 -> 1.7.10 (*) x").is_err()
+        );
     }
 }
@@ -0,0 +1,149 @@
+/*
+ * Copyright 2023 sukawasatoru
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::model::Coordinate;
+use crate::prelude::*;
+use regex::Regex;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+
+/// Parse the report of the `htmlDependencyReport` task, which is the JSON in
+/// `build/reports/project/dependencies/js/*.js`.
+///
+/// Returns the first level dependencies with the project name like
+/// [parse_module_dependencies_string](crate::function::gradle::parse_module_dependencies_string).
+///
+/// https://docs.gradle.org/current/dsl/org.gradle.api.reporting.dependencies.HtmlDependencyReportTask.html
+pub fn parse_dependency_report(
+    report: &str,
+    configuration_filter: Option<&Regex>,
+) -> Fallible<Vec<(Coordinate, Vec<String>)>> {
+    // the `.js` wraps the JSON with `var projectDependencyReport = ...;`.
+    let json = report.trim();
+    let json = match json.find('{') {
+        Some(start) => json[start..].trim_end_matches(';'),
+        None => bail!("missing JSON object"),
+    };
+    let parsed = serde_json::from_str::<Report>(json)?;
+
+    let mut first_levels = vec![];
+    for configuration in &parsed.project.configurations {
+        if !configuration_filter.map_or(true, |filter| filter.is_match(&configuration.name)) {
+            debug!(configuration = %configuration.name, "skip");
+            continue;
+        }
+        collect_first_levels(&configuration.dependencies, &mut first_levels);
+    }
+
+    let mut list = BTreeMap::<Coordinate, Vec<String>>::new();
+    for name in first_levels {
+        let coordinate = parse_name(name).with_context(|| format!("unexpected name: {}", name))?;
+        let modules = list.entry(coordinate).or_default();
+        if !modules.contains(&parsed.project.name) {
+            modules.push(parsed.project.name.clone());
+        }
+    }
+
+    Ok(list.into_iter().collect())
+}
+
+/// Collect the dependencies and the children of `project :lib` as the first level.
+fn collect_first_levels<'a>(dependencies: &'a [ReportDependency], list: &mut Vec<&'a str>) {
+    for dependency in dependencies {
+        if dependency.name.starts_with("project ") {
+            collect_first_levels(&dependency.children, list);
+        } else {
+            list.push(&dependency.name);
+        }
+    }
+}
+
+/// `group:artifact:version` or `group:artifact[:requested] ➡ selected`.
+fn parse_name(name: &str) -> Fallible<Coordinate> {
+    match name.split_once('➡') {
+        Some((requested, selected)) => {
+            let requested = requested.trim().parse::<Coordinate>()?;
+            Ok(Coordinate::new(requested.group_id, requested.artifact_id)
+                .with_version(selected.trim()))
+        }
+        None => name.parse(),
+    }
+}
+
+#[derive(Deserialize)]
+struct Report {
+    project: ReportProject,
+}
+
+#[derive(Deserialize)]
+struct ReportProject {
+    name: String,
+
+    #[serde(default)]
+    configurations: Vec<ReportConfiguration>,
+}
+
+#[derive(Deserialize)]
+struct ReportConfiguration {
+    name: String,
+
+    #[serde(default)]
+    dependencies: Vec<ReportDependency>,
+}
+
+#[derive(Deserialize)]
+struct ReportDependency {
+    name: String,
+
+    #[serde(default)]
+    children: Vec<ReportDependency>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const REPORT: &str = r#"var projectDependencyReport = {"gradleVersion":"Gradle 8.4","generationDate":"Mon Oct 16 10:00:00 JST 2023","project":{"name":"app","description":null,"configurations":[{"name":"debugRuntimeClasspath","description":"Resolved configuration for runtime for variant: debug","dependencies":[{"module":"com.squareup.leakcanary:leakcanary-android","name":"com.squareup.leakcanary:leakcanary-android:2.10","resolvable":"RESOLVED","hasConflict":false,"alreadyRendered":false,"children":[]}],"moduleInsights":[]},{"name":"releaseRuntimeClasspath","description":"Resolved configuration for runtime for variant: release","dependencies":[{"module":null,"name":"project :lib","resolvable":"RESOLVED","hasConflict":false,"alreadyRendered":false,"children":[{"module":"androidx.core:core-ktx","name":"androidx.core:core-ktx:1.9.0","resolvable":"RESOLVED","hasConflict":false,"alreadyRendered":false,"children":[]}]},{"module":"org.jetbrains.kotlin:kotlin-stdlib","name":"org.jetbrains.kotlin:kotlin-stdlib:1.6.21 ➡ 1.7.10","resolvable":"RESOLVED","hasConflict":true,"alreadyRendered":false,"children":[{"module":"org.jetbrains:annotations","name":"org.jetbrains:annotations:13.0","resolvable":"RESOLVED","hasConflict":false,"alreadyRendered":false,"children":[]}]},{"module":"androidx.compose.ui:ui-tooling","name":"androidx.compose.ui:ui-tooling ➡ 1.3.3","resolvable":"RESOLVED","hasConflict":true,"alreadyRendered":false,"children":[]}],"moduleInsights":[]}]}};"#;
+
+    #[test]
+    fn parse_dependency_report_all() {
+        let actual = parse_dependency_report(REPORT, None).unwrap();
+        let expected = [
+            "androidx.compose.ui:ui-tooling:1.3.3",
+            "androidx.core:core-ktx:1.9.0",
+            "com.squareup.leakcanary:leakcanary-android:2.10",
+            "org.jetbrains.kotlin:kotlin-stdlib:1.7.10",
+        ]
+        .map(|data| (data.parse::<Coordinate>().unwrap(), vec!["app".to_string()]))
+        .to_vec();
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn parse_dependency_report_configuration_filter() {
+        let filter = Regex::new("^debug").unwrap();
+        let actual = parse_dependency_report(REPORT, Some(&filter)).unwrap();
+        let expected = vec![(
+            "com.squareup.leakcanary:leakcanary-android:2.10"
+                .parse::<Coordinate>()
+                .unwrap(),
+            vec!["app".to_string()],
+        )];
+
+        assert_eq!(expected, actual);
+    }
+}
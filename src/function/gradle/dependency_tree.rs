@@ -0,0 +1,360 @@
+/*
+ * Copyright 2023 sukawasatoru
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::function::gradle::parse_dependencies_string::{
+    calculate_level, configuration_name, project_name,
+};
+use crate::prelude::*;
+use std::fmt::{Display, Formatter};
+use std::io::BufRead;
+
+/// Dependency tree of a configuration of the Gradle output.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DependencyTree {
+    /// Gradle module of the `Project ':app'` section.
+    pub module: Option<String>,
+
+    /// Configuration such as `releaseRuntimeClasspath`.
+    pub configuration: Option<String>,
+
+    /// First level of the tree.
+    pub roots: Vec<DependencyNode>,
+}
+
+/// Line of the dependency tree with the children.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DependencyNode {
+    /// `group:artifact` or `project :lib`.
+    pub name: String,
+
+    /// Version that is declared by the parent such as `1.2.0` of `1.2.0 -> 1.5.0`.
+    pub requested: Option<String>,
+
+    /// Version that is selected by the conflict resolution.
+    pub selected: Option<String>,
+
+    /// `(*)`, `(c)` or `(n)` of the line.
+    pub suffix: Option<String>,
+
+    pub children: Vec<DependencyNode>,
+}
+
+impl DependencyNode {
+    /// `project :lib` of the project dependency.
+    pub fn is_project(&self) -> bool {
+        self.name.starts_with("project ")
+    }
+
+    /// `(*)` that Gradle omits the children because they are listed previously.
+    pub fn is_omitted(&self) -> bool {
+        self.suffix.as_deref() == Some("(*)")
+    }
+
+    /// `(c)` of the dependency constraint.
+    pub fn is_constraint(&self) -> bool {
+        self.suffix.as_deref() == Some("(c)")
+    }
+
+    /// Whether the conflict resolution selected the version that differs from the requested.
+    pub fn is_upgraded(&self) -> bool {
+        matches!((&self.requested, &self.selected), (Some(requested), Some(selected)) if requested != selected)
+    }
+
+    /// `group:artifact:selected` of the external dependency.
+    pub fn selected_name(&self) -> String {
+        match &self.selected {
+            Some(selected) if !self.is_project() => format!("{}:{}", self.name, selected),
+            _ => self.name.clone(),
+        }
+    }
+
+    /// Visit the node and the descendants in the depth-first order with the ancestors.
+    pub fn walk<'a, F>(&'a self, f: &mut F)
+    where
+        F: FnMut(&[&'a DependencyNode], &'a DependencyNode),
+    {
+        fn walk_impl<'a, F>(
+            node: &'a DependencyNode,
+            ancestors: &mut Vec<&'a DependencyNode>,
+            f: &mut F,
+        ) where
+            F: FnMut(&[&'a DependencyNode], &'a DependencyNode),
+        {
+            f(ancestors, node);
+            ancestors.push(node);
+            for child in &node.children {
+                walk_impl(child, ancestors, f);
+            }
+            ancestors.pop();
+        }
+
+        walk_impl(self, &mut vec![], f);
+    }
+}
+
+impl Display for DependencyNode {
+    /// Same as the line of the Gradle output without the tree prefix.
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.name)?;
+        match (&self.requested, &self.selected) {
+            (Some(requested), Some(selected)) if requested != selected => {
+                write!(f, ":{} -> {}", requested, selected)?
+            }
+            (Some(requested), _) => write!(f, ":{}", requested)?,
+            (None, Some(selected)) => write!(f, " -> {}", selected)?,
+            (None, None) => {}
+        }
+        if let Some(suffix) = &self.suffix {
+            write!(f, " {}", suffix)?;
+        }
+        Ok(())
+    }
+}
+
+/// Same as [crate::function::gradle::parse_dependencies_string] but keeps the structure of the
+/// trees and the requested and the selected versions instead of flattening them.
+///
+/// A tree is returned per configuration of each project section. The lines that cannot be parsed
+/// such as the legend are skipped.
+pub fn parse_dependency_tree<R>(reader: &mut R) -> Fallible<Vec<DependencyTree>>
+where
+    R: BufRead,
+{
+    /// Attach the nodes deeper than `level` to their parents.
+    fn unwind(stack: &mut Vec<DependencyNode>, roots: &mut Vec<DependencyNode>, level: usize) {
+        while level < stack.len() {
+            let node = stack.pop().expect("empty stack");
+            match stack.last_mut() {
+                Some(parent) => parent.children.push(node),
+                None => roots.push(node),
+            }
+        }
+    }
+
+    let mut trees = vec![];
+    let mut current = DependencyTree {
+        module: None,
+        configuration: None,
+        roots: vec![],
+    };
+    let mut stack = Vec::<DependencyNode>::new();
+    let mut line_number = 0usize;
+    loop {
+        let mut line = String::new();
+        let line = match reader.read_line(&mut line) {
+            Ok(0) => break,
+            Ok(_) => {
+                line_number += 1;
+                line.trim_end()
+            }
+            Err(e) => bail!("failed to read lines: {}", e),
+        };
+
+        let module = project_name(line);
+        let level = match module {
+            Some(_) => None,
+            None => {
+                calculate_level(line).with_context(|| format!("line {}: {}", line_number, line))?
+            }
+        };
+        let configuration = match level {
+            Some(_) => None,
+            None => configuration_name(line),
+        };
+        if module.is_some() || configuration.is_some() {
+            // the tree of the next project or configuration follows.
+            unwind(&mut stack, &mut current.roots, 0);
+            let next = DependencyTree {
+                module: module.or_else(|| current.module.clone()),
+                configuration,
+                roots: vec![],
+            };
+            let finished = std::mem::replace(&mut current, next);
+            if !finished.roots.is_empty() {
+                trees.push(finished);
+            }
+            continue;
+        }
+
+        let level = match level {
+            Some(data) => data,
+            None => continue,
+        };
+        let node = match line
+            .split_once("--- ")
+            .and_then(|(_, data)| parse_node(data))
+        {
+            Some(data) => data,
+            None => {
+                debug!(line_number, %line, "skip the line");
+                continue;
+            }
+        };
+        if stack.len() < level {
+            debug!(line_number, %line, "skip the line of the unexpected indent");
+            continue;
+        }
+        unwind(&mut stack, &mut current.roots, level);
+        stack.push(node);
+    }
+    unwind(&mut stack, &mut current.roots, 0);
+    if !current.roots.is_empty() {
+        trees.push(current);
+    }
+
+    Ok(trees)
+}
+
+/// Parse the line without the tree prefix such as `androidx.core:core:1.2.0 -> 1.9.0 (*)`.
+fn parse_node(text: &str) -> Option<DependencyNode> {
+    let (text, suffix) = match text.rsplit_once(' ') {
+        Some((rest, suffix @ ("(*)" | "(c)" | "(n)"))) => (rest, Some(suffix.to_string())),
+        _ => (text, None),
+    };
+    if let Some(project) = text.strip_prefix("project ") {
+        return Some(DependencyNode {
+            name: format!("project {}", project),
+            requested: None,
+            selected: None,
+            suffix,
+            children: vec![],
+        });
+    }
+
+    let (declared, selected) = match text.split_once(" -> ") {
+        Some((declared, selected)) => (declared, Some(selected.trim().to_string())),
+        None => (text, None),
+    };
+    let mut segments = declared.splitn(3, ':');
+    let group_id = segments.next()?;
+    let artifact_id = segments.next()?;
+    let requested = segments.next().map(str::to_string);
+
+    Some(DependencyNode {
+        name: format!("{}:{}", group_id, artifact_id),
+        selected: selected.or_else(|| requested.clone()),
+        requested,
+        suffix,
+        children: vec![],
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_dependency_tree_projects() {
+        let gradle_output = r"
+------------------------------------------------------------
+Project ':app'
+------------------------------------------------------------
+
+releaseRuntimeClasspath - Resolved configuration for runtime for variant: release
++--- androidx.core:core-ktx:1.9.0
+|    +--- androidx.annotation:annotation:1.1.0 -> 1.5.0
+|    \--- androidx.core:core:1.9.0
+|         \--- androidx.annotation:annotation:1.2.0 -> 1.5.0 (*)
++--- project :lib
+|    \--- androidx.annotation:annotation:{strictly 1.5.0} -> 1.5.0 (c)
+\--- androidx.compose.ui:ui-tooling -> 1.3.3
+
+releaseUnitTestRuntimeClasspath - Resolved configuration for runtime for variant: releaseUnitTest
+No dependencies
+
+(*) - dependencies omitted (listed previously)
+
+------------------------------------------------------------
+Project ':lib'
+------------------------------------------------------------
+
+releaseRuntimeClasspath - Resolved configuration for runtime for variant: release
+\--- androidx.annotation:annotation:1.5.0
+";
+
+        let actual = parse_dependency_tree(&mut gradle_output.as_bytes()).unwrap();
+        assert_eq!(2, actual.len());
+
+        let app = &actual[0];
+        assert_eq!(Some(":app".into()), app.module);
+        assert_eq!(Some("releaseRuntimeClasspath".into()), app.configuration);
+        assert_eq!(3, app.roots.len());
+        let core_ktx = &app.roots[0];
+        assert_eq!("androidx.core:core-ktx:1.9.0", core_ktx.to_string());
+        assert_eq!(2, core_ktx.children.len());
+        assert!(core_ktx.children[0].is_upgraded());
+        let annotation = &core_ktx.children[1].children[0];
+        assert_eq!(
+            "androidx.annotation:annotation:1.2.0 -> 1.5.0 (*)",
+            annotation.to_string()
+        );
+        assert!(annotation.is_omitted());
+        assert_eq!(
+            "androidx.annotation:annotation:1.5.0",
+            annotation.selected_name()
+        );
+        assert!(app.roots[1].is_project());
+        assert!(app.roots[1].children[0].is_constraint());
+        assert_eq!(
+            "androidx.compose.ui:ui-tooling -> 1.3.3",
+            app.roots[2].to_string()
+        );
+
+        let lib = &actual[1];
+        assert_eq!(Some(":lib".into()), lib.module);
+        assert_eq!(1, lib.roots.len());
+    }
+
+    #[test]
+    fn walk_ancestors() {
+        let tree = parse_dependency_tree(
+            &mut r"
+\--- androidx.core:core-ktx:1.9.0
+     \--- androidx.core:core:1.9.0
+          \--- androidx.annotation:annotation:1.2.0
+"
+            .as_bytes(),
+        )
+        .unwrap();
+        let mut actual = vec![];
+        tree[0].roots[0].walk(&mut |ancestors, node| {
+            actual.push((ancestors.len(), node.name.clone()));
+        });
+        assert_eq!(
+            vec![
+                (0, "androidx.core:core-ktx".to_string()),
+                (1, "androidx.core:core".into()),
+                (2, "androidx.annotation:annotation".into()),
+            ],
+            actual,
+        );
+    }
+
+    #[test]
+    fn parse_node_by_bom() {
+        assert_eq!(
+            Some(DependencyNode {
+                name: "androidx.compose.ui:ui-tooling".into(),
+                requested: None,
+                selected: Some("1.3.3".into()),
+                suffix: None,
+                children: vec![],
+            }),
+            parse_node("androidx.compose.ui:ui-tooling -> 1.3.3"),
+        );
+        assert_eq!(None, parse_node("No dependencies"));
+    }
+}
@@ -0,0 +1,154 @@
+/*
+ * Copyright 2023 sukawasatoru
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::function::gradle::dependency_tree::{parse_dependency_tree, DependencyNode};
+use crate::prelude::*;
+use std::fmt::{Display, Formatter};
+use std::io::BufRead;
+
+/// Path from the first level of the tree to the artifact.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct InsightPath {
+    /// Gradle module of the `Project ':app'` section.
+    pub module: Option<String>,
+
+    pub configuration: Option<String>,
+
+    /// Nodes from the first level to the artifact without the children.
+    pub nodes: Vec<DependencyNode>,
+}
+
+impl InsightPath {
+    /// The artifact that is the last node of the path.
+    pub fn target(&self) -> &DependencyNode {
+        self.nodes.last().expect("empty path")
+    }
+}
+
+impl Display for InsightPath {
+    /// Write the path from the artifact to the configuration as the `dependencyInsight` task.
+    ///
+    /// ```text
+    /// androidx.annotation:annotation:1.2.0 -> 1.5.0
+    /// \--- androidx.core:core:1.9.0
+    ///      \--- :app releaseRuntimeClasspath
+    /// ```
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "{}", self.target())?;
+        let parents = self.nodes.iter().rev().skip(1).map(ToString::to_string);
+        let root = [self.module.as_deref(), self.configuration.as_deref()]
+            .into_iter()
+            .flatten()
+            .collect::<Vec<_>>()
+            .join(" ");
+        for (indent, line) in parents
+            .chain((!root.is_empty()).then_some(root))
+            .enumerate()
+        {
+            writeln!(f, "{}\\--- {}", " ".repeat(indent * 5), line)?;
+        }
+        Ok(())
+    }
+}
+
+/// Returns every path of the dependency trees that pulls in the artifact of `module_id` that is
+/// `group:artifact`.
+///
+/// The trees of all projects and configurations are parsed. The subtree of the `(*)` is not
+/// repeated by Gradle so the paths through it are not listed.
+pub fn parse_dependency_insight<R>(reader: &mut R, module_id: &str) -> Fallible<Vec<InsightPath>>
+where
+    R: BufRead,
+{
+    let mut paths = vec![];
+    for tree in parse_dependency_tree(reader)? {
+        for root in &tree.roots {
+            root.walk(&mut |ancestors, node| {
+                if node.name != module_id {
+                    return;
+                }
+                paths.push(InsightPath {
+                    module: tree.module.clone(),
+                    configuration: tree.configuration.clone(),
+                    nodes: ancestors
+                        .iter()
+                        .copied()
+                        .chain([node])
+                        .map(|data| DependencyNode {
+                            children: vec![],
+                            ..data.clone()
+                        })
+                        .collect(),
+                });
+            });
+        }
+    }
+
+    Ok(paths)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_dependency_insight_annotation() {
+        let gradle_output = r"
+------------------------------------------------------------
+Project ':app'
+------------------------------------------------------------
+
+releaseRuntimeClasspath - Resolved configuration for runtime for variant: release
++--- androidx.core:core-ktx:1.9.0
+|    +--- androidx.annotation:annotation:1.1.0 -> 1.5.0
+|    \--- androidx.core:core:1.9.0
+|         \--- androidx.annotation:annotation:1.2.0 -> 1.5.0 (*)
++--- project :lib
+|    \--- androidx.annotation:annotation:{strictly 1.5.0} -> 1.5.0 (c)
+\--- androidx.compose.ui:ui-tooling -> 1.3.3
+
+(*) - dependencies omitted (listed previously)
+";
+
+        let actual = parse_dependency_insight(
+            &mut gradle_output.as_bytes(),
+            "androidx.annotation:annotation",
+        )
+        .unwrap();
+        assert_eq!(3, actual.len());
+        assert_eq!(
+            r"androidx.annotation:annotation:1.1.0 -> 1.5.0
+\--- androidx.core:core-ktx:1.9.0
+     \--- :app releaseRuntimeClasspath
+",
+            actual[0].to_string(),
+        );
+        assert_eq!(
+            r"androidx.annotation:annotation:1.2.0 -> 1.5.0 (*)
+\--- androidx.core:core:1.9.0
+     \--- androidx.core:core-ktx:1.9.0
+          \--- :app releaseRuntimeClasspath
+",
+            actual[1].to_string(),
+        );
+        assert_eq!(
+            Some("{strictly 1.5.0}".to_string()),
+            actual[2].target().requested
+        );
+        assert_eq!(Some("1.5.0".to_string()), actual[2].target().selected);
+        assert_eq!("project :lib", actual[2].nodes[0].name);
+    }
+}
@@ -0,0 +1,108 @@
+/*
+ * Copyright 2023 sukawasatoru
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::model::Coordinate;
+use crate::prelude::*;
+use regex::Regex;
+use std::collections::BTreeSet;
+use std::io::BufRead;
+
+/// Parse the `gradle.lockfile` that is written by `--write-locks`.
+///
+/// The lockfile has all resolved dependencies including the transitive ones.
+/// Only the dependencies of the configurations that match the `configuration_filter` are
+/// returned if specified.
+///
+/// https://docs.gradle.org/current/userguide/dependency_locking.html
+pub fn parse_lockfile<R>(
+    reader: R,
+    configuration_filter: Option<&Regex>,
+) -> Fallible<Vec<Coordinate>>
+where
+    R: BufRead,
+{
+    let mut list = BTreeSet::new();
+    for line in reader.lines() {
+        let line = line?;
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        // `group:artifact:version=configuration1,configuration2`
+        let (coordinate, configurations) = line.split_once('=').unwrap_or((line, ""));
+        if coordinate == "empty" {
+            continue;
+        }
+
+        if let Some(filter) = configuration_filter {
+            if !configurations
+                .split(',')
+                .any(|data| filter.is_match(data.trim()))
+            {
+                continue;
+            }
+        }
+
+        list.insert(
+            coordinate
+                .parse::<Coordinate>()
+                .with_context(|| format!("unexpected line: {}", line))?,
+        );
+    }
+
+    Ok(list.into_iter().collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LOCKFILE: &str = r#"# This is a Gradle generated file for dependency locking.
+# Manual edits can break the build and are not advised.
+# This file is expected to be part of source control.
+androidx.core:core-ktx:1.9.0=debugRuntimeClasspath,releaseRuntimeClasspath
+com.squareup.leakcanary:leakcanary-android:2.10=debugRuntimeClasspath
+org.jetbrains.kotlin:kotlin-stdlib:1.7.10=debugRuntimeClasspath,releaseRuntimeClasspath
+empty=debugAndroidTestCompileClasspath
+"#;
+
+    #[test]
+    fn parse_lockfile_all() {
+        let actual = parse_lockfile(LOCKFILE.as_bytes(), None).unwrap();
+        let expected = [
+            "androidx.core:core-ktx:1.9.0",
+            "com.squareup.leakcanary:leakcanary-android:2.10",
+            "org.jetbrains.kotlin:kotlin-stdlib:1.7.10",
+        ]
+        .map(|data| data.parse::<Coordinate>().unwrap());
+
+        assert_eq!(expected.to_vec(), actual);
+    }
+
+    #[test]
+    fn parse_lockfile_configuration_filter() {
+        let filter = Regex::new("^releaseRuntimeClasspath$").unwrap();
+        let actual = parse_lockfile(LOCKFILE.as_bytes(), Some(&filter)).unwrap();
+        let expected = [
+            "androidx.core:core-ktx:1.9.0",
+            "org.jetbrains.kotlin:kotlin-stdlib:1.7.10",
+        ]
+        .map(|data| data.parse::<Coordinate>().unwrap());
+
+        assert_eq!(expected.to_vec(), actual);
+    }
+}
@@ -0,0 +1,151 @@
+/*
+ * Copyright 2023 sukawasatoru
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::function::maven::POM;
+use crate::prelude::*;
+use serde::Deserialize;
+
+/// Parse the Gradle Module Metadata (`.module`) into the [POM].
+///
+/// The Gradle Module Metadata has no name, description and licenses, so only the component
+/// information and the packaging that is inferred from the published files are available.
+///
+/// https://github.com/gradle/gradle/blob/master/platforms/documentation/docs/src/docs/design/gradle-module-metadata-latest-specification.md
+pub fn parse_module_metadata(json: &str) -> Fallible<POM> {
+    let parsed = serde_json::from_str::<ModuleMetadata>(json)?;
+
+    ensure!(
+        parsed.format_version.starts_with("1."),
+        "unsupported format version: {}",
+        parsed.format_version,
+    );
+
+    Ok(parsed.into())
+}
+
+impl From<ModuleMetadata> for POM {
+    fn from(value: ModuleMetadata) -> Self {
+        let packaging = value
+            .variants
+            .iter()
+            .flat_map(|data| &data.files)
+            .find_map(|data| data.name.rsplit_once('.').map(|(_, ext)| ext.to_owned()));
+
+        Self {
+            group_id: Some(value.component.group),
+            artifact_id: value.component.module,
+            version: Some(value.component.version),
+            packaging,
+            ..Default::default()
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct ModuleMetadata {
+    #[serde(rename = "formatVersion")]
+    format_version: String,
+
+    component: Component,
+
+    #[serde(default)]
+    variants: Vec<Variant>,
+}
+
+#[derive(Deserialize)]
+struct Component {
+    group: String,
+    module: String,
+    version: String,
+}
+
+#[derive(Deserialize)]
+struct Variant {
+    #[serde(default)]
+    files: Vec<File>,
+}
+
+#[derive(Deserialize)]
+struct File {
+    name: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_module_metadata_core_ktx() {
+        let source = r#"{
+  "formatVersion": "1.1",
+  "component": {
+    "group": "androidx.core",
+    "module": "core-ktx",
+    "version": "1.12.0",
+    "attributes": {
+      "org.gradle.status": "release"
+    }
+  },
+  "createdBy": {
+    "gradle": {
+      "version": "8.4-rc-1"
+    }
+  },
+  "variants": [
+    {
+      "name": "releaseVariantReleaseApiPublication",
+      "attributes": {
+        "org.gradle.category": "library",
+        "org.gradle.usage": "java-api"
+      },
+      "files": [
+        {
+          "name": "core-ktx-1.12.0.aar",
+          "url": "core-ktx-1.12.0.aar",
+          "size": 2240,
+          "sha256": "2d4a5fbc5ab4f0d2a9a0a2f9e2d0a3bd5e5c4e8e5d6e0f1f9c1d5b0b2e3a7c11"
+        }
+      ]
+    }
+  ]
+}"#;
+
+        let actual = parse_module_metadata(source).unwrap();
+        let expected = POM {
+            group_id: Some("androidx.core".into()),
+            artifact_id: "core-ktx".into(),
+            version: Some("1.12.0".into()),
+            packaging: Some("aar".into()),
+            ..Default::default()
+        };
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn parse_module_metadata_unsupported_format_version() {
+        let source = r#"{
+  "formatVersion": "2.0",
+  "component": {
+    "group": "androidx.core",
+    "module": "core-ktx",
+    "version": "1.12.0"
+  }
+}"#;
+
+        assert!(parse_module_metadata(source).is_err());
+    }
+}
@@ -15,19 +15,164 @@
  */
 
 use crate::function::gradle::pretty_version;
+use crate::model::Coordinate;
 use crate::prelude::*;
 use once_cell::sync::Lazy;
 use regex::Regex;
-use std::collections::HashSet;
+use std::collections::{BTreeMap, HashSet};
+use std::fmt::{Display, Formatter};
 use std::io::BufRead;
 use tracing::debug_span;
 
+/// Line of the dependency tree that failed to parse.
+#[derive(Debug)]
+pub struct LineError {
+    /// 1-based line number of the input.
+    pub line_number: usize,
+    pub line: String,
+    pub error: anyhow::Error,
+}
+
+impl Display for LineError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "line {}: {:#}: {}",
+            self.line_number, self.error, self.line
+        )
+    }
+}
+
 /// https://docs.gradle.org/current/userguide/viewing_debugging_dependencies.html
-pub fn parse_dependencies_string<R>(reader: &mut R) -> Fallible<Vec<String>>
+pub fn parse_dependencies_string<R>(reader: &mut R) -> Fallible<Vec<Coordinate>>
+where
+    R: BufRead,
+{
+    let list = parse_dependency_trees(reader, None, None)?
+        .into_iter()
+        .filter_map(|(_, entry)| match entry {
+            TreeEntry::Dependency { coordinate, .. } => Some(coordinate),
+            TreeEntry::Project(_) => None,
+        })
+        .collect::<HashSet<_>>();
+
+    let mut list = Vec::from_iter(list);
+    list.sort();
+
+    Ok(list)
+}
+
+/// Same as [parse_dependencies_string] but pairs each dependency with the Gradle modules that
+/// declare it, e.g. `:app`, from the `Project ':app'` sections of the output.
+///
+/// The modules are empty if the output has no project section.
+/// Only the configurations that match the `configuration_filter` are parsed if specified.
+pub fn parse_module_dependencies_string<R>(
+    reader: &mut R,
+    configuration_filter: Option<&Regex>,
+) -> Fallible<Vec<(Coordinate, Vec<String>)>>
+where
+    R: BufRead,
+{
+    Ok(parse_tree_entries(reader, configuration_filter, None)?
+        .into_iter()
+        .filter_map(|(entry, modules)| match entry {
+            TreeEntry::Dependency { coordinate, .. } => Some((coordinate, modules)),
+            TreeEntry::Project(_) => None,
+        })
+        .collect())
+}
+
+/// Entry of the first level of the dependency tree.
+#[derive(Clone, Debug, Eq, Ord, PartialEq, PartialOrd)]
+pub enum TreeEntry {
+    /// External dependency. `constraint` is true if all occurrences are `(c)` of the dependency
+    /// constraint.
+    Dependency {
+        coordinate: Coordinate,
+        constraint: bool,
+    },
+
+    /// `project :lib` of the project dependency.
+    Project(String),
+}
+
+/// Same as [parse_module_dependencies_string] but the entries have the dependency constraints and
+/// the project dependencies that are merged into the external dependencies by it.
+///
+/// The dependencies come first and the projects follow.
+/// The dependency lines that fail to parse are skipped and collected to `errors` if specified,
+/// otherwise the first one is returned as the error.
+pub fn parse_tree_entries<R>(
+    reader: &mut R,
+    configuration_filter: Option<&Regex>,
+    errors: Option<&mut Vec<LineError>>,
+) -> Fallible<Vec<(TreeEntry, Vec<String>)>>
 where
     R: BufRead,
 {
-    let mut list = HashSet::new();
+    fn push_module(modules: &mut Vec<String>, module: Option<String>) {
+        if let Some(module) = module {
+            if !modules.contains(&module) {
+                modules.push(module);
+            }
+        }
+    }
+
+    let mut dependencies = BTreeMap::<Coordinate, (bool, Vec<String>)>::new();
+    let mut projects = BTreeMap::<String, Vec<String>>::new();
+    for (module, entry) in parse_dependency_trees(reader, configuration_filter, errors)? {
+        match entry {
+            TreeEntry::Dependency {
+                coordinate,
+                constraint,
+            } => {
+                let (constraint_only, modules) =
+                    dependencies.entry(coordinate).or_insert((true, vec![]));
+                *constraint_only &= constraint;
+                push_module(modules, module);
+            }
+            TreeEntry::Project(project) => {
+                push_module(projects.entry(project).or_default(), module);
+            }
+        }
+    }
+
+    Ok(dependencies
+        .into_iter()
+        .map(|(coordinate, (constraint, modules))| {
+            (
+                TreeEntry::Dependency {
+                    coordinate,
+                    constraint,
+                },
+                modules,
+            )
+        })
+        .chain(
+            projects
+                .into_iter()
+                .map(|(project, modules)| (TreeEntry::Project(project), modules)),
+        )
+        .collect())
+}
+
+/// Returns the first level dependencies with the module of the project section.
+///
+/// The trees of all configurations are parsed. The tree that has no configuration name is
+/// parsed regardless of the `configuration_filter`.
+fn parse_dependency_trees<R>(
+    reader: &mut R,
+    configuration_filter: Option<&Regex>,
+    mut errors: Option<&mut Vec<LineError>>,
+) -> Fallible<Vec<(Option<String>, TreeEntry)>>
+where
+    R: BufRead,
+{
+    let mut list = vec![];
+    let mut line_number = 0usize;
+    let mut current_module = None;
+    let mut selected = true;
     let mut found_start = false;
     let mut end = false;
     let mut current_level = 0usize;
@@ -35,7 +180,10 @@ where
         let mut line = String::new();
         let line = match reader.read_line(&mut line) {
             Ok(0) => break,
-            Ok(_) => line.trim_end(),
+            Ok(_) => {
+                line_number += 1;
+                line.trim_end()
+            }
             Err(e) => {
                 debug!(?e);
                 bail!("failed to read lines: {}", e);
@@ -45,9 +193,33 @@ where
         let line_span = debug_span!("", %line);
         let _enter = line_span.enter();
 
+        if let Some(module) = project_name(line) {
+            // the tree of the next project follows.
+            debug!(%module, "found project");
+            current_module = Some(module);
+            found_start = false;
+            end = false;
+            current_level = 0;
+            selected = true;
+            continue;
+        }
+
         let line_level = calculate_level(line)?;
         debug!(?line_level);
 
+        if line_level.is_none() {
+            if let Some(configuration) = configuration_name(line) {
+                // the tree of the next configuration follows.
+                selected =
+                    configuration_filter.map_or(true, |filter| filter.is_match(&configuration));
+                debug!(%configuration, selected, "found configuration");
+                found_start = false;
+                end = false;
+                current_level = 0;
+                continue;
+            }
+        }
+
         if !found_start || end {
             match line_level {
                 Some(0) => {
@@ -70,9 +242,15 @@ where
             }
         };
 
-        if line.contains("--- project ") {
+        if let Some(project) = project_dependency(line) {
             // \--- project :hoge
             //      \--- xxx:yyy:zzz
+            if current_level < line_level {
+                continue;
+            }
+            if selected {
+                list.push((current_module.clone(), TreeEntry::Project(project)));
+            }
             current_level = line_level + 1;
             continue;
         }
@@ -89,16 +267,70 @@ where
         // \--- xxx:yyy:zzz
         current_level = line_level;
 
-        list.insert(pretty_name(line).context("unexpected format")?);
-    }
+        if !selected {
+            continue;
+        }
 
-    let mut list = Vec::from_iter(list);
-    list.sort();
+        let coordinate = match pretty_name(line)
+            .and_then(|data| data.parse::<Coordinate>())
+            .and_then(|data| data.validate().map(|_| data))
+        {
+            Ok(data) => data,
+            Err(error) => match errors.as_mut() {
+                Some(errors) => {
+                    debug!(?error, "skip the line");
+                    errors.push(LineError {
+                        line_number,
+                        line: line.into(),
+                        error,
+                    });
+                    continue;
+                }
+                None => {
+                    return Err(error.context(format!("line {}: {}", line_number, line)));
+                }
+            },
+        };
+        list.push((
+            current_module.clone(),
+            TreeEntry::Dependency {
+                coordinate,
+                constraint: line.ends_with(" (c)"),
+            },
+        ));
+    }
 
     Ok(list)
 }
 
-fn calculate_level(line: &str) -> Fallible<Option<usize>> {
+/// `releaseRuntimeClasspath - Runtime classpath of ...` of the header of the configuration.
+pub(super) fn configuration_name(line: &str) -> Option<String> {
+    static REG: Lazy<Regex> =
+        Lazy::new(|| Regex::new(r"^([A-Za-z][A-Za-z0-9_]*) - .+$").expect("invalid pattern"));
+
+    REG.captures(line).map(|data| data[1].into())
+}
+
+/// `Root project 'name'` or `Project ':app'` of the header of the project section.
+pub(super) fn project_name(line: &str) -> Option<String> {
+    static REG: Lazy<Regex> =
+        Lazy::new(|| Regex::new(r"^(Root project|Project) '([^']*)'").expect("invalid pattern"));
+
+    REG.captures(line).map(|data| match &data[1] {
+        "Root project" => ":".into(),
+        _ => data[2].into(),
+    })
+}
+
+/// `+--- project :lib` of the project dependency.
+fn project_dependency(line: &str) -> Option<String> {
+    static REG: Lazy<Regex> =
+        Lazy::new(|| Regex::new(r"[+\\]--- project (\S+)").expect("invalid pattern"));
+
+    REG.captures(line).map(|data| data[1].into())
+}
+
+pub(super) fn calculate_level(line: &str) -> Fallible<Option<usize>> {
     line.find("--- ")
         .map(|data| {
             // -1 for `+--- ` or `\--- `.
@@ -109,12 +341,13 @@ fn calculate_level(line: &str) -> Fallible<Option<usize>> {
         .map_or(Ok(None), |v| v.map(Some))
 }
 
-fn pretty_name(line: &str) -> Option<String> {
+fn pretty_name(line: &str) -> Fallible<String> {
     static REG: Lazy<Regex> = Lazy::new(|| Regex::new(r"[+\\]--- (.*)$").expect("invalid pattern"));
 
     REG.captures(line)
         .and_then(|data| data.get(1).map(|data| data.as_str()))
-        .map(pretty_version)
+        .context("unexpected format")
+        .and_then(pretty_version)
 }
 
 #[cfg(test)]
@@ -131,8 +364,7 @@ mod tests {
 
     #[test]
     fn pretty_name_glide() {
-        let actual =
-            pretty_name(r#"|         \--- com.github.bumptech.glide:glide:4.15.1"#).unwrap();
+        let actual = pretty_name(r"|         \--- com.github.bumptech.glide:glide:4.15.1").unwrap();
         assert_eq!(actual, "com.github.bumptech.glide:glide:4.15.1");
     }
 
@@ -142,9 +374,226 @@ mod tests {
         assert_eq!(actual, "androidx.compose.ui:ui-tooling:1.3.3");
     }
 
+    #[test]
+    fn project_name_app() {
+        assert_eq!(Some(":app".into()), project_name("Project ':app'"));
+        assert_eq!(
+            Some(":feature:home".into()),
+            project_name("Project ':feature:home' - Home screen"),
+        );
+        assert_eq!(
+            Some(":".into()),
+            project_name("Root project 'android-template'")
+        );
+        assert_eq!(
+            None,
+            project_name("Project accessors enabled, but root project name not explicitly set"),
+        );
+    }
+
+    #[test]
+    fn configuration_name_release_runtime_classpath() {
+        assert_eq!(
+            Some("releaseRuntimeClasspath".into()),
+            configuration_name(
+                "releaseRuntimeClasspath - Resolved configuration for runtime for variant: release",
+            ),
+        );
+        // the bare word such as the log of the task is not the header.
+        assert_eq!(None, configuration_name("debugCompileClasspath"));
+        assert_eq!(None, configuration_name("Done"));
+        assert_eq!(None, configuration_name("No dependencies"));
+        assert_eq!(None, configuration_name("BUILD SUCCESSFUL in 1s"));
+        assert_eq!(
+            None,
+            configuration_name("(*) - dependencies omitted (listed previously)"),
+        );
+    }
+
+    #[test]
+    fn parse_module_dependencies_string_configuration_filter() {
+        let gradle_output = r"
+------------------------------------------------------------
+Project ':app'
+------------------------------------------------------------
+
+debugRuntimeClasspath - Resolved configuration for runtime for variant: debug
++--- androidx.core:core-ktx:1.9.0
+\--- com.squareup.leakcanary:leakcanary-android:2.10
+
+releaseCompileClasspath - Resolved configuration for compilation for variant: release
+No dependencies
+
+releaseRuntimeClasspath - Resolved configuration for runtime for variant: release
+\--- androidx.core:core-ktx:1.9.0
+
+(*) - dependencies omitted (listed previously)
+";
+
+        let filter = Regex::new("^release.*RuntimeClasspath$").unwrap();
+        let actual =
+            parse_module_dependencies_string(&mut gradle_output.as_bytes(), Some(&filter)).unwrap();
+        let expected = vec![(
+            "androidx.core:core-ktx:1.9.0"
+                .parse::<Coordinate>()
+                .unwrap(),
+            vec![":app".to_string()],
+        )];
+        assert_eq!(actual, expected);
+
+        let actual = parse_dependencies_string(&mut gradle_output.as_bytes()).unwrap();
+        let expected = [
+            "androidx.core:core-ktx:1.9.0",
+            "com.squareup.leakcanary:leakcanary-android:2.10",
+        ]
+        .map(|data| data.parse::<Coordinate>().unwrap());
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn parse_module_dependencies_string_multiple_projects() {
+        let gradle_output = r"
+> Task :app:dependencies
+
+------------------------------------------------------------
+Project ':app'
+------------------------------------------------------------
+
+releaseRuntimeClasspath - Resolved configuration for runtime for variant: release
++--- project :lib
+|    \--- androidx.core:core-ktx:1.9.0
++--- androidx.core:core-ktx:1.9.0
+\--- com.github.bumptech.glide:glide:4.15.1
+
+(*) - dependencies omitted (listed previously)
+
+> Task :lib:dependencies
+
+------------------------------------------------------------
+Project ':lib'
+------------------------------------------------------------
+
+releaseRuntimeClasspath - Resolved configuration for runtime for variant: release
++--- androidx.core:core-ktx:1.9.0
+\--- org.jetbrains.kotlin:kotlin-stdlib:1.7.10
+
+(*) - dependencies omitted (listed previously)
+";
+
+        let actual = parse_module_dependencies_string(&mut gradle_output.as_bytes(), None).unwrap();
+        let expected = vec![
+            (
+                "androidx.core:core-ktx:1.9.0"
+                    .parse::<Coordinate>()
+                    .unwrap(),
+                vec![":app".to_string(), ":lib".to_string()],
+            ),
+            (
+                "com.github.bumptech.glide:glide:4.15.1".parse().unwrap(),
+                vec![":app".to_string()],
+            ),
+            (
+                "org.jetbrains.kotlin:kotlin-stdlib:1.7.10".parse().unwrap(),
+                vec![":lib".to_string()],
+            ),
+        ];
+
+        assert_eq!(actual, expected);
+
+        let actual = parse_dependencies_string(&mut gradle_output.as_bytes()).unwrap();
+        let expected = [
+            "androidx.core:core-ktx:1.9.0",
+            "com.github.bumptech.glide:glide:4.15.1",
+            "org.jetbrains.kotlin:kotlin-stdlib:1.7.10",
+        ]
+        .map(|data| data.parse::<Coordinate>().unwrap());
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn parse_tree_entries_errors() {
+        let gradle_output = r"
+releaseRuntimeClasspath - Resolved configuration for runtime for variant: release
++--- androidx.core:core-ktx:1.9.0
++--- org.lwjgl:lwjgl:3.3.3:natives-linux:unexpected
+\--- com.github.bumptech.glide:glide:4.15.1
+";
+
+        assert!(parse_tree_entries(&mut gradle_output.as_bytes(), None, None).is_err());
+
+        let mut errors = vec![];
+        let actual = parse_tree_entries(&mut gradle_output.as_bytes(), None, Some(&mut errors))
+            .unwrap()
+            .into_iter()
+            .map(|(entry, _)| entry)
+            .collect::<Vec<_>>();
+        let expected = [
+            "androidx.core:core-ktx:1.9.0",
+            "com.github.bumptech.glide:glide:4.15.1",
+        ]
+        .map(|data| TreeEntry::Dependency {
+            coordinate: data.parse().unwrap(),
+            constraint: false,
+        });
+        assert_eq!(expected.to_vec(), actual);
+        assert_eq!(1, errors.len());
+        assert_eq!(4, errors[0].line_number);
+    }
+
+    #[test]
+    fn parse_tree_entries_constraints_and_projects() {
+        let gradle_output = r"
+------------------------------------------------------------
+Project ':app'
+------------------------------------------------------------
+
+releaseRuntimeClasspath - Resolved configuration for runtime for variant: release
++--- project :lib
+|    +--- androidx.core:core-ktx:1.9.0
+|    \--- project :liblib
+|         \--- com.github.bumptech.glide:glide:4.15.1
++--- androidx.core:core-ktx:1.9.0 (c)
+\--- androidx.lifecycle:lifecycle-common-java8:2.5.1 (c)
+
+(*) - dependencies omitted (listed previously)
+";
+
+        let actual = parse_tree_entries(&mut gradle_output.as_bytes(), None, None).unwrap();
+        let app = vec![":app".to_string()];
+        let expected = vec![
+            (
+                TreeEntry::Dependency {
+                    coordinate: "androidx.core:core-ktx:1.9.0".parse().unwrap(),
+                    constraint: false,
+                },
+                app.clone(),
+            ),
+            (
+                TreeEntry::Dependency {
+                    coordinate: "androidx.lifecycle:lifecycle-common-java8:2.5.1"
+                        .parse()
+                        .unwrap(),
+                    constraint: true,
+                },
+                app.clone(),
+            ),
+            (
+                TreeEntry::Dependency {
+                    coordinate: "com.github.bumptech.glide:glide:4.15.1".parse().unwrap(),
+                    constraint: false,
+                },
+                app.clone(),
+            ),
+            (TreeEntry::Project(":lib".into()), app.clone()),
+            (TreeEntry::Project(":liblib".into()), app),
+        ];
+        assert_eq!(actual, expected);
+    }
+
     #[test]
     fn parse_dependencies_string_app_release_runtime_classpath() {
-        let gradle_output = r#"
+        let gradle_output = r"
 Starting a Gradle Daemon (subsequent builds will be faster)
 Type-safe project accessors is an incubating feature.
 Project accessors enabled, but root project name not explicitly set for 'android-template'. Checking out the project in different folders will impact the generated code and implicitly the buildscript classpath, breaking caching.
@@ -472,7 +921,7 @@ See https://docs.gradle.org/7.6/userguide/command_line_interface.html#sec:comman
 
 BUILD SUCCESSFUL in 4s
 1 actionable task: 1 executed
-"#;
+";
 
         // tracing_subscriber::fmt()
         //     .with_max_level(tracing::Level::TRACE)
@@ -480,24 +929,25 @@ BUILD SUCCESSFUL in 4s
         //     .without_time()
         //     .init();
         let actual = parse_dependencies_string(&mut gradle_output.as_bytes()).unwrap();
-        let expected = vec![
-            "androidx.activity:activity-compose:1.6.1".to_owned(),
-            "androidx.compose.material:material:1.3.1".into(),
-            "androidx.compose.ui:ui-tooling:1.3.3".into(),
-            "androidx.compose:compose-bom:2023.01.00".into(),
-            "androidx.core:core-ktx:1.9.0".into(),
-            "androidx.profileinstaller:profileinstaller:1.3.0".into(),
-            "com.github.bumptech.glide:glide:4.15.1".into(),
-            "com.squareup.okhttp3:okhttp:4.9.3".into(),
-            "org.jetbrains.kotlin:kotlin-stdlib-jdk8:1.6.21".into(),
-        ];
+        let expected = [
+            "androidx.activity:activity-compose:1.6.1",
+            "androidx.compose:compose-bom:2023.01.00",
+            "androidx.compose.material:material:1.3.1",
+            "androidx.compose.ui:ui-tooling:1.3.3",
+            "androidx.core:core-ktx:1.9.0",
+            "androidx.profileinstaller:profileinstaller:1.3.0",
+            "com.github.bumptech.glide:glide:4.15.1",
+            "com.squareup.okhttp3:okhttp:4.9.3",
+            "org.jetbrains.kotlin:kotlin-stdlib-jdk8:1.6.21",
+        ]
+        .map(|data| data.parse::<Coordinate>().unwrap());
 
         assert_eq!(actual, expected);
     }
 
     #[test]
     fn parse_dependencies_string_app2_release_runtime_classpath() {
-        let gradle_output = r#"
+        let gradle_output = r"
 Type-safe project accessors is an incubating feature.
 Project accessors enabled, but root project name not explicitly set for 'android-template'. Checking out the project in different folders will impact the generated code and implicitly the buildscript classpath, breaking caching.
 
@@ -623,7 +1073,7 @@ See https://docs.gradle.org/7.6/userguide/command_line_interface.html#sec:comman
 
 BUILD SUCCESSFUL in 559ms
 1 actionable task: 1 executed
-"#;
+";
 
         // tracing_subscriber::fmt()
         //     .with_max_level(tracing::Level::TRACE)
@@ -632,19 +1082,20 @@ BUILD SUCCESSFUL in 559ms
         //     .init();
 
         let actual = parse_dependencies_string(&mut gradle_output.as_bytes()).unwrap();
-        let expected = vec![
-            "androidx.core:core-ktx:1.9.0".into(),
-            "com.github.bumptech.glide:glide:4.15.1".into(),
-            "com.squareup.okhttp3:okhttp:4.9.3".into(),
-            "org.jetbrains.kotlin:kotlin-stdlib-jdk8:1.6.21".to_owned(),
-        ];
+        let expected = [
+            "androidx.core:core-ktx:1.9.0",
+            "com.github.bumptech.glide:glide:4.15.1",
+            "com.squareup.okhttp3:okhttp:4.9.3",
+            "org.jetbrains.kotlin:kotlin-stdlib-jdk8:1.6.21",
+        ]
+        .map(|data| data.parse::<Coordinate>().unwrap());
 
         assert_eq!(actual, expected);
     }
 
     #[test]
     fn parse_dependencies_string_release_runtime_classpath() {
-        let gradle_output = r#"
+        let gradle_output = r"
 releaseRuntimeClasspath - Runtime classpath of compilation 'release' (target  (androidJvm)).
 +--- org.jetbrains.kotlin:kotlin-stdlib-jdk8:1.6.21
 |    +--- org.jetbrains.kotlin:kotlin-stdlib:1.6.21 -> 1.7.10
@@ -895,17 +1346,18 @@ releaseRuntimeClasspath - Runtime classpath of compilation 'release' (target  (a
 |    \--- org.jetbrains.kotlin:kotlin-stdlib:1.7.10 (*)
 +--- androidx.compose.material:material -> 1.3.1 (*)
 \--- androidx.profileinstaller:profileinstaller:1.3.0 (*)
-"#;
+";
 
         let actual = parse_dependencies_string(&mut gradle_output.as_bytes()).unwrap();
-        let expected = vec![
-            "androidx.activity:activity-compose:1.6.1".to_owned(),
-            "androidx.compose.material:material:1.3.1".into(),
-            "androidx.compose.ui:ui-tooling:1.3.3".into(),
-            "androidx.compose:compose-bom:2023.01.00".into(),
-            "androidx.profileinstaller:profileinstaller:1.3.0".into(),
-            "org.jetbrains.kotlin:kotlin-stdlib-jdk8:1.6.21".into(),
-        ];
+        let expected = [
+            "androidx.activity:activity-compose:1.6.1",
+            "androidx.compose:compose-bom:2023.01.00",
+            "androidx.compose.material:material:1.3.1",
+            "androidx.compose.ui:ui-tooling:1.3.3",
+            "androidx.profileinstaller:profileinstaller:1.3.0",
+            "org.jetbrains.kotlin:kotlin-stdlib-jdk8:1.6.21",
+        ]
+        .map(|data| data.parse::<Coordinate>().unwrap());
 
         assert_eq!(actual, expected);
     }
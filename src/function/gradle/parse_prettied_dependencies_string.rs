@@ -14,32 +14,91 @@
  * limitations under the License.
  */
 
-use crate::function::gradle::pretty_version;
+use crate::function::gradle::{pretty_version, LineError};
+use crate::model::{Annotation, Coordinate};
 use crate::prelude::*;
-use std::collections::HashSet;
+use std::collections::BTreeMap;
 use std::io::prelude::*;
 
-pub fn parse_prettied_dependencies_string<R>(mut reader: R) -> Fallible<Vec<String>>
+/// Parse the dependency per line.
+///
+/// A line can have the [Annotation] after ` # ` and can be the purl. e.g.:
+///
+/// ```text
+/// androidx.core:core-ktx:1.9.0 # [flavor-x] bundled only in flavor X
+/// pkg:maven/com.github.bumptech.glide/glide@4.15.1
+/// ```
+pub fn parse_prettied_dependencies_string<R>(reader: R) -> Fallible<Vec<(Coordinate, Annotation)>>
 where
     R: BufRead,
 {
-    let mut list = HashSet::new();
+    parse_prettied_entries(reader, None)
+}
+
+/// Same as [parse_prettied_dependencies_string] but the line that is malformed such as the
+/// invalid character and the stray whitespace is skipped and collected to `errors` if specified,
+/// otherwise the first one is returned as the error.
+pub fn parse_prettied_entries<R>(
+    mut reader: R,
+    mut errors: Option<&mut Vec<LineError>>,
+) -> Fallible<Vec<(Coordinate, Annotation)>>
+where
+    R: BufRead,
+{
+    let mut list = BTreeMap::<Coordinate, Annotation>::new();
+    let mut line_number = 0usize;
     loop {
         let mut line = String::new();
         match reader.read_line(&mut line) {
             Ok(0) => break,
             Ok(_) => {
-                let line = line.trim();
-                if line.is_empty() {
+                line_number += 1;
+                let (source, annotation) = match line.split_once(" # ") {
+                    Some((line, annotation)) => (line.trim(), annotation.parse::<Annotation>()?),
+                    None => (line.trim(), Annotation::default()),
+                };
+                if source.is_empty() {
                     continue;
                 }
-                let line = if line.split(':').collect::<Vec<_>>().len() == 3 {
-                    pretty_version(line)
-                } else {
-                    line.to_owned()
+
+                let parse_line = || -> Fallible<Coordinate> {
+                    ensure!(
+                        !has_stray_whitespace(source),
+                        "stray whitespace: {}",
+                        source
+                    );
+                    let source = if source.split(':').collect::<Vec<_>>().len() == 3 {
+                        pretty_version(source)?
+                    } else {
+                        source.to_owned()
+                    };
+                    let coordinate = source.parse::<Coordinate>()?;
+                    coordinate.validate()?;
+                    Ok(coordinate)
+                };
+                let coordinate = match parse_line() {
+                    Ok(data) => data,
+                    Err(error) => match errors.as_mut() {
+                        Some(errors) => {
+                            debug!(?error, "skip the line");
+                            errors.push(LineError {
+                                line_number,
+                                line: line.trim_end().into(),
+                                error,
+                            });
+                            continue;
+                        }
+                        None => {
+                            return Err(error.context(format!(
+                                "line {}: {}",
+                                line_number,
+                                line.trim_end()
+                            )));
+                        }
+                    },
                 };
 
-                list.insert(line);
+                list.entry(coordinate).or_default().merge(annotation);
             }
             Err(e) => {
                 debug!(?e);
@@ -48,16 +107,57 @@ where
         }
     }
 
-    let mut list = Vec::from_iter(list);
-    list.sort();
+    Ok(Vec::from_iter(list))
+}
 
-    Ok(list)
+/// Returns true if the `source` has the whitespace other than the decorations of Gradle such as
+/// ` -> 1.4.0 (*)` and the rich version such as `{strictly 1.6.10}`.
+fn has_stray_whitespace(source: &str) -> bool {
+    let decorations = match source.split_once('{') {
+        Some((_, rich_version)) => match rich_version.split_once('}') {
+            Some((_, rest)) => rest,
+            None => return true,
+        },
+        None => match source.split_once(char::is_whitespace) {
+            Some((_, rest)) => rest,
+            None => return false,
+        },
+    };
+    !matches!(
+        decorations
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .as_slice(),
+        [] | ["(*)" | "(c)" | "(n)"] | ["->", _] | ["->", _, "(*)" | "(c)" | "(n)"]
+    )
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn parse_prettied_entries_errors() {
+        let lines = r#"
+androidx.core:core-ktx:1.9.0
+androidx.core: core:1.9.0
+androidx.core:core:1.9.0 -> 1.10.0 oops
+com.example:lib$:1.0.0
+com.google.guava:guava:32.1.2-android
+"#;
+        let mut errors = vec![];
+        let actual = parse_prettied_entries(&mut lines.as_bytes(), Some(&mut errors)).unwrap();
+        assert_eq!(2, actual.len());
+        assert_eq!(
+            vec![3, 4, 5],
+            errors
+                .iter()
+                .map(|data| data.line_number)
+                .collect::<Vec<_>>(),
+        );
+        assert!(parse_prettied_dependencies_string(&mut lines.as_bytes()).is_err());
+    }
+
     #[test]
     fn parse_prettied_dependencies_string_without_version() {
         let lines = r#"
@@ -68,16 +168,44 @@ androidx.annotation:annotation
 androidx.annotation:annotation-experimental
 androidx.appcompat:appcompat
 androidx.appcompat:appcompat-resources
+"#;
+        let actual = parse_prettied_dependencies_string(&mut lines.as_bytes())
+            .unwrap()
+            .into_iter()
+            .map(|(coordinate, _)| coordinate)
+            .collect::<Vec<_>>();
+        let expected = [
+            "androidx.activity:activity",
+            "androidx.activity:activity-compose",
+            "androidx.activity:activity-ktx",
+            "androidx.annotation:annotation",
+            "androidx.annotation:annotation-experimental",
+            "androidx.appcompat:appcompat",
+            "androidx.appcompat:appcompat-resources",
+        ]
+        .map(|data| data.parse::<Coordinate>().unwrap());
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn parse_prettied_dependencies_string_purl() {
+        let lines = r#"
+pkg:maven/androidx.core/core-ktx@1.9.0 # [ui]
+com.github.bumptech.glide:glide:4.15.1
 "#;
         let actual = parse_prettied_dependencies_string(&mut lines.as_bytes()).unwrap();
         let expected = vec![
-            "androidx.activity:activity".to_owned(),
-            "androidx.activity:activity-compose".into(),
-            "androidx.activity:activity-ktx".into(),
-            "androidx.annotation:annotation".into(),
-            "androidx.annotation:annotation-experimental".into(),
-            "androidx.appcompat:appcompat".into(),
-            "androidx.appcompat:appcompat-resources".into(),
+            (
+                "androidx.core:core-ktx:1.9.0"
+                    .parse::<Coordinate>()
+                    .unwrap(),
+                "[ui]".parse::<Annotation>().unwrap(),
+            ),
+            (
+                "com.github.bumptech.glide:glide:4.15.1".parse().unwrap(),
+                Annotation::default(),
+            ),
         ];
 
         assert_eq!(actual, expected);
@@ -105,16 +233,46 @@ androidx.annotation:annotation:1.3.0
 androidx.appcompat:appcompat-resources:1.2.0
 androidx.appcompat:appcompat:1.1.0 -> 1.2.0 (*)
 androidx.appcompat:appcompat:1.2.0
+"#;
+        let actual = parse_prettied_dependencies_string(&mut lines.as_bytes())
+            .unwrap()
+            .into_iter()
+            .map(|(coordinate, _)| coordinate)
+            .collect::<Vec<_>>();
+        let expected = [
+            "androidx.activity:activity:1.4.0",
+            "androidx.activity:activity-compose:1.4.0",
+            "androidx.activity:activity-ktx:1.4.0",
+            "androidx.annotation:annotation:1.3.0",
+            "androidx.annotation:annotation-experimental:1.1.0",
+            "androidx.appcompat:appcompat:1.2.0",
+            "androidx.appcompat:appcompat-resources:1.2.0",
+        ]
+        .map(|data| data.parse::<Coordinate>().unwrap());
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn parse_prettied_dependencies_string_with_annotation() {
+        let lines = r#"
+androidx.activity:activity:1.4.0 # [flavor-x] bundled only in flavor X
+androidx.activity:activity:1.3.1 -> 1.4.0 # [ui]
+androidx.appcompat:appcompat:1.2.0
 "#;
         let actual = parse_prettied_dependencies_string(&mut lines.as_bytes()).unwrap();
         let expected = vec![
-            "androidx.activity:activity-compose:1.4.0".to_owned(),
-            "androidx.activity:activity-ktx:1.4.0".into(),
-            "androidx.activity:activity:1.4.0".into(),
-            "androidx.annotation:annotation-experimental:1.1.0".into(),
-            "androidx.annotation:annotation:1.3.0".into(),
-            "androidx.appcompat:appcompat-resources:1.2.0".into(),
-            "androidx.appcompat:appcompat:1.2.0".into(),
+            (
+                "androidx.activity:activity:1.4.0".parse().unwrap(),
+                Annotation {
+                    tags: vec!["flavor-x".into(), "ui".into()],
+                    note: Some("bundled only in flavor X".into()),
+                },
+            ),
+            (
+                "androidx.appcompat:appcompat:1.2.0".parse().unwrap(),
+                Annotation::default(),
+            ),
         ];
 
         assert_eq!(actual, expected);
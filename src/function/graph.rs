@@ -0,0 +1,347 @@
+/*
+ * Copyright 2023 sukawasatoru
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Dependency graph of the Gradle output that the nodes are colored by the license.
+
+use crate::function::compatibility::LicenseFamily;
+use crate::function::gradle::{DependencyNode, DependencyTree};
+use crate::model::{LicenseConjunction, LicenseOrigin, SPDX};
+use crate::prelude::*;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::io::Write;
+
+/// Color of the node that is decided by the most restrictive license of the artifact.
+#[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd)]
+pub enum NodeColor {
+    Permissive,
+    WeakCopyleft,
+    Copyleft,
+    Proprietary,
+
+    /// The license is not recognized or the artifact is not resolved.
+    Unknown,
+
+    /// `project :lib` of the project dependency.
+    Project,
+}
+
+impl NodeColor {
+    pub fn of(licenses: Option<&[SPDX]>) -> Self {
+        let licenses = match licenses {
+            Some(data) if !data.is_empty() => data,
+            _ => return Self::Unknown,
+        };
+        licenses
+            .iter()
+            .map(|data| match LicenseFamily::of(data) {
+                LicenseFamily::Permissive => Self::Permissive,
+                LicenseFamily::WeakCopyleft => Self::WeakCopyleft,
+                LicenseFamily::StrongCopyleft | LicenseFamily::NetworkCopyleft => Self::Copyleft,
+                LicenseFamily::Proprietary => Self::Proprietary,
+                LicenseFamily::Unknown => Self::Unknown,
+            })
+            .max_by_key(|data| data.severity())
+            .unwrap_or(Self::Unknown)
+    }
+
+    fn severity(&self) -> u8 {
+        match self {
+            Self::Project => 0,
+            Self::Permissive => 1,
+            Self::WeakCopyleft => 2,
+            Self::Unknown => 3,
+            Self::Proprietary => 4,
+            Self::Copyleft => 5,
+        }
+    }
+
+    /// Class name of the Mermaid.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Permissive => "permissive",
+            Self::WeakCopyleft => "weakCopyleft",
+            Self::Copyleft => "copyleft",
+            Self::Proprietary => "proprietary",
+            Self::Unknown => "unknown",
+            Self::Project => "project",
+        }
+    }
+
+    /// Fill color of the node.
+    pub fn rgb(&self) -> &'static str {
+        match self {
+            Self::Permissive => "#c8e6c9",
+            Self::WeakCopyleft => "#fff59d",
+            Self::Copyleft => "#ef9a9a",
+            Self::Proprietary => "#ffcc80",
+            Self::Unknown => "#e0e0e0",
+            Self::Project => "#bbdefb",
+        }
+    }
+}
+
+/// Licenses of the artifacts keyed by `group:artifact:version` and the origin of the licenses
+/// that are not from the POM.
+pub type LicenseMap<'a> = HashMap<String, (&'a [SPDX], Option<&'a LicenseOrigin>)>;
+
+/// Node and edge lists that merge the same artifacts of the trees.
+struct Graph {
+    /// `group:artifact:version` or the root such as `:app releaseRuntimeClasspath`.
+    nodes: Vec<(String, String, NodeColor)>,
+    edges: BTreeSet<(usize, usize)>,
+}
+
+impl Graph {
+    fn new(
+        trees: &[DependencyTree],
+        licenses: &LicenseMap,
+        conjunction: LicenseConjunction,
+    ) -> Self {
+        let mut graph = Self {
+            nodes: vec![],
+            edges: BTreeSet::new(),
+        };
+        let mut ids = HashMap::<String, usize>::new();
+        let mut node_id = |graph: &mut Graph, key: String, label: String, color: NodeColor| {
+            *ids.entry(key.clone()).or_insert_with(|| {
+                graph.nodes.push((key, label, color));
+                graph.nodes.len() - 1
+            })
+        };
+
+        for tree in trees {
+            let root = [tree.module.as_deref(), tree.configuration.as_deref()]
+                .into_iter()
+                .flatten()
+                .collect::<Vec<_>>()
+                .join(" ");
+            let root_id = node_id(&mut graph, root.clone(), root, NodeColor::Project);
+            for node in &tree.roots {
+                node.walk(&mut |ancestors, node| {
+                    let id = Self::node_id(&mut graph, &mut node_id, node, licenses, conjunction);
+                    let parent_id = match ancestors.last() {
+                        Some(parent) => {
+                            Self::node_id(&mut graph, &mut node_id, parent, licenses, conjunction)
+                        }
+                        None => root_id,
+                    };
+                    graph.edges.insert((parent_id, id));
+                });
+            }
+        }
+
+        graph
+    }
+
+    fn node_id<F>(
+        graph: &mut Graph,
+        node_id: &mut F,
+        node: &DependencyNode,
+        licenses: &LicenseMap,
+        conjunction: LicenseConjunction,
+    ) -> usize
+    where
+        F: FnMut(&mut Graph, String, String, NodeColor) -> usize,
+    {
+        let key = node.selected_name();
+        if node.is_project() {
+            return node_id(graph, key.clone(), key, NodeColor::Project);
+        }
+        let (node_licenses, origin) = match licenses.get(&key) {
+            Some((data, origin)) => (Some(*data), *origin),
+            None => (None, None),
+        };
+        let label = match (node_licenses, origin) {
+            (Some(data), Some(origin)) if !data.is_empty() => {
+                format!("{}\n{} ({})", key, conjunction.join(data), origin.label())
+            }
+            (Some(data), None) if !data.is_empty() => {
+                format!("{}\n{}", key, conjunction.join(data))
+            }
+            _ => format!("{}\nunknown", key),
+        };
+        node_id(graph, key, label, NodeColor::of(node_licenses))
+    }
+}
+
+/// Write the Graphviz DOT of the dependency `trees` that the nodes are colored by the `licenses`.
+///
+/// https://graphviz.org/doc/info/lang.html
+pub fn write_dot<W: Write>(
+    mut writer: W,
+    trees: &[DependencyTree],
+    licenses: &LicenseMap,
+    conjunction: LicenseConjunction,
+) -> Fallible<()> {
+    let graph = Graph::new(trees, licenses, conjunction);
+
+    writeln!(writer, "digraph dependencies {{")?;
+    writeln!(writer, "  rankdir=LR;")?;
+    writeln!(writer, "  node [shape=box, style=filled];")?;
+    for (index, (_, label, color)) in graph.nodes.iter().enumerate() {
+        writeln!(
+            writer,
+            "  n{} [label=\"{}\", fillcolor=\"{}\"];",
+            index,
+            escape_dot(label),
+            color.rgb(),
+        )?;
+    }
+    for (from, to) in &graph.edges {
+        writeln!(writer, "  n{} -> n{};", from, to)?;
+    }
+    writeln!(writer, "}}")?;
+
+    writer.flush()?;
+
+    Ok(())
+}
+
+/// Write the Mermaid flowchart of the dependency `trees` that the nodes are colored by the
+/// `licenses`.
+///
+/// https://mermaid.js.org/syntax/flowchart.html
+pub fn write_mermaid<W: Write>(
+    mut writer: W,
+    trees: &[DependencyTree],
+    licenses: &LicenseMap,
+    conjunction: LicenseConjunction,
+) -> Fallible<()> {
+    let graph = Graph::new(trees, licenses, conjunction);
+
+    writeln!(writer, "flowchart LR")?;
+    for (index, (_, label, _)) in graph.nodes.iter().enumerate() {
+        writeln!(writer, "  n{}[\"{}\"]", index, escape_mermaid(label))?;
+    }
+    for (from, to) in &graph.edges {
+        writeln!(writer, "  n{} --> n{}", from, to)?;
+    }
+
+    let mut classes = BTreeMap::<NodeColor, Vec<String>>::new();
+    for (index, (_, _, color)) in graph.nodes.iter().enumerate() {
+        classes
+            .entry(*color)
+            .or_default()
+            .push(format!("n{}", index));
+    }
+    for (color, ids) in classes {
+        writeln!(writer, "  classDef {} fill:{}", color.name(), color.rgb())?;
+        writeln!(writer, "  class {} {}", ids.join(","), color.name())?;
+    }
+
+    writer.flush()?;
+
+    Ok(())
+}
+
+fn escape_dot(label: &str) -> String {
+    label
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+fn escape_mermaid(label: &str) -> String {
+    label.replace('"', "#quot;").replace('\n', "<br/>")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::function::gradle::parse_dependency_tree;
+
+    fn trees() -> Vec<DependencyTree> {
+        parse_dependency_tree(
+            &mut r"
+releaseRuntimeClasspath - Resolved configuration for runtime for variant: release
++--- androidx.core:core:1.9.0
+|    \--- androidx.annotation:annotation:1.2.0 -> 1.5.0
++--- org.example:gpl-lib:1.0.0
+|    \--- androidx.annotation:annotation:1.5.0 (*)
+\--- project :lib
+"
+            .as_bytes(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn write_dot_license_colors() {
+        let gpl = [SPDX::Other("GPL-2.0-only".into())];
+        let apache = [SPDX::Apache20];
+        let overridden = LicenseOrigin::Overridden { note: None };
+        let licenses = LicenseMap::from([
+            ("androidx.core:core:1.9.0".to_string(), (&apache[..], None)),
+            (
+                "androidx.annotation:annotation:1.5.0".into(),
+                (&apache[..], Some(&overridden)),
+            ),
+            ("org.example:gpl-lib:1.0.0".into(), (&gpl[..], None)),
+        ]);
+
+        let mut actual = vec![];
+        write_dot(&mut actual, &trees(), &licenses, LicenseConjunction::Or).unwrap();
+        assert_eq!(
+            r##"digraph dependencies {
+  rankdir=LR;
+  node [shape=box, style=filled];
+  n0 [label="releaseRuntimeClasspath", fillcolor="#bbdefb"];
+  n1 [label="androidx.core:core:1.9.0\nApache-2.0", fillcolor="#c8e6c9"];
+  n2 [label="androidx.annotation:annotation:1.5.0\nApache-2.0 (overridden)", fillcolor="#c8e6c9"];
+  n3 [label="org.example:gpl-lib:1.0.0\nGPL-2.0-only", fillcolor="#ef9a9a"];
+  n4 [label="project :lib", fillcolor="#bbdefb"];
+  n0 -> n1;
+  n0 -> n3;
+  n0 -> n4;
+  n1 -> n2;
+  n3 -> n2;
+}
+"##,
+            String::from_utf8(actual).unwrap(),
+        );
+    }
+
+    #[test]
+    fn write_mermaid_unknown() {
+        let mut actual = vec![];
+        write_mermaid(
+            &mut actual,
+            &trees(),
+            &LicenseMap::new(),
+            LicenseConjunction::Or,
+        )
+        .unwrap();
+        assert_eq!(
+            r#"flowchart LR
+  n0["releaseRuntimeClasspath"]
+  n1["androidx.core:core:1.9.0<br/>unknown"]
+  n2["androidx.annotation:annotation:1.5.0<br/>unknown"]
+  n3["org.example:gpl-lib:1.0.0<br/>unknown"]
+  n4["project :lib"]
+  n0 --> n1
+  n0 --> n3
+  n0 --> n4
+  n1 --> n2
+  n3 --> n2
+  classDef unknown fill:#e0e0e0
+  class n1,n2,n3 unknown
+  classDef project fill:#bbdefb
+  class n0,n4 project
+"#,
+            String::from_utf8(actual).unwrap(),
+        );
+    }
+}
@@ -0,0 +1,132 @@
+/*
+ * Copyright 2023 sukawasatoru
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+//! Heuristic that infers the licenses of the POMs that have no license from the sibling
+//! artifacts of the same group and version such as `androidx.compose.ui:ui-*`.
+
+use crate::function::summary::has_unknown_license;
+use crate::model::{Coordinate, SPDX};
+use crate::prelude::*;
+use std::collections::{HashMap, HashSet};
+
+/// Licenses of the artifact that are inferred from the `source` artifact of the same group and
+/// version.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct InferredLicense {
+    pub licenses: Vec<SPDX>,
+    pub source: Coordinate,
+}
+
+/// Infer the licenses of the `entries` that have no license.
+///
+/// The licenses are inferred only if all siblings of the same group id and version that have
+/// the recognized licenses agree on them, and the first sibling in the order of `entries` is
+/// the source. The `entries` are the coordinates and their licenses and the version is the
+/// version of the POM that may differ from the input.
+pub fn infer_group_licenses<'a, I>(entries: I) -> HashMap<Coordinate, InferredLicense>
+where
+    I: IntoIterator<Item = (&'a Coordinate, Option<&'a str>, &'a [SPDX])>,
+{
+    let entries = entries.into_iter().collect::<Vec<_>>();
+    let mut siblings = HashMap::<(&str, Option<&str>), Vec<(&Coordinate, &[SPDX])>>::new();
+    for (coordinate, version, licenses) in &entries {
+        if !has_unknown_license(licenses) {
+            siblings
+                .entry((&coordinate.group_id, *version))
+                .or_default()
+                .push((coordinate, licenses));
+        }
+    }
+
+    let mut inferred = HashMap::new();
+    for (coordinate, version, licenses) in &entries {
+        if !licenses.is_empty() || version.is_none() {
+            continue;
+        }
+        let siblings = match siblings.get(&(coordinate.group_id.as_str(), *version)) {
+            Some(data) => data,
+            None => continue,
+        };
+        let (source, source_licenses) = siblings[0];
+        let expected = source_licenses.iter().collect::<HashSet<_>>();
+        if siblings
+            .iter()
+            .any(|(_, data)| data.iter().collect::<HashSet<_>>() != expected)
+        {
+            debug!(dep_name = %coordinate, "the siblings have the different licenses");
+            continue;
+        }
+        inferred.insert(
+            (*coordinate).clone(),
+            InferredLicense {
+                licenses: source_licenses.to_vec(),
+                source: source.clone(),
+            },
+        );
+    }
+    inferred
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn infer_group_licenses_siblings() {
+        let ui = "androidx.compose.ui:ui:1.5.0"
+            .parse::<Coordinate>()
+            .unwrap();
+        let graphics = "androidx.compose.ui:ui-graphics:1.5.0"
+            .parse::<Coordinate>()
+            .unwrap();
+        let text = "androidx.compose.ui:ui-text:1.5.0"
+            .parse::<Coordinate>()
+            .unwrap();
+        let old = "androidx.compose.ui:ui-util:1.4.0"
+            .parse::<Coordinate>()
+            .unwrap();
+        let other = "com.example:lib:1.5.0".parse::<Coordinate>().unwrap();
+        let apache = [SPDX::Apache20];
+        let actual = infer_group_licenses([
+            (&ui, Some("1.5.0"), apache.as_slice()),
+            (&graphics, Some("1.5.0"), apache.as_slice()),
+            (&text, Some("1.5.0"), [].as_slice()),
+            (&old, Some("1.4.0"), [].as_slice()),
+            (&other, Some("1.5.0"), [].as_slice()),
+        ]);
+
+        let expected = HashMap::from([(
+            text,
+            InferredLicense {
+                licenses: vec![SPDX::Apache20],
+                source: ui,
+            },
+        )]);
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn infer_group_licenses_disagreement() {
+        let a = "com.example:a:1.0.0".parse::<Coordinate>().unwrap();
+        let b = "com.example:b:1.0.0".parse::<Coordinate>().unwrap();
+        let c = "com.example:c:1.0.0".parse::<Coordinate>().unwrap();
+        let actual = infer_group_licenses([
+            (&a, Some("1.0.0"), [SPDX::MIT].as_slice()),
+            (&b, Some("1.0.0"), [SPDX::Apache20].as_slice()),
+            (&c, Some("1.0.0"), [].as_slice()),
+        ]);
+        assert!(actual.is_empty());
+    }
+}
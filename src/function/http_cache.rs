@@ -0,0 +1,282 @@
+/*
+ * Copyright 2023 sukawasatoru
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Disk cache of the responses that have the validators for the conditional requests.
+
+use crate::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::fs::OpenOptions;
+use std::io::{ErrorKind, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Age of the temporary file that is left by the crashed process and removed by [HttpCache::new].
+const STALE_TMP_AGE: Duration = Duration::from_secs(60 * 60);
+
+/// Attempts of the unique name of the temporary file.
+const TMP_ATTEMPTS: u32 = 16;
+
+/// Response stored with `ETag` and `Last-Modified` that are sent as `If-None-Match` and
+/// `If-Modified-Since` of the next request.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct CacheEntry {
+    pub url: String,
+    pub etag: Option<String>,
+    pub last_modified: Option<String>,
+    pub body: String,
+}
+
+/// Cache of the file per URL in the directory.
+#[derive(Debug)]
+pub struct HttpCache {
+    dir: PathBuf,
+}
+
+impl HttpCache {
+    /// Create the cache of the `dir` and remove the temporary files of the crashed processes that
+    /// share the directory.
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        let cache = Self { dir: dir.into() };
+        cache.remove_stale_tmp_files(STALE_TMP_AGE);
+        cache
+    }
+
+    /// Returns `None` if the entry is not found or broken.
+    pub fn load(&self, url: &str) -> Option<CacheEntry> {
+        let path = self.entry_path(url);
+        let json = std::fs::read_to_string(&path).ok()?;
+        match serde_json::from_str::<CacheEntry>(&json) {
+            // the hash of the different URLs may collide.
+            Ok(data) if data.url == url => Some(data),
+            Ok(_) => None,
+            Err(e) => {
+                debug!(?e, path = %path.display(), "ignore the broken cache");
+                None
+            }
+        }
+    }
+
+    /// Write the entry to the temporary file and rename it so that the concurrent processes
+    /// never read the partially written entry.
+    pub fn store(&self, entry: &CacheEntry) -> Fallible<()> {
+        static SEQUENCE: AtomicU64 = AtomicU64::new(0);
+        let suffix = format!(
+            "{}-{}",
+            std::process::id(),
+            SEQUENCE.fetch_add(1, Ordering::Relaxed)
+        );
+        self.store_with_suffix(entry, &suffix)
+    }
+
+    /// Same as [HttpCache::store] but the temporary file is named with the `suffix` first.
+    ///
+    /// The processes of the containers that share the directory may have the same pid so the
+    /// temporary file is created exclusively and renamed with the time if it exists.
+    fn store_with_suffix(&self, entry: &CacheEntry, suffix: &str) -> Fallible<()> {
+        std::fs::create_dir_all(&self.dir)
+            .with_context(|| format!("failed to create the directory: {}", self.dir.display()))?;
+
+        let path = self.entry_path(&entry.url);
+        let mut tmp_path = path.with_extension(format!("{}.tmp", suffix));
+        let mut attempt = 0;
+        let mut file = loop {
+            match OpenOptions::new()
+                .write(true)
+                .create_new(true)
+                .open(&tmp_path)
+            {
+                Ok(data) => break data,
+                Err(e) if e.kind() == ErrorKind::AlreadyExists && attempt < TMP_ATTEMPTS => {
+                    attempt += 1;
+                    let nanos = SystemTime::now()
+                        .duration_since(UNIX_EPOCH)
+                        .unwrap_or_default()
+                        .as_nanos();
+                    tmp_path = path.with_extension(format!("{}-{}-{}.tmp", suffix, nanos, attempt));
+                }
+                Err(e) => {
+                    return Err(e).with_context(|| {
+                        format!("failed to create the cache: {}", tmp_path.display())
+                    })
+                }
+            }
+        };
+        let written = file
+            .write_all(serde_json::to_string(entry)?.as_bytes())
+            .with_context(|| format!("failed to write the cache: {}", tmp_path.display()))
+            .and_then(|_| {
+                drop(file);
+                std::fs::rename(&tmp_path, &path)
+                    .with_context(|| format!("failed to rename the cache: {}", path.display()))
+            });
+        if written.is_err() {
+            std::fs::remove_file(&tmp_path).ok();
+        }
+
+        written
+    }
+
+    /// Remove the temporary files that are not modified for the `stale_after`.
+    fn remove_stale_tmp_files(&self, stale_after: Duration) {
+        let entries = match std::fs::read_dir(&self.dir) {
+            Ok(data) => data,
+            Err(_) => return,
+        };
+        for path in entries.flatten().map(|data| data.path()) {
+            if path.extension().map_or(true, |data| data != "tmp") || !is_stale(&path, stale_after)
+            {
+                continue;
+            }
+            match std::fs::remove_file(&path) {
+                Ok(_) => debug!(path = %path.display(), "removed the stale temporary file"),
+                Err(e) => debug!(?e, path = %path.display(), "failed to remove the stale file"),
+            }
+        }
+    }
+
+    fn entry_path(&self, url: &str) -> PathBuf {
+        self.dir
+            .join(format!("{:016x}.json", fnv1a(url.as_bytes())))
+    }
+}
+
+fn is_stale(path: &Path, stale_after: Duration) -> bool {
+    std::fs::metadata(path)
+        .and_then(|data| data.modified())
+        .ok()
+        .and_then(|data| data.elapsed().ok())
+        .map_or(false, |data| stale_after <= data)
+}
+
+/// FNV-1a that is stable across the Rust versions unlike `DefaultHasher`.
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf29ce484222325, |hash, byte| {
+        (hash ^ *byte as u64).wrapping_mul(0x100000001b3)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn store_and_load() {
+        let dir = std::env::temp_dir().join(format!("oss-info-maven-cache-{}", std::process::id()));
+        let cache = HttpCache::new(&dir);
+        let entry = CacheEntry {
+            url: "https://repo1.maven.org/maven2/com/example/lib/maven-metadata.xml".into(),
+            etag: Some("\"abc\"".into()),
+            last_modified: None,
+            body: "<metadata/>".into(),
+        };
+
+        assert_eq!(None, cache.load(&entry.url));
+        cache.store(&entry).unwrap();
+        let actual = cache.load(&entry.url);
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(Some(entry), actual);
+    }
+
+    #[test]
+    fn store_concurrently() {
+        let dir = std::env::temp_dir().join(format!(
+            "oss-info-maven-cache-concurrent-{}",
+            std::process::id()
+        ));
+        let cache = HttpCache::new(&dir);
+        let entry = CacheEntry {
+            url: "https://repo1.maven.org/maven2/com/example/lib/maven-metadata.xml".into(),
+            etag: None,
+            last_modified: None,
+            body: "<metadata/>".into(),
+        };
+
+        let results = std::thread::scope(|scope| {
+            let handles = (0..8)
+                .map(|_| scope.spawn(|| cache.store(&entry)))
+                .collect::<Vec<_>>();
+            handles
+                .into_iter()
+                .map(|data| data.join().unwrap())
+                .collect::<Vec<_>>()
+        });
+        let actual = cache.load(&entry.url);
+        let entries = std::fs::read_dir(&dir).unwrap().count();
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        for result in results {
+            result.unwrap();
+        }
+        assert_eq!(Some(entry), actual);
+        assert_eq!(1, entries, "no temporary file is left");
+    }
+
+    #[test]
+    fn store_same_tmp_name() {
+        let dir = std::env::temp_dir().join(format!(
+            "oss-info-maven-cache-same-name-{}",
+            std::process::id()
+        ));
+        std::fs::create_dir_all(&dir).unwrap();
+        let entry = CacheEntry {
+            url: "https://repo1.maven.org/maven2/com/example/lib/maven-metadata.xml".into(),
+            etag: None,
+            last_modified: None,
+            body: "<metadata/>".into(),
+        };
+        // the file of the job that crashed with the same pid and the sequence number.
+        let stale_path = dir
+            .join(format!("{:016x}.json", fnv1a(entry.url.as_bytes())))
+            .with_extension("1-0.tmp");
+        std::fs::write(&stale_path, "partial").unwrap();
+
+        // the jobs of the containers that have the same pid.
+        let writers = [HttpCache::new(&dir), HttpCache::new(&dir)];
+        let has_fresh_tmp = stale_path.exists();
+        let results = std::thread::scope(|scope| {
+            let handles = writers
+                .iter()
+                .map(|cache| scope.spawn(|| cache.store_with_suffix(&entry, "1-0")))
+                .collect::<Vec<_>>();
+            handles
+                .into_iter()
+                .map(|data| data.join().unwrap())
+                .collect::<Vec<_>>()
+        });
+        let actual = writers[0].load(&entry.url);
+        HttpCache::new(&dir).remove_stale_tmp_files(Duration::ZERO);
+        let entries = std::fs::read_dir(&dir).unwrap().count();
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert!(
+            has_fresh_tmp,
+            "the temporary file of the running job is kept"
+        );
+        for result in results {
+            result.unwrap();
+        }
+        assert_eq!(Some(entry), actual);
+        assert_eq!(1, entries, "the stale temporary file is removed");
+    }
+
+    #[test]
+    fn fnv1a_stable() {
+        assert_eq!(0xcbf29ce484222325, fnv1a(b""));
+        assert_eq!(0xaf63dc4c8601ec8c, fnv1a(b"a"));
+    }
+}
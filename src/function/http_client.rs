@@ -0,0 +1,362 @@
+/*
+ * Copyright 2023 sukawasatoru
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::function::http_cache::HttpCache;
+use crate::function::http_transport::{HttpRequest, HttpResponse, HttpTransport, Method};
+use crate::function::metrics::RequestMetrics;
+use crate::function::rate_limit::RateLimiter;
+use crate::prelude::*;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::OnceCell;
+use tracing::Instrument;
+use url::Url;
+
+/// HTTP client shared by the retrieve functions.
+///
+/// `reqwest::Client` can be converted to this without any additional behavior.
+#[derive(Clone)]
+pub struct HttpClient {
+    transport: Arc<dyn HttpTransport>,
+    rate_limiter: Option<Arc<RateLimiter>>,
+    validate_xml: bool,
+    strict_xml: bool,
+    timeout: Option<Duration>,
+    cache: Option<Arc<HttpCache>>,
+    credentials: Arc<Vec<Credential>>,
+    metrics: Arc<RequestMetrics>,
+    shared_metadata: Option<Arc<SharedMetadata>>,
+}
+
+/// maven-metadata.xml per URL that is requested once and shared with the concurrent requests.
+type SharedMetadata = Mutex<HashMap<String, Arc<SharedMetadataCell>>>;
+
+/// maven-metadata.xml of a URL that the concurrent requests wait for.
+#[derive(Default)]
+pub(crate) struct SharedMetadataCell {
+    pub value: OnceCell<String>,
+    attempted: AtomicBool,
+}
+
+impl SharedMetadataCell {
+    /// Mark the request of the maven-metadata.xml and returns `true` if it re-issues the failed
+    /// one.
+    pub fn start_attempt(&self) -> bool {
+        self.attempted.swap(true, Ordering::Relaxed)
+    }
+}
+
+/// Basic authentication of the requests under the `url_prefix` such as the `<server>` of the
+/// Maven settings.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Credential {
+    /// Repository root without the trailing slash.
+    pub url_prefix: String,
+    pub username: String,
+    pub password: Option<String>,
+}
+
+impl Credential {
+    fn matches(&self, url: &str) -> bool {
+        match url.strip_prefix(&self.url_prefix) {
+            Some(rest) => rest.is_empty() || rest.starts_with('/'),
+            None => false,
+        }
+    }
+
+    /// Value of the `Authorization` header.
+    fn basic_auth(&self) -> String {
+        let user_pass = format!(
+            "{}:{}",
+            self.username,
+            self.password.as_deref().unwrap_or_default()
+        );
+        format!("Basic {}", encode_base64(user_pass.as_bytes()))
+    }
+}
+
+/// https://www.rfc-editor.org/rfc/rfc4648#section-4
+fn encode_base64(data: &[u8]) -> String {
+    const TABLE: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut encoded = String::with_capacity((data.len() + 2) / 3 * 4);
+    for chunk in data.chunks(3) {
+        let bits = chunk.iter().enumerate().fold(0u32, |acc, (index, byte)| {
+            acc | ((*byte as u32) << (16 - index * 8))
+        });
+        for index in 0..4 {
+            if index <= chunk.len() {
+                encoded.push(TABLE[((bits >> (18 - index * 6)) & 0x3f) as usize] as char);
+            } else {
+                encoded.push('=');
+            }
+        }
+    }
+    encoded
+}
+
+impl HttpClient {
+    pub fn new(client: reqwest::Client) -> Self {
+        Self::with_transport(Arc::new(client))
+    }
+
+    /// Send the requests with the `transport` such as the client of the `fetch` API instead of
+    /// `reqwest`.
+    pub fn with_transport(transport: Arc<dyn HttpTransport>) -> Self {
+        Self {
+            transport,
+            rate_limiter: None,
+            validate_xml: false,
+            strict_xml: false,
+            timeout: None,
+            cache: None,
+            credentials: Default::default(),
+            metrics: Default::default(),
+            shared_metadata: None,
+        }
+    }
+
+    /// Limit the requests per repository host.
+    pub fn with_rate_limiter(mut self, rate_limiter: Arc<RateLimiter>) -> Self {
+        self.rate_limiter = Some(rate_limiter);
+        self
+    }
+
+    /// Fail the request that is not completed within the timeout including the connection.
+    ///
+    /// Use `reqwest::ClientBuilder::connect_timeout` to limit the connection separately.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Validate the fetched maven-metadata.xml and POMs against the XSD rules and report the
+    /// violations as warnings.
+    pub fn with_xml_validation(mut self, validate_xml: bool) -> Self {
+        self.validate_xml = validate_xml;
+        self
+    }
+
+    /// Fail the retrieval of the maven-metadata.xml and the POM that violate the XSD rules
+    /// instead of warning the violations.
+    pub fn with_strict_xml(mut self, strict_xml: bool) -> Self {
+        self.strict_xml = strict_xml;
+        self
+    }
+
+    pub fn validates_xml(&self) -> bool {
+        self.validate_xml || self.strict_xml
+    }
+
+    pub fn is_strict_xml(&self) -> bool {
+        self.strict_xml
+    }
+
+    /// Store maven-metadata.xml to the cache and revalidate it with the conditional request.
+    pub fn with_cache(mut self, cache: Arc<HttpCache>) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+
+    pub fn cache(&self) -> Option<&HttpCache> {
+        self.cache.as_deref()
+    }
+
+    /// Request the maven-metadata.xml of the same URL only once and share it with the clones
+    /// instead of requesting it for each coordinate of the artifact such as the multiple versions
+    /// and the platform artifacts.
+    ///
+    /// The concurrent requests wait for the first one, and the next request retries if it fails.
+    pub fn with_shared_metadata(mut self) -> Self {
+        self.shared_metadata = Some(Default::default());
+        self
+    }
+
+    /// Cell of the maven-metadata.xml of `url` if [HttpClient::with_shared_metadata].
+    pub(crate) fn shared_metadata(&self, url: &str) -> Option<Arc<SharedMetadataCell>> {
+        let shared_metadata = self.shared_metadata.as_ref()?;
+        let mut shared_metadata = shared_metadata.lock().unwrap();
+        Some(shared_metadata.entry(url.into()).or_default().clone())
+    }
+
+    /// Counters of the requests that are shared with the clones.
+    pub fn metrics(&self) -> &RequestMetrics {
+        &self.metrics
+    }
+
+    /// Authenticate the requests to the repositories that need the credentials.
+    pub fn with_credentials(mut self, credentials: Vec<Credential>) -> Self {
+        self.credentials = Arc::new(credentials);
+        self
+    }
+
+    pub fn get(&self, url: &str) -> HttpRequest {
+        HttpRequest::new(Method::Get, url)
+    }
+
+    pub fn head(&self, url: &str) -> HttpRequest {
+        HttpRequest::new(Method::Head, url)
+    }
+
+    pub fn post(&self, url: &str) -> HttpRequest {
+        HttpRequest::new(Method::Post, url)
+    }
+
+    pub async fn send(&self, mut request: HttpRequest) -> Fallible<HttpResponse> {
+        if request.timeout.is_none() {
+            request.timeout = self.timeout;
+        }
+        if let Some(credential) = self
+            .credentials
+            .iter()
+            .find(|data| data.matches(&request.url))
+            .filter(|_| !request.has_header("Authorization"))
+        {
+            request = request.header("Authorization", credential.basic_auth());
+        }
+
+        if let Some(rate_limiter) = &self.rate_limiter {
+            let host = Url::parse(&request.url)
+                .ok()
+                .and_then(|data| data.host_str().map(str::to_owned))
+                .unwrap_or_default();
+            rate_limiter.acquire(&host).await;
+        }
+
+        self.metrics.add_request();
+        let span = tracing::info_span!(
+            "http.request",
+            http.method = ?request.method,
+            http.url = %request.url,
+            http.status = tracing::field::Empty
+        );
+        let res = self.transport.fetch(request).instrument(span.clone()).await;
+        if let Ok(data) = &res {
+            span.record("http.status", data.status());
+        }
+        res
+    }
+}
+
+impl Default for HttpClient {
+    fn default() -> Self {
+        Self::new(reqwest::Client::default())
+    }
+}
+
+impl From<reqwest::Client> for HttpClient {
+    fn from(value: reqwest::Client) -> Self {
+        Self::new(value)
+    }
+}
+
+/// Connection settings of the `reqwest::Client` that resolves many artifacts from a few hosts.
+///
+/// HTTP/2 is negotiated with ALPN of the `native-tls-alpn` feature of reqwest so that the
+/// requests to the same host are multiplexed over a connection, and the connections are kept
+/// alive between the bursts of the requests instead of renegotiating TLS.
+#[cfg(not(target_arch = "wasm32"))]
+#[derive(Clone, Debug)]
+pub struct ConnectionOptions {
+    pub connect_timeout: Option<Duration>,
+
+    /// Maximum idle connections per host in the pool.
+    pub pool_max_idle_per_host: usize,
+
+    /// Close the connection that is idle in the pool longer than this.
+    pub pool_idle_timeout: Option<Duration>,
+
+    pub tcp_keepalive: Option<Duration>,
+
+    /// Interval of the HTTP/2 PING that keeps the idle connection alive.
+    pub http2_keep_alive_interval: Option<Duration>,
+
+    pub proxy: Option<reqwest::Proxy>,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl Default for ConnectionOptions {
+    fn default() -> Self {
+        Self {
+            connect_timeout: None,
+            pool_max_idle_per_host: 4,
+            pool_idle_timeout: Some(Duration::from_secs(90)),
+            tcp_keepalive: Some(Duration::from_secs(60)),
+            http2_keep_alive_interval: Some(Duration::from_secs(30)),
+            proxy: None,
+        }
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl ConnectionOptions {
+    pub fn build(&self) -> reqwest::Result<reqwest::Client> {
+        let builder = reqwest::Client::builder()
+            .pool_max_idle_per_host(self.pool_max_idle_per_host)
+            .pool_idle_timeout(self.pool_idle_timeout)
+            .tcp_keepalive(self.tcp_keepalive)
+            .http2_adaptive_window(true);
+        let builder = match self.connect_timeout {
+            Some(timeout) => builder.connect_timeout(timeout),
+            None => builder,
+        };
+        let builder = match self.http2_keep_alive_interval {
+            Some(interval) => builder
+                .http2_keep_alive_interval(interval)
+                .http2_keep_alive_while_idle(true),
+            None => builder,
+        };
+        let builder = match &self.proxy {
+            Some(proxy) => builder.proxy(proxy.clone()),
+            None => builder,
+        };
+        builder.build()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn credential_matches() {
+        let credential = Credential {
+            url_prefix: "https://nexus.example.com/repository/maven-public".into(),
+            username: "Aladdin".into(),
+            password: Some("open sesame".into()),
+        };
+
+        assert!(credential.matches(
+            "https://nexus.example.com/repository/maven-public/com/example/maven-metadata.xml"
+        ));
+        assert!(!credential.matches("https://nexus.example.com/repository/maven-public-other/"));
+        assert!(!credential.matches("https://example.com/repository/maven-public/"));
+        assert_eq!(
+            "Basic QWxhZGRpbjpvcGVuIHNlc2FtZQ==",
+            credential.basic_auth()
+        );
+    }
+
+    #[test]
+    fn encode_base64_padding() {
+        assert_eq!("", encode_base64(b""));
+        assert_eq!("Zg==", encode_base64(b"f"));
+        assert_eq!("Zm8=", encode_base64(b"fo"));
+        assert_eq!("Zm9v", encode_base64(b"foo"));
+        assert_eq!("Zm9vYg==", encode_base64(b"foob"));
+    }
+}
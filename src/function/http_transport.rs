@@ -0,0 +1,315 @@
+/*
+ * Copyright 2023 sukawasatoru
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Transport of the [crate::function::http_client::HttpClient] that is `reqwest` for the native
+//! builds and can be replaced with the client of the `fetch` API for `wasm32-unknown-unknown`.
+
+use crate::prelude::*;
+use std::time::Duration;
+
+#[cfg(not(target_arch = "wasm32"))]
+pub type TransportFuture<'a> = futures::future::BoxFuture<'a, Fallible<HttpResponse>>;
+
+/// The futures of the browser are not `Send`.
+#[cfg(target_arch = "wasm32")]
+pub type TransportFuture<'a> = futures::future::LocalBoxFuture<'a, Fallible<HttpResponse>>;
+
+/// Send a request and receive the whole response.
+///
+/// The [crate::function::http_client::HttpClient] applies the timeout, the credentials and the
+/// rate limit to the request before this.
+#[cfg(not(target_arch = "wasm32"))]
+pub trait HttpTransport: Send + Sync {
+    fn fetch(&self, request: HttpRequest) -> TransportFuture<'_>;
+}
+
+/// Send a request and receive the whole response.
+///
+/// The [crate::function::http_client::HttpClient] applies the timeout, the credentials and the
+/// rate limit to the request before this.
+#[cfg(target_arch = "wasm32")]
+pub trait HttpTransport {
+    fn fetch(&self, request: HttpRequest) -> TransportFuture<'_>;
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Method {
+    Get,
+    Head,
+    Post,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct HttpRequest {
+    pub method: Method,
+    pub url: String,
+    pub headers: Vec<(String, String)>,
+    pub body: Option<String>,
+
+    /// Timeout of the whole request. The transport that has no timeout ignores this.
+    pub timeout: Option<Duration>,
+}
+
+impl HttpRequest {
+    pub fn new(method: Method, url: impl Into<String>) -> Self {
+        Self {
+            method,
+            url: url.into(),
+            headers: vec![],
+            body: None,
+            timeout: None,
+        }
+    }
+
+    pub fn header(mut self, name: &str, value: impl Into<String>) -> Self {
+        self.headers.push((name.into(), value.into()));
+        self
+    }
+
+    pub fn body(mut self, body: impl Into<String>) -> Self {
+        self.body = Some(body.into());
+        self
+    }
+
+    /// Whether the request has the header. The name is case-insensitive.
+    pub fn has_header(&self, name: &str) -> bool {
+        self.headers
+            .iter()
+            .any(|(data, _)| data.eq_ignore_ascii_case(name))
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct HttpResponse {
+    pub url: String,
+    pub status: u16,
+    pub headers: Vec<(String, String)>,
+    pub body: Vec<u8>,
+}
+
+impl HttpResponse {
+    pub fn status(&self) -> u16 {
+        self.status
+    }
+
+    pub fn is_success(&self) -> bool {
+        (200..300).contains(&self.status)
+    }
+
+    /// Returns the first value of the header. The name is case-insensitive.
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(data, _)| data.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Returns the error if the status is the client error or the server error.
+    pub fn error_for_status(self) -> Fallible<Self> {
+        match self.status {
+            400..=499 => bail!(
+                "HTTP status client error ({}) for url ({})",
+                self.status,
+                self.url
+            ),
+            500..=599 => bail!(
+                "HTTP status server error ({}) for url ({})",
+                self.status,
+                self.url
+            ),
+            _ => Ok(self),
+        }
+    }
+
+    /// Returns the error with the beginning of the body if the body is an HTML page such as the
+    /// login page or the error page that the proxy or the mirror returns with 200 instead of the
+    /// XML or the JSON.
+    ///
+    /// The body that starts with `<html` or `<!DOCTYPE html` is HTML. The `Content-Type` of
+    /// `text/html` is HTML unless the body starts with the XML declaration, the root of the
+    /// content such as `<project` or the JSON because some servers return them as `text/html`.
+    /// The leading comments of the body are skipped.
+    pub fn error_for_html(self) -> Fallible<Self> {
+        const SNIPPET_LEN: usize = 200;
+
+        let body = String::from_utf8_lossy(&self.body);
+        let body = body.trim_start_matches('\u{feff}').trim_start();
+        let mut content = body;
+        while let Some(rest) = content.strip_prefix("<!--") {
+            content = match rest.split_once("-->") {
+                Some((_, rest)) => rest.trim_start(),
+                None => "",
+            };
+        }
+        let leading = content
+            .chars()
+            .take(14)
+            .collect::<String>()
+            .to_ascii_lowercase();
+        let is_html_body = leading.starts_with("<html") || leading.starts_with("<!doctype html");
+        let is_html_type = self.header("Content-Type").map_or(false, |data| {
+            data.trim_start()
+                .to_ascii_lowercase()
+                .starts_with("text/html")
+        });
+        let is_content = ["<?xml", "<project", "<metadata", "<ivy-module", "{"]
+            .iter()
+            .any(|data| leading.starts_with(data));
+        if is_html_body || (is_html_type && !is_content) {
+            let snippet = body.split_whitespace().collect::<Vec<_>>().join(" ");
+            let snippet = match snippet.char_indices().nth(SNIPPET_LEN) {
+                Some((index, _)) => format!("{}...", &snippet[..index]),
+                None => snippet,
+            };
+            bail!(
+                "HTML page is returned instead of the content for url ({}). the proxy or the mirror may return an error page: {}",
+                self.url,
+                snippet
+            );
+        }
+        Ok(self)
+    }
+
+    /// Decode the body as UTF-8. The invalid sequences are replaced with `U+FFFD`.
+    pub fn text(self) -> String {
+        match String::from_utf8(self.body) {
+            Ok(data) => data,
+            Err(e) => String::from_utf8_lossy(e.as_bytes()).into_owned(),
+        }
+    }
+}
+
+impl HttpTransport for reqwest::Client {
+    fn fetch(&self, request: HttpRequest) -> TransportFuture<'_> {
+        Box::pin(async move {
+            let builder = match request.method {
+                Method::Get => self.get(&request.url),
+                Method::Head => self.head(&request.url),
+                Method::Post => self.post(&request.url),
+            };
+            let builder = request
+                .headers
+                .iter()
+                .fold(builder, |acc, (name, value)| acc.header(name, value));
+            let builder = match request.body {
+                Some(body) => builder.body(body),
+                None => builder,
+            };
+            // the `fetch` API of reqwest has no timeout.
+            #[cfg(not(target_arch = "wasm32"))]
+            let builder = match request.timeout {
+                Some(timeout) => builder.timeout(timeout),
+                None => builder,
+            };
+
+            let res = builder.send().await?;
+            let status = res.status().as_u16();
+            let headers = res
+                .headers()
+                .iter()
+                .filter_map(|(name, value)| {
+                    Some((name.as_str().to_owned(), value.to_str().ok()?.to_owned()))
+                })
+                .collect();
+            let body = res.bytes().await?.to_vec();
+
+            Ok(HttpResponse {
+                url: request.url,
+                status,
+                headers,
+                body,
+            })
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn error_for_status() {
+        let res = |status| HttpResponse {
+            url: "https://repo1.maven.org/maven2/com/example/maven-metadata.xml".into(),
+            status,
+            headers: vec![("ETag".into(), "\"abc\"".into())],
+            body: vec![],
+        };
+
+        assert!(res(200).error_for_status().is_ok());
+        assert!(res(304).error_for_status().is_ok());
+        assert!(res(404).error_for_status().is_err());
+        assert!(res(503).error_for_status().is_err());
+        assert_eq!(Some("\"abc\""), res(200).header("etag"));
+    }
+
+    #[test]
+    fn error_for_html() {
+        let res = |content_type: &str, body: &str| HttpResponse {
+            url: "https://repo1.maven.org/maven2/com/example/maven-metadata.xml".into(),
+            status: 200,
+            headers: vec![("Content-Type".into(), content_type.into())],
+            body: body.as_bytes().to_vec(),
+        };
+
+        assert!(res("text/xml", "<?xml version=\"1.0\"?><metadata/>")
+            .error_for_html()
+            .is_ok());
+        assert!(res(
+            "text/html; charset=utf-8",
+            "<?xml version=\"1.0\"?><metadata/>"
+        )
+        .error_for_html()
+        .is_ok());
+        assert!(res("text/html", "Sign in").error_for_html().is_err());
+        assert!(res(
+            "text/html",
+            "<project xmlns=\"http://maven.apache.org/POM/4.0.0\"></project>"
+        )
+        .error_for_html()
+        .is_ok());
+        assert!(res(
+            "text/html",
+            "<!-- Licensed under the Apache License -->\n<project></project>"
+        )
+        .error_for_html()
+        .is_ok());
+        assert!(res("text/html", "{\"formatVersion\": \"1.1\"}")
+            .error_for_html()
+            .is_ok());
+        assert!(res("text/plain", "<!-- error -->\n<html></html>")
+            .error_for_html()
+            .is_err());
+
+        let actual = res(
+            "application/xml",
+            "\n<!DOCTYPE HTML>\n<html>\n  <head><title>Sign in</title></head>\n</html>\n",
+        )
+        .error_for_html()
+        .unwrap_err()
+        .to_string();
+        assert_eq!(
+            "HTML page is returned instead of the content for url (https://repo1.maven.org/maven2/com/example/maven-metadata.xml). the proxy or the mirror may return an error page: <!DOCTYPE HTML> <html> <head><title>Sign in</title></head> </html>",
+            actual
+        );
+
+        let actual = res("text/html", &"a".repeat(300))
+            .error_for_html()
+            .unwrap_err()
+            .to_string();
+        assert!(actual.ends_with(&format!(": {}...", "a".repeat(200))));
+    }
+}
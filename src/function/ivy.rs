@@ -0,0 +1,190 @@
+/*
+ * Copyright 2023 sukawasatoru
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! https://ant.apache.org/ivy/history/latest-milestone/ivyfile.html
+
+use crate::function::maven::POM;
+use crate::model::{Coordinate, SPDX};
+use crate::prelude::*;
+use serde::Deserialize;
+
+/// Returns the URL of the Ivy file of the `gradle` layout that is
+/// `[organisation]/[module]/[revision]/ivy-[revision].xml`.
+///
+/// The organisation is not split by `.` because the Ivy repositories are not Maven compatible.
+pub fn ivy_file_url(repo_root: &str, coordinate: &Coordinate, version: &str) -> String {
+    format!(
+        "{}/{}/{}/{}/ivy-{}.xml",
+        repo_root.trim_end_matches('/'),
+        coordinate.group_id,
+        coordinate.artifact_id,
+        version,
+        version,
+    )
+}
+
+/// Map the `<info>` of the Ivy file into the [POM].
+///
+/// `<license>` is mapped into the licenses and the `homepage` of `<description>` is mapped into
+/// the URL. The Ivy file has no name so it is left empty.
+pub fn parse_ivy(xml: &str) -> Fallible<POM> {
+    let parsed = quick_xml::de::from_str::<IvyModule>(xml)?;
+    let info = parsed.info;
+    let description = info.description.unwrap_or_default();
+    let text = description.text.trim();
+
+    Ok(POM {
+        group_id: Some(info.organisation),
+        artifact_id: info.module,
+        version: info.revision,
+        description: (!text.is_empty()).then(|| text.to_string()),
+        url: description.homepage,
+        licenses: info
+            .licenses
+            .iter()
+            .map(|data| match data.name.parse().expect("unexpected spdx") {
+                SPDX::Other(name) => data
+                    .url
+                    .as_deref()
+                    .and_then(SPDX::from_url)
+                    .unwrap_or(SPDX::Other(name)),
+                license => license,
+            })
+            .collect(),
+        license_urls: info
+            .licenses
+            .into_iter()
+            .filter_map(|data| data.url)
+            .collect(),
+        ..Default::default()
+    })
+}
+
+#[derive(Deserialize)]
+struct IvyModule {
+    info: Info,
+}
+
+#[derive(Deserialize)]
+struct Info {
+    #[serde(rename = "@organisation")]
+    organisation: String,
+
+    #[serde(rename = "@module")]
+    module: String,
+
+    #[serde(rename = "@revision")]
+    revision: Option<String>,
+
+    #[serde(rename = "license", default)]
+    licenses: Vec<License>,
+
+    description: Option<Description>,
+}
+
+#[derive(Deserialize)]
+struct License {
+    #[serde(rename = "@name")]
+    name: String,
+
+    #[serde(rename = "@url")]
+    url: Option<String>,
+}
+
+#[derive(Default, Deserialize)]
+struct Description {
+    #[serde(rename = "@homepage")]
+    homepage: Option<String>,
+
+    #[serde(rename = "$text", default)]
+    text: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_ivy_internal_lib() {
+        let source = r#"<?xml version="1.0" encoding="UTF-8"?>
+<ivy-module version="2.0">
+  <info organisation="com.example.internal" module="internal-lib" revision="1.2.0" status="release" publication="20231001120000">
+    <license name="Apache License, Version 2.0" url="https://www.apache.org/licenses/LICENSE-2.0.txt"/>
+    <license name="Example Commercial License" url="https://example.com/license"/>
+    <ivyauthor name="Example" url="https://example.com"/>
+    <description homepage="https://example.com/internal-lib">
+      Internal library.
+    </description>
+  </info>
+  <configurations>
+    <conf name="default" visibility="public"/>
+  </configurations>
+  <publications>
+    <artifact name="internal-lib" type="jar" ext="jar" conf="default"/>
+  </publications>
+  <dependencies>
+    <dependency org="com.squareup.okhttp3" name="okhttp" rev="4.11.0" conf="default"/>
+  </dependencies>
+</ivy-module>
+"#;
+
+        let actual = parse_ivy(source).unwrap();
+        assert_eq!(Some("com.example.internal".into()), actual.group_id);
+        assert_eq!("internal-lib", actual.artifact_id);
+        assert_eq!(Some("1.2.0".into()), actual.version);
+        assert_eq!(None, actual.name);
+        assert_eq!(Some("Internal library.".into()), actual.description);
+        assert_eq!(Some("https://example.com/internal-lib".into()), actual.url);
+        assert_eq!(
+            vec![
+                SPDX::Apache20,
+                SPDX::Other("Example Commercial License".into())
+            ],
+            actual.licenses,
+        );
+        assert_eq!(
+            vec![
+                "https://www.apache.org/licenses/LICENSE-2.0.txt".to_string(),
+                "https://example.com/license".into(),
+            ],
+            actual.license_urls,
+        );
+    }
+
+    #[test]
+    fn parse_ivy_minimal() {
+        let actual = parse_ivy(
+            r#"<ivy-module version="2.0"><info organisation="org.example" module="minimal"/></ivy-module>"#,
+        )
+        .unwrap();
+        assert_eq!("minimal", actual.artifact_id);
+        assert_eq!(None, actual.version);
+        assert_eq!(None, actual.description);
+        assert!(actual.licenses.is_empty());
+    }
+
+    #[test]
+    fn ivy_file_url_gradle_layout() {
+        assert_eq!(
+            "https://ivy.example/com.example/internal-lib/1.2.0/ivy-1.2.0.xml",
+            ivy_file_url(
+                "https://ivy.example/",
+                &"com.example:internal-lib".parse().unwrap(),
+                "1.2.0",
+            ),
+        );
+    }
+}
@@ -0,0 +1,125 @@
+/*
+ * Copyright 2023 sukawasatoru
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+//! JSON Lines that has an object per artifact to stream the results to the other tools and to
+//! read them back for the re-processing.
+
+use crate::function::maven::{ArtifactFile, POM};
+use crate::model::Coordinate;
+use crate::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::io::{BufRead, Write};
+
+#[derive(Serialize)]
+struct RecordRef<'a> {
+    coordinate: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pom: Option<&'a POM>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    artifact_file: Option<&'a ArtifactFile>,
+}
+
+#[derive(Deserialize)]
+struct Record {
+    coordinate: String,
+    pom: Option<POM>,
+    artifact_file: Option<ArtifactFile>,
+}
+
+/// Line of [read_jsonl]. The resolved result is `None` if the artifact failed to resolve.
+pub type JsonlRecord = (Coordinate, Option<(POM, Option<ArtifactFile>)>);
+
+/// Write a line of the `coordinate` and flush it. The `resolved` is `None` if the artifact
+/// failed to resolve.
+pub fn write_jsonl_record<W: Write>(
+    mut writer: W,
+    coordinate: &Coordinate,
+    resolved: Option<(&POM, Option<&ArtifactFile>)>,
+) -> Fallible<()> {
+    let record = RecordRef {
+        coordinate: coordinate.to_string(),
+        pom: resolved.map(|(pom, _)| pom),
+        artifact_file: resolved.and_then(|(_, artifact_file)| artifact_file),
+    };
+    serde_json::to_writer(&mut writer, &record)?;
+    writeln!(writer)?;
+    writer.flush()?;
+    Ok(())
+}
+
+/// Read the lines of [write_jsonl_record]. The empty lines are ignored.
+pub fn read_jsonl<R: BufRead>(reader: R) -> Fallible<Vec<JsonlRecord>> {
+    let mut records = vec![];
+    for (index, line) in reader.lines().enumerate() {
+        let line = line.context("failed to read lines")?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let record = serde_json::from_str::<Record>(&line)
+            .with_context(|| format!("line {}: failed to parse the record", index + 1))?;
+        let coordinate = record
+            .coordinate
+            .parse::<Coordinate>()
+            .with_context(|| format!("line {}: {}", index + 1, record.coordinate))?;
+        records.push((
+            coordinate,
+            record.pom.map(|pom| (pom, record.artifact_file)),
+        ));
+    }
+    Ok(records)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::SPDX;
+
+    #[test]
+    fn write_and_read_jsonl() {
+        let glide = "com.github.bumptech.glide:glide:4.16.0"
+            .parse::<Coordinate>()
+            .unwrap();
+        let missing = "com.example:missing:1.0.0".parse::<Coordinate>().unwrap();
+        let pom = POM {
+            group_id: Some("com.github.bumptech.glide".into()),
+            artifact_id: "glide".into(),
+            version: Some("4.16.0".into()),
+            packaging: Some("aar".into()),
+            name: Some("Glide".into()),
+            licenses: vec![SPDX::BSD2],
+            ..Default::default()
+        };
+
+        let mut buf = vec![];
+        write_jsonl_record(&mut buf, &glide, Some((&pom, None))).unwrap();
+        write_jsonl_record(&mut buf, &missing, None).unwrap();
+        let text = String::from_utf8(buf.clone()).unwrap();
+        let lines = text.lines().collect::<Vec<_>>();
+        assert_eq!(2, lines.len());
+        assert_eq!(r#"{"coordinate":"com.example:missing:1.0.0"}"#, lines[1]);
+
+        let actual = read_jsonl(buf.as_slice()).unwrap();
+        let expected = vec![(glide, Some((pom, None))), (missing, None)];
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn read_jsonl_line_number() {
+        let actual = read_jsonl("\n{\"coordinate\":\"a:b:1\"}\n{".as_bytes())
+            .unwrap_err()
+            .to_string();
+        assert_eq!("line 3: failed to parse the record", actual);
+    }
+}
@@ -0,0 +1,70 @@
+/*
+ * Copyright 2023 sukawasatoru
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::fmt::{Display, Formatter};
+
+/// Result of the liveness check of the URL such as the license URL.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum LinkStatus {
+    Alive,
+
+    /// `404 Not Found` or `410 Gone`.
+    Dead(u16),
+
+    /// The domain is gone or the server doesn't respond.
+    Unreachable(String),
+
+    /// The server returned an error that doesn't mean the absence. e.g. `403 Forbidden`
+    Unknown(u16),
+}
+
+impl LinkStatus {
+    pub fn from_status(status: u16) -> Self {
+        match status {
+            200..=399 => Self::Alive,
+            404 | 410 => Self::Dead(status),
+            _ => Self::Unknown(status),
+        }
+    }
+
+    pub fn is_dead(&self) -> bool {
+        matches!(self, Self::Dead(_) | Self::Unreachable(_))
+    }
+}
+
+impl Display for LinkStatus {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Alive => f.write_str("alive"),
+            Self::Dead(status) | Self::Unknown(status) => write!(f, "HTTP {}", status),
+            Self::Unreachable(reason) => write!(f, "unreachable ({})", reason),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_status_dead() {
+        assert_eq!(LinkStatus::Alive, LinkStatus::from_status(200));
+        assert!(LinkStatus::from_status(404).is_dead());
+        assert!(LinkStatus::from_status(410).is_dead());
+        assert!(!LinkStatus::from_status(403).is_dead());
+        assert!(LinkStatus::Unreachable("dns error".into()).is_dead());
+    }
+}
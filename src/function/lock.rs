@@ -0,0 +1,169 @@
+/*
+ * Copyright 2023 sukawasatoru
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Lock file that records how each dependency was resolved so that a re-run can verify that
+//! the resolutions haven't drifted.
+
+use crate::model::Coordinate;
+use crate::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::io::{Read, Write};
+
+const LOCK_VERSION: u32 = 1;
+
+/// Resolution of a dependency.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct LockEntry {
+    /// Root URL of the Maven repository.
+    pub repository: String,
+
+    /// Version selected from the maven-metadata.xml.
+    pub version: String,
+
+    /// URL of the POM, or the Gradle Module Metadata if the POM is not published.
+    pub pom_url: String,
+
+    /// Lowercase hex of the SHA-256 of the file of `pom_url`.
+    pub sha256: String,
+}
+
+impl LockEntry {
+    /// Returns the differing fields as `field: locked -> actual`.
+    pub fn drifts(&self, actual: &LockEntry) -> Vec<String> {
+        [
+            ("repository", &self.repository, &actual.repository),
+            ("version", &self.version, &actual.version),
+            ("pom_url", &self.pom_url, &actual.pom_url),
+            ("sha256", &self.sha256, &actual.sha256),
+        ]
+        .into_iter()
+        .filter(|(_, locked, actual)| locked != actual)
+        .map(|(field, locked, actual)| format!("{}: {} -> {}", field, locked, actual))
+        .collect()
+    }
+}
+
+#[derive(Serialize)]
+struct LockRef<'a> {
+    version: u32,
+    locked: Vec<LockedRef<'a>>,
+}
+
+#[derive(Serialize)]
+struct LockedRef<'a> {
+    coordinate: String,
+    #[serde(flatten)]
+    entry: &'a LockEntry,
+}
+
+#[derive(Deserialize)]
+struct Lock {
+    version: u32,
+    locked: Vec<Locked>,
+}
+
+#[derive(Deserialize)]
+struct Locked {
+    coordinate: String,
+    #[serde(flatten)]
+    entry: LockEntry,
+}
+
+/// Returns the resolutions of the lock file.
+pub fn read_lock<R: Read>(reader: R) -> Fallible<BTreeMap<Coordinate, LockEntry>> {
+    let lock = serde_json::from_reader::<_, Lock>(reader)?;
+    ensure!(
+        lock.version == LOCK_VERSION,
+        "unsupported lock version: {}",
+        lock.version,
+    );
+
+    lock.locked
+        .into_iter()
+        .map(|data| Ok((data.coordinate.parse::<Coordinate>()?, data.entry)))
+        .collect()
+}
+
+/// Write the resolutions in the order of `entries`.
+pub fn write_lock<'a, W, I>(writer: W, entries: I) -> Fallible<()>
+where
+    W: Write,
+    I: IntoIterator<Item = (&'a Coordinate, &'a LockEntry)>,
+{
+    let lock = LockRef {
+        version: LOCK_VERSION,
+        locked: entries
+            .into_iter()
+            .map(|(coordinate, entry)| LockedRef {
+                coordinate: coordinate.to_string(),
+                entry,
+            })
+            .collect(),
+    };
+    serde_json::to_writer_pretty(writer, &lock)?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn glide_entry() -> LockEntry {
+        LockEntry {
+            repository: "https://repo1.maven.org/maven2".into(),
+            version: "4.16.0".into(),
+            pom_url: "https://repo1.maven.org/maven2/com/github/bumptech/glide/glide/4.16.0/glide-4.16.0.pom".into(),
+            sha256: "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855".into(),
+        }
+    }
+
+    #[test]
+    fn write_and_read_lock() {
+        let coordinate = "com.github.bumptech.glide:glide:4.16.0"
+            .parse::<Coordinate>()
+            .unwrap();
+        let entry = glide_entry();
+
+        let mut buf = vec![];
+        write_lock(&mut buf, [(&coordinate, &entry)]).unwrap();
+        let actual = read_lock(buf.as_slice()).unwrap();
+
+        let expected = BTreeMap::from([(coordinate, entry)]);
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn read_lock_unsupported_version() {
+        assert!(read_lock(r#"{"version":0,"locked":[]}"#.as_bytes()).is_err());
+    }
+
+    #[test]
+    fn drifts() {
+        let locked = glide_entry();
+        let actual = LockEntry {
+            sha256: "0000".into(),
+            ..glide_entry()
+        };
+
+        assert!(locked.drifts(&locked).is_empty());
+        assert_eq!(
+            vec![format!("sha256: {} -> 0000", locked.sha256)],
+            locked.drifts(&actual)
+        );
+    }
+}
@@ -14,8 +14,26 @@
  * limitations under the License.
  */
 
+pub use artifact_file::{artifact_extension, parse_checksum, ArtifactFile};
+pub use bom::write_bom_pom;
 pub use maven_metadata::{parse_maven_metadata, Dependency};
-pub use pom::{parse_pom, POM};
+pub use pom::{parse_pom, parse_pom_dependencies, PomDependency, Relocation, POM};
+pub use repository_config::{is_google_maven_artifact, is_gradle_plugin_marker, RepositoryConfig};
+pub use settings::{
+    parse_maven_settings, MavenSettings, Mirror, Proxy, Server, CENTRAL_REPOSITORY_ID,
+    GOOGLE_REPOSITORY_ID,
+};
+pub use version_selector::{
+    DefaultVersionSelector, FixedVersionSelector, LatestVersionSelector, ReleaseVersionSelector,
+    RichVersionSelector, VersionSelector,
+};
+pub use xml_validation::{validate_maven_metadata, validate_pom};
 
+mod artifact_file;
+mod bom;
 mod maven_metadata;
 mod pom;
+mod repository_config;
+mod settings;
+mod version_selector;
+mod xml_validation;
@@ -0,0 +1,98 @@
+/*
+ * Copyright 2023 sukawasatoru
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Size and digest of the main artifact (jar/aar) published with a POM.
+#[derive(Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub struct ArtifactFile {
+    pub file_name: String,
+    pub size: Option<u64>,
+    pub sha256: Option<String>,
+}
+
+/// Returns the file extension of the main artifact for the `<packaging>`.
+///
+/// https://maven.apache.org/ref/3.9.4/maven-core/artifact-handlers.html
+pub fn artifact_extension(packaging: Option<&str>) -> Option<&str> {
+    match packaging {
+        None | Some("jar") | Some("bundle") | Some("maven-plugin") | Some("ejb") => Some("jar"),
+        Some("pom") => None,
+        Some(data) => Some(data),
+    }
+}
+
+/// Parse the content of the `.sha256` file.
+///
+/// Some repositories publish `<digest>` and others publish `<digest>  <file name>`.
+pub fn parse_checksum(checksum: &str) -> Fallible<String> {
+    let digest = checksum
+        .split_whitespace()
+        .next()
+        .context("empty checksum")?
+        .to_ascii_lowercase();
+
+    ensure!(
+        digest.len() == 64 && digest.chars().all(|data| data.is_ascii_hexdigit()),
+        "unexpected sha-256 format: {}",
+        digest,
+    );
+
+    Ok(digest)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn artifact_extension_default() {
+        assert_eq!(Some("jar"), artifact_extension(None));
+        assert_eq!(Some("jar"), artifact_extension(Some("bundle")));
+        assert_eq!(Some("aar"), artifact_extension(Some("aar")));
+        assert_eq!(None, artifact_extension(Some("pom")));
+    }
+
+    #[test]
+    fn parse_checksum_digest_only() {
+        let actual =
+            parse_checksum("d4ebbd1b8d3a3ea7a4a8a59ec1f5cbbc4c7e1ae1f4ef12a4a1b2b0ed2cbc2ad9\n")
+                .unwrap();
+        assert_eq!(
+            "d4ebbd1b8d3a3ea7a4a8a59ec1f5cbbc4c7e1ae1f4ef12a4a1b2b0ed2cbc2ad9",
+            actual,
+        );
+    }
+
+    #[test]
+    fn parse_checksum_with_file_name() {
+        let actual = parse_checksum(
+            "D4EBBD1B8D3A3EA7A4A8A59EC1F5CBBC4C7E1AE1F4EF12A4A1B2B0ED2CBC2AD9  glide-4.16.0.aar",
+        )
+        .unwrap();
+        assert_eq!(
+            "d4ebbd1b8d3a3ea7a4a8a59ec1f5cbbc4c7e1ae1f4ef12a4a1b2b0ed2cbc2ad9",
+            actual,
+        );
+    }
+
+    #[test]
+    fn parse_checksum_unexpected_format() {
+        assert!(parse_checksum("<html></html>").is_err());
+        assert!(parse_checksum("").is_err());
+    }
+}
@@ -0,0 +1,194 @@
+/*
+ * Copyright 2023 sukawasatoru
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::function::maven::POM;
+use crate::model::Coordinate;
+use crate::prelude::*;
+use quick_xml::escape::escape;
+use std::collections::HashSet;
+use std::io::Write;
+
+/// Write the Maven BOM `pom.xml` that pins the version of every entry in the
+/// `<dependencyManagement>`.
+///
+/// The version of the coordinate is preferred and the version of the POM is used as fallback.
+/// The entry that has no version or is already pinned is skipped.
+///
+/// https://maven.apache.org/guides/introduction/introduction-to-dependency-mechanism.html#bill-of-materials-bom-poms
+pub fn write_bom_pom<'a, W, I>(mut writer: W, bom: &Coordinate, entries: I) -> Fallible<()>
+where
+    W: Write,
+    I: IntoIterator<Item = (&'a Coordinate, &'a POM)>,
+{
+    let bom_version = bom
+        .version
+        .as_deref()
+        .with_context(|| format!("missing version of the BOM: {}", bom))?;
+
+    writeln!(writer, r#"<?xml version="1.0" encoding="UTF-8"?>"#)?;
+    writeln!(
+        writer,
+        r#"<project xmlns="http://maven.apache.org/POM/4.0.0" xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance" xsi:schemaLocation="http://maven.apache.org/POM/4.0.0 https://maven.apache.org/xsd/maven-4.0.0.xsd">"#
+    )?;
+    writeln!(writer, "  <modelVersion>4.0.0</modelVersion>")?;
+    writeln!(writer, "  <groupId>{}</groupId>", escape(&bom.group_id))?;
+    writeln!(
+        writer,
+        "  <artifactId>{}</artifactId>",
+        escape(&bom.artifact_id)
+    )?;
+    writeln!(writer, "  <version>{}</version>", escape(bom_version))?;
+    writeln!(writer, "  <packaging>pom</packaging>")?;
+    writeln!(writer, "  <dependencyManagement>")?;
+    writeln!(writer, "    <dependencies>")?;
+
+    let mut pinned = HashSet::new();
+    for (coordinate, pom) in entries {
+        let version = match coordinate.version.as_deref().or(pom.version.as_deref()) {
+            Some(data) => data,
+            None => {
+                warn!(dep_name = %coordinate, "skip the entry that has no version");
+                continue;
+            }
+        };
+
+        // `<type>` is the extension and defaults to `jar`.
+        let dependency_type = coordinate
+            .packaging
+            .as_deref()
+            .or(pom.packaging.as_deref())
+            .filter(|data| !matches!(*data, "jar" | "bundle"));
+
+        if !pinned.insert((
+            &coordinate.group_id,
+            &coordinate.artifact_id,
+            &coordinate.classifier,
+            dependency_type,
+        )) {
+            warn!(dep_name = %coordinate, %version, "skip the entry that is already pinned");
+            continue;
+        }
+
+        writeln!(writer, "      <dependency>")?;
+        writeln!(
+            writer,
+            "        <groupId>{}</groupId>",
+            escape(&coordinate.group_id)
+        )?;
+        writeln!(
+            writer,
+            "        <artifactId>{}</artifactId>",
+            escape(&coordinate.artifact_id)
+        )?;
+        writeln!(writer, "        <version>{}</version>", escape(version))?;
+        if let Some(dependency_type) = dependency_type {
+            writeln!(writer, "        <type>{}</type>", escape(dependency_type))?;
+        }
+        if let Some(classifier) = &coordinate.classifier {
+            writeln!(
+                writer,
+                "        <classifier>{}</classifier>",
+                escape(classifier)
+            )?;
+        }
+        writeln!(writer, "      </dependency>")?;
+    }
+
+    writeln!(writer, "    </dependencies>")?;
+    writeln!(writer, "  </dependencyManagement>")?;
+    writeln!(writer, "</project>")?;
+
+    writer.flush()?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::function::maven::parse_pom;
+
+    #[test]
+    fn write_bom_pom_pin_versions() {
+        let core_ktx = POM {
+            packaging: Some("aar".into()),
+            ..POM::new(None, "core-ktx", "1.12.0")
+        };
+        let okhttp = POM {
+            packaging: Some("jar".into()),
+            ..POM::new(None, "okhttp", "4.11.0")
+        };
+        let lwjgl = POM::new(None, "lwjgl", "3.3.3");
+
+        let mut actual = vec![];
+        write_bom_pom(
+            &mut actual,
+            &"com.example:third-party-bom:1.0.0".parse().unwrap(),
+            [
+                (&"androidx.core:core-ktx:1.9.0".parse().unwrap(), &core_ktx),
+                (&"com.squareup.okhttp3:okhttp".parse().unwrap(), &okhttp),
+                (
+                    &"com.squareup.okhttp3:okhttp:4.9.3".parse().unwrap(),
+                    &okhttp,
+                ),
+                (
+                    &"org.lwjgl:lwjgl:3.3.3:natives-linux".parse().unwrap(),
+                    &lwjgl,
+                ),
+            ],
+        )
+        .unwrap();
+
+        let expected = r#"<?xml version="1.0" encoding="UTF-8"?>
+<project xmlns="http://maven.apache.org/POM/4.0.0" xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance" xsi:schemaLocation="http://maven.apache.org/POM/4.0.0 https://maven.apache.org/xsd/maven-4.0.0.xsd">
+  <modelVersion>4.0.0</modelVersion>
+  <groupId>com.example</groupId>
+  <artifactId>third-party-bom</artifactId>
+  <version>1.0.0</version>
+  <packaging>pom</packaging>
+  <dependencyManagement>
+    <dependencies>
+      <dependency>
+        <groupId>androidx.core</groupId>
+        <artifactId>core-ktx</artifactId>
+        <version>1.9.0</version>
+        <type>aar</type>
+      </dependency>
+      <dependency>
+        <groupId>com.squareup.okhttp3</groupId>
+        <artifactId>okhttp</artifactId>
+        <version>4.11.0</version>
+      </dependency>
+      <dependency>
+        <groupId>org.lwjgl</groupId>
+        <artifactId>lwjgl</artifactId>
+        <version>3.3.3</version>
+        <classifier>natives-linux</classifier>
+      </dependency>
+    </dependencies>
+  </dependencyManagement>
+</project>
+"#;
+
+        let actual = String::from_utf8(actual).unwrap();
+        assert_eq!(expected, actual);
+
+        // readable by the own parser.
+        let parsed = parse_pom(&actual).unwrap();
+        assert_eq!("third-party-bom", parsed.artifact_id);
+        assert_eq!(Some("pom".into()), parsed.packaging);
+    }
+}
@@ -31,6 +31,12 @@ pub struct Dependency {
     pub version: Option<String>,
     pub latest_version: Option<String>,
     pub release_version: Option<String>,
+
+    /// `<versions>` in the published order.
+    pub versions: Vec<String>,
+
+    /// `yyyyMMddHHmmss`.
+    pub last_updated: Option<String>,
 }
 
 impl From<Metadata> for Dependency {
@@ -41,6 +47,12 @@ impl From<Metadata> for Dependency {
             version: value.version,
             latest_version: value.versioning.latest,
             release_version: value.versioning.release,
+            versions: value
+                .versioning
+                .versions
+                .map(|data| data.field)
+                .unwrap_or_default(),
+            last_updated: value.versioning.last_updated,
         }
     }
 }
@@ -63,12 +75,65 @@ struct Metadata {
 struct Versioning {
     latest: Option<String>,
     release: Option<String>,
+    versions: Option<Versions>,
+
+    #[serde(rename = "lastUpdated")]
+    last_updated: Option<String>,
+}
+
+#[derive(Deserialize, PartialEq)]
+struct Versions {
+    #[serde(rename = "version", default)]
+    field: Vec<String>,
 }
 
 #[cfg(test)]
 mod tests {
+    use super::*;
     use serde::{Deserialize, Serialize};
 
+    #[test]
+    fn parse_maven_metadata_versions() {
+        let source = r#"<?xml version="1.0" encoding="UTF-8"?>
+<metadata>
+  <groupId>com.github.bumptech.glide</groupId>
+  <artifactId>glide</artifactId>
+  <versioning>
+    <latest>4.16.0</latest>
+    <release>4.16.0</release>
+    <versions>
+      <version>4.15.0</version>
+      <version>4.15.1</version>
+      <version>4.16.0</version>
+    </versions>
+    <lastUpdated>20230821070349</lastUpdated>
+  </versioning>
+</metadata>
+"#;
+
+        let actual = parse_maven_metadata(source).unwrap();
+        assert_eq!(vec!["4.15.0", "4.15.1", "4.16.0"], actual.versions);
+        assert_eq!(Some("20230821070349".into()), actual.last_updated);
+    }
+
+    #[test]
+    fn parse_maven_metadata_without_versions() {
+        let source = r#"<?xml version="1.0" encoding="UTF-8"?>
+<metadata>
+  <groupId>com.example</groupId>
+  <artifactId>example</artifactId>
+  <version>1.0.0</version>
+  <versioning>
+    <release>1.0.0</release>
+  </versioning>
+</metadata>
+"#;
+
+        let actual = parse_maven_metadata(source).unwrap();
+        assert!(actual.versions.is_empty());
+        assert_eq!(None, actual.last_updated);
+    }
+
     #[test]
     #[ignore]
     fn quick_xml_playground() {
@@ -14,19 +14,168 @@
  * limitations under the License.
  */
 
-use crate::model::SPDX;
-use crate::Fallible;
-use serde::Deserialize;
+use crate::model::{Coordinate, SPDX};
+use crate::prelude::*;
+use quick_xml::escape::escape;
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
 use url::Url;
 
 /// https://maven.apache.org/pom.html
+///
+/// `${name}` is substituted with the `<properties>` and the built-in `project.*` properties.
 pub fn parse_pom(xml: &str) -> Fallible<POM> {
-    let parsed = quick_xml::de::from_str::<Project>(xml)?;
+    let xml = interpolate(xml);
+    let parsed = quick_xml::de::from_str::<Project>(&xml)?;
 
     Ok(parsed.into())
 }
 
-#[derive(Debug, Eq, PartialEq)]
+/// Parse the `<dependencies>` of the project file such as the `pom.xml` of a Maven project.
+///
+/// The missing version is resolved with the `<dependencyManagement>` of the same file. The
+/// dependencies of the `test`, `provided` and `system` scopes are skipped because they are not in
+/// the runtime classpath. Returns an error if the version of any dependency is missing or has the
+/// property that is not defined in the same file, such as the version of the parent BOM.
+pub fn parse_pom_dependencies(xml: &str) -> Fallible<Vec<Coordinate>> {
+    let xml = interpolate(xml);
+    let parsed = quick_xml::de::from_str::<DependencyProject>(&xml)?;
+
+    let managed = parsed
+        .dependency_management
+        .and_then(|data| data.dependencies)
+        .map(|data| data.field)
+        .unwrap_or_default();
+    let mut coordinates = vec![];
+    let mut unresolved = vec![];
+    for dependency in parsed
+        .dependencies
+        .map(|data| data.field)
+        .unwrap_or_default()
+    {
+        let module_id = format!("{}:{}", dependency.group_id, dependency.artifact_id);
+        if let Some(scope @ ("test" | "provided" | "system")) = dependency.scope.as_deref() {
+            debug!(%module_id, %scope, "skip the dependency that is not in the runtime classpath");
+            continue;
+        }
+
+        let version = dependency.version.clone().or_else(|| {
+            managed
+                .iter()
+                .find(|data| {
+                    data.group_id == dependency.group_id
+                        && data.artifact_id == dependency.artifact_id
+                })
+                .and_then(|data| data.version.clone())
+        });
+        let version = match version {
+            Some(data) if data.contains("${") => {
+                unresolved.push(format!("{} ({})", module_id, data));
+                continue;
+            }
+            Some(data) => data,
+            None => {
+                unresolved.push(format!("{} (missing)", module_id));
+                continue;
+            }
+        };
+
+        coordinates.push(Coordinate {
+            version: Some(version),
+            classifier: dependency.classifier,
+            packaging: dependency.r#type.filter(|data| data != "jar"),
+            ..Coordinate::new(dependency.group_id, dependency.artifact_id)
+        });
+    }
+
+    if !unresolved.is_empty() {
+        bail!(
+            "the version of the dependencies is not resolved: {}",
+            unresolved.join(", ")
+        );
+    }
+
+    Ok(coordinates)
+}
+
+/// https://maven.apache.org/pom.html#properties
+///
+/// Returns the `xml` as is if the properties cannot be parsed so that the POM is parsed without
+/// the interpolation.
+fn interpolate(xml: &str) -> Cow<str> {
+    if !xml.contains("${") {
+        return Cow::Borrowed(xml);
+    }
+
+    let source = match quick_xml::de::from_str::<PropertySource>(xml) {
+        Ok(data) => data,
+        Err(e) => {
+            warn!(?e, "failed to parse the properties. skip the interpolation");
+            return Cow::Borrowed(xml);
+        }
+    };
+    let mut properties = source.properties.unwrap_or_default();
+    let parent = source.parent.unwrap_or_default();
+    for (name, value) in [
+        (
+            "groupId",
+            source.group_id.or_else(|| parent.group_id.clone()),
+        ),
+        ("artifactId", Some(source.artifact_id)),
+        ("version", source.version.or_else(|| parent.version.clone())),
+        ("name", source.name),
+        ("url", source.url),
+        ("inceptionYear", source.inception_year),
+        ("parent.groupId", parent.group_id),
+        ("parent.version", parent.version),
+    ] {
+        if let Some(value) = value {
+            // `pom.*` is the deprecated alias.
+            properties.insert(format!("pom.{}", name), value.clone());
+            properties.insert(format!("project.{}", name), value);
+        }
+    }
+
+    // resolve the properties that refer to other properties.
+    for _ in 0..8 {
+        let resolved = properties
+            .iter()
+            .map(|(name, value)| (name.clone(), substitute(value, &properties, false)))
+            .collect::<HashMap<_, _>>();
+        if resolved == properties {
+            break;
+        }
+        properties = resolved;
+    }
+
+    Cow::Owned(substitute(xml, &properties, true))
+}
+
+/// Substitute `${name}` with the `properties`. The unknown property is kept as is.
+fn substitute(text: &str, properties: &HashMap<String, String>, escape_value: bool) -> String {
+    let mut substituted = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find("${") {
+        let end = match rest[start..].find('}') {
+            Some(data) => start + data,
+            None => break,
+        };
+        substituted.push_str(&rest[..start]);
+        match properties.get(&rest[start + 2..end]) {
+            Some(value) if escape_value => substituted.push_str(&escape(value)),
+            Some(value) => substituted.push_str(value),
+            None => substituted.push_str(&rest[start..=end]),
+        }
+        rest = &rest[end + 1..];
+    }
+    substituted.push_str(rest);
+
+    substituted
+}
+
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
 pub struct POM {
     pub group_id: Option<String>,
     pub artifact_id: String,
@@ -34,11 +183,150 @@ pub struct POM {
     pub packaging: Option<String>,
     pub name: Option<String>,
     pub description: Option<String>,
+    pub url: Option<String>,
+    pub inception_year: Option<String>,
+    pub organization: Option<String>,
     pub licenses: Vec<SPDX>,
+
+    /// `<url>` of the `<license>`s.
+    pub license_urls: Vec<String>,
+
+    /// `<scm><url>` that is the browsable repository of the source code.
+    pub scm_url: Option<String>,
+
+    /// Root URL of the repository that served the POM. Filled in by [crate::retrieve_maven_lib].
+    pub repository: Option<String>,
+
+    /// `<distributionManagement><relocation>` of the relocation stub.
+    ///
+    /// [crate::retrieve_maven_lib] follows it so the other fields of the returned POM are of the
+    /// relocated artifact and this has all parts of the relocated coordinate.
+    pub relocation: Option<Relocation>,
+
+    /// `<dependencies>` that the artifact requires. The missing version is resolved with the
+    /// `<dependencyManagement>` of the same POM.
+    #[serde(default)]
+    pub dependencies: Vec<PomDependency>,
+
+    /// `<dependencyManagement>` such as the managed artifacts of the BOM.
+    #[serde(default)]
+    pub dependency_management: Vec<PomDependency>,
+}
+
+/// `<dependency>` of the `<dependencies>` and the `<dependencyManagement>`.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct PomDependency {
+    pub group_id: String,
+    pub artifact_id: String,
+    pub version: Option<String>,
+
+    /// `<scope>` that defaults to `compile` as Maven does if `None`.
+    pub scope: Option<String>,
+
+    pub optional: bool,
+}
+
+impl PomDependency {
+    /// Coordinate of the dependency that has the version if any.
+    pub fn coordinate(&self) -> Coordinate {
+        let coordinate = Coordinate::new(self.group_id.clone(), self.artifact_id.clone());
+        match &self.version {
+            Some(version) => coordinate.with_version(version.clone()),
+            None => coordinate,
+        }
+    }
+}
+
+impl From<ProjectDependency> for PomDependency {
+    fn from(value: ProjectDependency) -> Self {
+        Self {
+            group_id: value.group_id,
+            artifact_id: value.artifact_id,
+            version: value.version,
+            scope: value.scope,
+            optional: value
+                .optional
+                .map_or(false, |data| data.trim().eq_ignore_ascii_case("true")),
+        }
+    }
+}
+
+/// https://maven.apache.org/guides/mini/guide-relocation.html
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct Relocation {
+    pub group_id: Option<String>,
+    pub artifact_id: Option<String>,
+    pub version: Option<String>,
+    pub message: Option<String>,
+}
+
+impl Display for Relocation {
+    /// `group:artifact:version` without the missing parts.
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let parts = [&self.group_id, &self.artifact_id, &self.version]
+            .into_iter()
+            .flatten()
+            .map(String::as_str)
+            .collect::<Vec<_>>();
+        f.write_str(&parts.join(":"))
+    }
+}
+
+impl POM {
+    /// POM that only has the coordinate such as the artifact without any `<project>` information.
+    pub fn new(group_id: Option<&str>, artifact_id: &str, version: &str) -> Self {
+        Self {
+            group_id: group_id.map(Into::into),
+            artifact_id: artifact_id.into(),
+            version: Some(version.into()),
+            ..Default::default()
+        }
+    }
+
+    /// Synthesize the copyright line from `<inceptionYear>` and `<organization>`.
+    pub fn copyright(&self) -> Option<String> {
+        let organization = self.organization.as_deref()?;
+        Some(match self.inception_year.as_deref() {
+            Some(year) => format!("Copyright {} {}", year, organization),
+            None => format!("Copyright {}", organization),
+        })
+    }
+
+    /// `<packaging>` that defaults to `jar` as Maven does.
+    pub fn packaging_or_default(&self) -> &str {
+        self.packaging.as_deref().unwrap_or("jar")
+    }
 }
 
 impl From<Project> for POM {
     fn from(value: Project) -> Self {
+        let dependency_management = value
+            .dependency_management
+            .and_then(|data| data.dependencies)
+            .map(|data| data.field)
+            .unwrap_or_default()
+            .into_iter()
+            .map(PomDependency::from)
+            .collect::<Vec<_>>();
+        let dependencies = value
+            .dependencies
+            .map(|data| data.field)
+            .unwrap_or_default()
+            .into_iter()
+            .map(|data| {
+                let mut dependency = PomDependency::from(data);
+                if dependency.version.is_none() {
+                    dependency.version = dependency_management
+                        .iter()
+                        .find(|data| {
+                            data.group_id == dependency.group_id
+                                && data.artifact_id == dependency.artifact_id
+                        })
+                        .and_then(|data| data.version.clone());
+                }
+                dependency
+            })
+            .collect();
         Self {
             group_id: value.group_id,
             artifact_id: value.artifact_id,
@@ -46,16 +334,58 @@ impl From<Project> for POM {
             packaging: value.packaging,
             name: value.name,
             description: value.description,
+            url: value.url,
+            inception_year: value.inception_year,
+            // the organization of the developers is the copyright holder of many libraries that
+            // have no `<organization>`.
+            organization: value.organization.and_then(|data| data.name).or_else(|| {
+                value
+                    .developers
+                    .into_iter()
+                    .flat_map(|data| data.field)
+                    .find_map(|data| data.organization)
+            }),
             licenses: value
+                .licenses
+                .as_ref()
+                .map(|licenses| {
+                    licenses
+                        .field
+                        .iter()
+                        .map(|data| match data.name.parse().expect("unexpected spdx") {
+                            SPDX::Other(name) => data
+                                .url
+                                .as_ref()
+                                .and_then(|url| SPDX::from_url(url.as_str()))
+                                .unwrap_or(SPDX::Other(name)),
+                            license => license,
+                        })
+                        .collect()
+                })
+                .unwrap_or_else(Vec::new),
+            license_urls: value
                 .licenses
                 .map(|licenses| {
                     licenses
                         .field
                         .into_iter()
-                        .map(|data| data.name.parse().expect("unexpected spdx"))
+                        .filter_map(|data| data.url.map(|url| url.to_string()))
                         .collect()
                 })
                 .unwrap_or_else(Vec::new),
+            scm_url: value.scm.and_then(|data| data.url),
+            repository: None,
+            relocation: value
+                .distribution_management
+                .and_then(|data| data.relocation)
+                .map(|data| Relocation {
+                    group_id: data.group_id,
+                    artifact_id: data.artifact_id,
+                    version: data.version,
+                    message: data.message,
+                }),
+            dependencies,
+            dependency_management,
         }
     }
 }
@@ -73,7 +403,124 @@ struct Project {
     packaging: Option<String>,
     name: Option<String>,
     description: Option<String>,
+    url: Option<String>,
+
+    #[serde(rename = "inceptionYear")]
+    inception_year: Option<String>,
+
+    organization: Option<Organization>,
+    developers: Option<Developers>,
     licenses: Option<Licenses>,
+    scm: Option<Scm>,
+
+    #[serde(rename = "distributionManagement")]
+    distribution_management: Option<DistributionManagement>,
+
+    #[serde(rename = "dependencyManagement")]
+    dependency_management: Option<DependencyManagement>,
+
+    dependencies: Option<ProjectDependencies>,
+}
+
+/// Subset of the [Project] for [parse_pom_dependencies].
+#[derive(Deserialize)]
+struct DependencyProject {
+    #[serde(rename = "dependencyManagement")]
+    dependency_management: Option<DependencyManagement>,
+
+    dependencies: Option<ProjectDependencies>,
+}
+
+#[derive(Deserialize, PartialEq)]
+struct DependencyManagement {
+    dependencies: Option<ProjectDependencies>,
+}
+
+#[derive(Deserialize, PartialEq)]
+struct ProjectDependencies {
+    #[serde(rename = "$value", default)]
+    field: Vec<ProjectDependency>,
+}
+
+#[derive(Deserialize, PartialEq)]
+struct ProjectDependency {
+    #[serde(rename = "groupId")]
+    group_id: String,
+
+    #[serde(rename = "artifactId")]
+    artifact_id: String,
+
+    version: Option<String>,
+    r#type: Option<String>,
+    classifier: Option<String>,
+    scope: Option<String>,
+    optional: Option<String>,
+}
+
+/// Subset of the [Project] for the interpolation.
+#[derive(Deserialize)]
+struct PropertySource {
+    #[serde(rename = "groupId")]
+    group_id: Option<String>,
+
+    #[serde(rename = "artifactId")]
+    artifact_id: String,
+
+    version: Option<String>,
+    name: Option<String>,
+    url: Option<String>,
+
+    #[serde(rename = "inceptionYear")]
+    inception_year: Option<String>,
+
+    parent: Option<Parent>,
+    properties: Option<HashMap<String, String>>,
+}
+
+#[derive(Default, Deserialize)]
+struct Parent {
+    #[serde(rename = "groupId")]
+    group_id: Option<String>,
+
+    version: Option<String>,
+}
+
+#[derive(Deserialize, PartialEq)]
+struct Organization {
+    name: Option<String>,
+}
+
+#[derive(Deserialize, PartialEq)]
+struct Developers {
+    #[serde(rename = "$value", default)]
+    field: Vec<Developer>,
+}
+
+#[derive(Deserialize, PartialEq)]
+struct Developer {
+    organization: Option<String>,
+}
+
+#[derive(Deserialize, PartialEq)]
+struct Scm {
+    url: Option<String>,
+}
+
+#[derive(Deserialize, PartialEq)]
+struct DistributionManagement {
+    relocation: Option<ProjectRelocation>,
+}
+
+#[derive(Deserialize, PartialEq)]
+struct ProjectRelocation {
+    #[serde(rename = "groupId")]
+    group_id: Option<String>,
+
+    #[serde(rename = "artifactId")]
+    artifact_id: Option<String>,
+
+    version: Option<String>,
+    message: Option<String>,
 }
 
 #[derive(Deserialize, PartialEq)]
@@ -85,6 +532,325 @@ struct Licenses {
 #[derive(Deserialize, PartialEq)]
 struct License {
     name: String,
-    url: Url,
+    url: Option<Url>,
     distribution: Option<String>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_pom_dependencies_managed_versions() {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<project xmlns="http://maven.apache.org/POM/4.0.0">
+  <modelVersion>4.0.0</modelVersion>
+  <groupId>com.example</groupId>
+  <artifactId>service</artifactId>
+  <version>1.0.0</version>
+  <properties>
+    <okhttp.version>4.12.0</okhttp.version>
+  </properties>
+  <dependencyManagement>
+    <dependencies>
+      <dependency>
+        <groupId>com.squareup.okhttp3</groupId>
+        <artifactId>okhttp</artifactId>
+        <version>${okhttp.version}</version>
+      </dependency>
+    </dependencies>
+  </dependencyManagement>
+  <dependencies>
+    <dependency>
+      <groupId>com.squareup.okhttp3</groupId>
+      <artifactId>okhttp</artifactId>
+    </dependency>
+    <dependency>
+      <groupId>com.google.guava</groupId>
+      <artifactId>guava</artifactId>
+      <version>32.1.3-jre</version>
+      <type>jar</type>
+    </dependency>
+    <dependency>
+      <groupId>io.netty</groupId>
+      <artifactId>netty-transport-native-epoll</artifactId>
+      <version>4.1.100.Final</version>
+      <classifier>linux-x86_64</classifier>
+    </dependency>
+    <dependency>
+      <groupId>junit</groupId>
+      <artifactId>junit</artifactId>
+      <version>4.13.2</version>
+      <scope>test</scope>
+    </dependency>
+  </dependencies>
+</project>
+"#;
+
+        let expected = vec![
+            Coordinate::new("com.squareup.okhttp3", "okhttp").with_version("4.12.0"),
+            Coordinate::new("com.google.guava", "guava").with_version("32.1.3-jre"),
+            Coordinate {
+                classifier: Some("linux-x86_64".into()),
+                ..Coordinate::new("io.netty", "netty-transport-native-epoll")
+                    .with_version("4.1.100.Final")
+            },
+        ];
+        assert_eq!(expected, parse_pom_dependencies(xml).unwrap());
+    }
+
+    #[test]
+    fn parse_pom_dependencies_unresolved_versions() {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<project xmlns="http://maven.apache.org/POM/4.0.0">
+  <modelVersion>4.0.0</modelVersion>
+  <groupId>com.example</groupId>
+  <artifactId>service</artifactId>
+  <version>1.0.0</version>
+  <dependencies>
+    <dependency>
+      <groupId>com.google.guava</groupId>
+      <artifactId>guava</artifactId>
+      <version>32.1.3-jre</version>
+    </dependency>
+    <dependency>
+      <groupId>com.squareup.okhttp3</groupId>
+      <artifactId>okhttp</artifactId>
+      <version>${okhttp.version}</version>
+    </dependency>
+    <dependency>
+      <groupId>org.slf4j</groupId>
+      <artifactId>slf4j-api</artifactId>
+    </dependency>
+  </dependencies>
+</project>
+"#;
+
+        let actual = parse_pom_dependencies(xml).unwrap_err().to_string();
+        assert!(
+            actual.contains("com.squareup.okhttp3:okhttp (${okhttp.version})"),
+            "{}",
+            actual,
+        );
+        assert!(
+            actual.contains("org.slf4j:slf4j-api (missing)"),
+            "{}",
+            actual
+        );
+        assert!(!actual.contains("guava"), "{}", actual);
+    }
+
+    #[test]
+    fn parse_pom_interpolate_properties() {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<project xmlns="http://maven.apache.org/POM/4.0.0">
+  <modelVersion>4.0.0</modelVersion>
+  <parent>
+    <groupId>org.example</groupId>
+    <artifactId>parent</artifactId>
+    <version>1.0.0</version>
+  </parent>
+  <artifactId>example</artifactId>
+  <version>${revision}</version>
+  <name>${project.artifactId} &amp; friends</name>
+  <url>https://example.org/${project.artifactId}</url>
+  <properties>
+    <revision>${base.version}-jre</revision>
+    <base.version>2.1.0</base.version>
+    <vendor>Example &amp; Co.</vendor>
+  </properties>
+  <organization>
+    <name>${vendor}</name>
+  </organization>
+  <licenses>
+    <license>
+      <name>Apache-2.0</name>
+      <url>${project.url}/LICENSE</url>
+    </license>
+  </licenses>
+  <description>${unknown}</description>
+</project>
+"#;
+
+        let actual = parse_pom(xml).unwrap();
+        let expected = POM {
+            artifact_id: "example".into(),
+            version: Some("2.1.0-jre".into()),
+            name: Some("example & friends".into()),
+            description: Some("${unknown}".into()),
+            url: Some("https://example.org/example".into()),
+            organization: Some("Example & Co.".into()),
+            licenses: vec![SPDX::Apache20],
+            license_urls: vec!["https://example.org/example/LICENSE".into()],
+            ..Default::default()
+        };
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn parse_pom_malformed_properties() {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<project xmlns="http://maven.apache.org/POM/4.0.0">
+  <modelVersion>4.0.0</modelVersion>
+  <groupId>com.example</groupId>
+  <artifactId>example</artifactId>
+  <version>1.0.0</version>
+  <name>${project.artifactId}</name>
+  <properties>
+    <nested>
+      <value>1</value>
+    </nested>
+  </properties>
+  <licenses>
+    <license>
+      <name>MIT License</name>
+    </license>
+  </licenses>
+</project>
+"#;
+
+        let actual = parse_pom(xml).unwrap();
+        assert_eq!("example", actual.artifact_id);
+        assert_eq!(Some("${project.artifactId}"), actual.name.as_deref());
+        assert_eq!(vec![SPDX::MIT], actual.licenses);
+    }
+
+    #[test]
+    fn parse_pom_developer_organization() {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<project xmlns="http://maven.apache.org/POM/4.0.0">
+  <modelVersion>4.0.0</modelVersion>
+  <groupId>com.squareup.okio</groupId>
+  <artifactId>okio</artifactId>
+  <version>3.6.0</version>
+  <inceptionYear>2013</inceptionYear>
+  <developers>
+    <developer>
+      <name>Jake Wharton</name>
+    </developer>
+    <developer>
+      <name>Square, Inc.</name>
+      <organization>Square, Inc.</organization>
+    </developer>
+  </developers>
+</project>
+"#;
+
+        let actual = parse_pom(xml).unwrap();
+        assert_eq!(Some("Square, Inc.".into()), actual.organization);
+        assert_eq!(
+            Some("Copyright 2013 Square, Inc.".into()),
+            actual.copyright()
+        );
+    }
+
+    #[test]
+    fn parse_pom_dependencies_model() {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<project xmlns="http://maven.apache.org/POM/4.0.0">
+  <modelVersion>4.0.0</modelVersion>
+  <groupId>com.squareup.okhttp3</groupId>
+  <artifactId>okhttp</artifactId>
+  <version>4.12.0</version>
+  <dependencyManagement>
+    <dependencies>
+      <dependency>
+        <groupId>org.jetbrains.kotlin</groupId>
+        <artifactId>kotlin-bom</artifactId>
+        <version>1.8.21</version>
+        <type>pom</type>
+        <scope>import</scope>
+      </dependency>
+      <dependency>
+        <groupId>com.squareup.okio</groupId>
+        <artifactId>okio</artifactId>
+        <version>3.6.0</version>
+      </dependency>
+    </dependencies>
+  </dependencyManagement>
+  <dependencies>
+    <dependency>
+      <groupId>com.squareup.okio</groupId>
+      <artifactId>okio</artifactId>
+      <scope>compile</scope>
+    </dependency>
+    <dependency>
+      <groupId>org.conscrypt</groupId>
+      <artifactId>conscrypt-openjdk-uber</artifactId>
+      <version>2.5.2</version>
+      <scope>runtime</scope>
+      <optional>true</optional>
+    </dependency>
+  </dependencies>
+</project>
+"#;
+
+        let actual = parse_pom(xml).unwrap();
+        let expected = vec![
+            PomDependency {
+                group_id: "com.squareup.okio".into(),
+                artifact_id: "okio".into(),
+                version: Some("3.6.0".into()),
+                scope: Some("compile".into()),
+                optional: false,
+            },
+            PomDependency {
+                group_id: "org.conscrypt".into(),
+                artifact_id: "conscrypt-openjdk-uber".into(),
+                version: Some("2.5.2".into()),
+                scope: Some("runtime".into()),
+                optional: true,
+            },
+        ];
+        assert_eq!(expected, actual.dependencies);
+        assert_eq!(
+            vec![
+                "org.jetbrains.kotlin:kotlin-bom:1.8.21",
+                "com.squareup.okio:okio:3.6.0",
+            ],
+            actual
+                .dependency_management
+                .iter()
+                .map(|data| data.coordinate().to_string())
+                .collect::<Vec<_>>(),
+        );
+        assert_eq!(
+            Some("import"),
+            actual.dependency_management[0].scope.as_deref()
+        );
+    }
+
+    #[test]
+    fn parse_pom_relocation() {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<project xmlns="http://maven.apache.org/POM/4.0.0">
+  <modelVersion>4.0.0</modelVersion>
+  <groupId>mysql</groupId>
+  <artifactId>mysql-connector-java</artifactId>
+  <version>8.0.33</version>
+  <distributionManagement>
+    <relocation>
+      <groupId>com.mysql</groupId>
+      <artifactId>mysql-connector-j</artifactId>
+      <message>MySQL Connector/J artifacts moved to reverse-DNS compliant Maven 2+ coordinates.</message>
+    </relocation>
+  </distributionManagement>
+</project>
+"#;
+
+        let relocation = parse_pom(xml).unwrap().relocation.unwrap();
+        assert_eq!("com.mysql:mysql-connector-j", relocation.to_string());
+        assert_eq!(
+            Some(
+                "MySQL Connector/J artifacts moved to reverse-DNS compliant Maven 2+ coordinates."
+            ),
+            relocation.message.as_deref()
+        );
+    }
+
+    #[test]
+    fn substitute_unclosed() {
+        let properties = HashMap::from([("a".to_string(), "b".to_string())]);
+        assert_eq!("b ${a", substitute("${a} ${a", &properties, false));
+    }
+}
@@ -0,0 +1,118 @@
+/*
+ * Copyright 2023 sukawasatoru
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::model::Coordinate;
+
+/// Maven repositories that the retrieve functions select by the group id.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RepositoryConfig {
+    /// https://maven.google.com/web/index.html
+    pub google_maven: String,
+
+    /// https://central.sonatype.com/
+    pub maven_central: String,
+
+    /// https://plugins.gradle.org/ that has the plugin marker artifacts of the `plugins {}` block.
+    pub gradle_plugin_portal: String,
+}
+
+impl RepositoryConfig {
+    /// Use the `repo_root` for all artifacts such as the mock server of the tests.
+    pub fn single(repo_root: impl Into<String>) -> Self {
+        let repo_root = repo_root.into();
+        Self {
+            google_maven: repo_root.clone(),
+            maven_central: repo_root.clone(),
+            gradle_plugin_portal: repo_root,
+        }
+    }
+
+    /// Returns the repository root without the trailing slash.
+    pub fn select(&self, coordinate: &Coordinate) -> &str {
+        let repo_root = if is_google_maven_artifact(coordinate) {
+            &self.google_maven
+        } else if is_gradle_plugin_marker(coordinate) {
+            &self.gradle_plugin_portal
+        } else {
+            &self.maven_central
+        };
+        repo_root.trim_end_matches('/')
+    }
+}
+
+impl Default for RepositoryConfig {
+    fn default() -> Self {
+        Self {
+            google_maven: "https://dl.google.com/android/maven2".into(),
+            maven_central: "https://repo1.maven.org/maven2".into(),
+            gradle_plugin_portal: "https://plugins.gradle.org/m2".into(),
+        }
+    }
+}
+
+/// Whether the artifact is published only to Google Maven Repository.
+pub fn is_google_maven_artifact(coordinate: &Coordinate) -> bool {
+    let group_id = coordinate.group_id.as_str();
+    group_id.starts_with("androidx") || group_id.starts_with("com.google.android")
+}
+
+/// Whether the artifact is the plugin marker of the Gradle plugin such as
+/// `org.jetbrains.kotlin.android:org.jetbrains.kotlin.android.gradle.plugin` that is published
+/// only to Gradle Plugin Portal.
+///
+/// https://docs.gradle.org/current/userguide/plugins.html#sec:plugin_markers
+pub fn is_gradle_plugin_marker(coordinate: &Coordinate) -> bool {
+    coordinate.artifact_id == format!("{}.gradle.plugin", coordinate.group_id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn select() {
+        let config = RepositoryConfig {
+            google_maven: "http://google.example/".into(),
+            maven_central: "http://central.example".into(),
+            gradle_plugin_portal: "http://plugins.example/m2".into(),
+        };
+
+        assert_eq!(
+            "http://google.example",
+            config.select(&"androidx.core:core-ktx".parse().unwrap()),
+        );
+        assert_eq!(
+            "http://google.example",
+            config.select(&"com.google.android.material:material".parse().unwrap()),
+        );
+        assert_eq!(
+            "http://central.example",
+            config.select(&"com.github.bumptech.glide:glide".parse().unwrap()),
+        );
+        assert_eq!(
+            "http://plugins.example/m2",
+            config.select(
+                &"org.jetbrains.kotlin.android:org.jetbrains.kotlin.android.gradle.plugin"
+                    .parse()
+                    .unwrap()
+            ),
+        );
+        assert_eq!(
+            "http://central.example",
+            config.select(&"org.jetbrains.kotlin:kotlin-gradle-plugin".parse().unwrap()),
+        );
+    }
+}
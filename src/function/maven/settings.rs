@@ -0,0 +1,474 @@
+/*
+ * Copyright 2023 sukawasatoru
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::function::http_client::Credential;
+use crate::function::maven::RepositoryConfig;
+use crate::prelude::*;
+use serde::Deserialize;
+use url::Url;
+
+/// Repository id of [RepositoryConfig::maven_central] that `<mirrorOf>` and `<server>` refer to.
+pub const CENTRAL_REPOSITORY_ID: &str = "central";
+
+/// Repository id of [RepositoryConfig::google_maven] that is same as the Android Gradle Plugin.
+pub const GOOGLE_REPOSITORY_ID: &str = "google";
+
+/// Repository id of [RepositoryConfig::gradle_plugin_portal].
+pub const GRADLE_PLUGIN_PORTAL_REPOSITORY_ID: &str = "gradle-plugin-portal";
+
+/// Subset of the `settings.xml` of Maven for the repository routing, the authentication and the
+/// proxy.
+///
+/// https://maven.apache.org/settings.html
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct MavenSettings {
+    pub mirrors: Vec<Mirror>,
+    pub servers: Vec<Server>,
+    pub proxies: Vec<Proxy>,
+}
+
+/// https://maven.apache.org/guides/mini/guide-mirror-settings.html
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Mirror {
+    pub id: String,
+
+    /// Comma separated repository ids such as `central`, `*`, `external:*` and `!id`.
+    pub mirror_of: String,
+    pub url: String,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Server {
+    pub id: String,
+    pub username: Option<String>,
+    pub password: Option<String>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Proxy {
+    pub id: Option<String>,
+    pub active: bool,
+    pub protocol: String,
+    pub host: String,
+    pub port: u16,
+    pub username: Option<String>,
+    pub password: Option<String>,
+
+    /// `|` separated hosts that may have `*` at the start or the end.
+    pub non_proxy_hosts: Option<String>,
+}
+
+/// Parse the `settings.xml`.
+///
+/// `${env.NAME}` is substituted with the environment variable as Maven does for the passwords
+/// that are not written to the file. The encrypted passwords of `settings-security.xml` are not
+/// supported.
+pub fn parse_maven_settings(xml: &str) -> Fallible<MavenSettings> {
+    let parsed = quick_xml::de::from_str::<Settings>(xml)?;
+
+    let mirrors = parsed
+        .mirrors
+        .map(|data| data.field)
+        .unwrap_or_default()
+        .into_iter()
+        .map(|data| Mirror {
+            id: data.id,
+            mirror_of: data.mirror_of,
+            url: expand_env(&data.url),
+        })
+        .collect();
+    let servers = parsed
+        .servers
+        .map(|data| data.field)
+        .unwrap_or_default()
+        .into_iter()
+        .map(|data| Server {
+            id: data.id,
+            username: data.username.as_deref().map(expand_env),
+            password: data.password.as_deref().map(expand_env),
+        })
+        .collect();
+    let proxies = parsed
+        .proxies
+        .map(|data| data.field)
+        .unwrap_or_default()
+        .into_iter()
+        .map(|data| {
+            Ok(Proxy {
+                id: data.id,
+                active: data.active.map_or(true, |data| data.trim() == "true"),
+                protocol: data.protocol.unwrap_or_else(|| "http".into()),
+                host: expand_env(&data.host),
+                port: match data.port {
+                    Some(port) => port
+                        .trim()
+                        .parse()
+                        .with_context(|| format!("invalid port of the proxy: {}", port))?,
+                    None => 8080,
+                },
+                username: data.username.as_deref().map(expand_env),
+                password: data.password.as_deref().map(expand_env),
+                non_proxy_hosts: data.non_proxy_hosts,
+            })
+        })
+        .collect::<Fallible<_>>()?;
+
+    Ok(MavenSettings {
+        mirrors,
+        servers,
+        proxies,
+    })
+}
+
+impl MavenSettings {
+    /// Returns the mirror of the repository. The mirror of the exact id is preferred to the
+    /// patterns as Maven does.
+    pub fn find_mirror(&self, repository_id: &str, repository_url: &str) -> Option<&Mirror> {
+        self.mirrors
+            .iter()
+            .find(|data| data.mirror_of.trim() == repository_id)
+            .or_else(|| {
+                self.mirrors
+                    .iter()
+                    .find(|data| matches_mirror_of(&data.mirror_of, repository_id, repository_url))
+            })
+    }
+
+    /// Replace the repositories with the mirrors.
+    pub fn apply_mirrors(&self, repositories: &RepositoryConfig) -> RepositoryConfig {
+        let mirrored = |id: &str, url: &String| match self.find_mirror(id, url) {
+            Some(mirror) => {
+                debug!(repository = id, mirror = %mirror.id, "use the mirror");
+                mirror.url.clone()
+            }
+            None => url.clone(),
+        };
+        RepositoryConfig {
+            google_maven: mirrored(GOOGLE_REPOSITORY_ID, &repositories.google_maven),
+            maven_central: mirrored(CENTRAL_REPOSITORY_ID, &repositories.maven_central),
+            gradle_plugin_portal: mirrored(
+                GRADLE_PLUGIN_PORTAL_REPOSITORY_ID,
+                &repositories.gradle_plugin_portal,
+            ),
+        }
+    }
+
+    /// Returns the credentials of the `<server>`s for the repositories or their mirrors.
+    pub fn credentials(&self, repositories: &RepositoryConfig) -> Vec<Credential> {
+        let mut credentials = Vec::<Credential>::new();
+        for (id, url) in [
+            (GOOGLE_REPOSITORY_ID, &repositories.google_maven),
+            (CENTRAL_REPOSITORY_ID, &repositories.maven_central),
+            (
+                GRADLE_PLUGIN_PORTAL_REPOSITORY_ID,
+                &repositories.gradle_plugin_portal,
+            ),
+        ] {
+            let (server_id, url) = match self.find_mirror(id, url) {
+                Some(mirror) => (mirror.id.as_str(), &mirror.url),
+                None => (id, url),
+            };
+            let server = match self.servers.iter().find(|data| data.id == server_id) {
+                Some(data) => data,
+                None => continue,
+            };
+            let Some(username) = server.username.clone() else {
+                warn!(server = %server.id, "skip the server that has no username");
+                continue;
+            };
+            let url_prefix = url.trim_end_matches('/');
+            if credentials.iter().any(|data| data.url_prefix == url_prefix) {
+                continue;
+            }
+            credentials.push(Credential {
+                url_prefix: url_prefix.into(),
+                username,
+                password: server.password.clone(),
+            });
+        }
+        credentials
+    }
+
+    /// Returns the first active `<proxy>`.
+    pub fn active_proxy(&self) -> Option<&Proxy> {
+        self.proxies.iter().find(|data| data.active)
+    }
+}
+
+impl Proxy {
+    /// Proxy of all requests except the hosts of the `<nonProxyHosts>`.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn to_reqwest_proxy(&self) -> Fallible<reqwest::Proxy> {
+        let proxy_url = Url::parse(&format!("{}://{}:{}", self.protocol, self.host, self.port))
+            .with_context(|| format!("invalid proxy: {}", self.host))?;
+        let non_proxy_hosts = self
+            .non_proxy_hosts
+            .as_deref()
+            .unwrap_or_default()
+            .split(['|', ','])
+            .map(|data| data.trim().to_ascii_lowercase())
+            .filter(|data| !data.is_empty())
+            .collect::<Vec<_>>();
+        let proxy = reqwest::Proxy::custom(move |url| {
+            let host = url.host_str().unwrap_or_default().to_ascii_lowercase();
+            if non_proxy_hosts
+                .iter()
+                .any(|pattern| matches_host(pattern, &host))
+            {
+                None
+            } else {
+                Some(proxy_url.clone())
+            }
+        });
+        Ok(match &self.username {
+            Some(username) => {
+                proxy.basic_auth(username, self.password.as_deref().unwrap_or_default())
+            }
+            None => proxy,
+        })
+    }
+}
+
+/// https://maven.apache.org/guides/mini/guide-mirror-settings.html#advanced-mirror-specification
+fn matches_mirror_of(mirror_of: &str, repository_id: &str, repository_url: &str) -> bool {
+    let mut matched = false;
+    for pattern in mirror_of.split(',').map(str::trim) {
+        match pattern {
+            _ if pattern.strip_prefix('!') == Some(repository_id) => return false,
+            "*" => matched = true,
+            "external:*" => matched |= !is_local(repository_url),
+            "external:http:*" => {
+                matched |= repository_url.starts_with("http://") && !is_local(repository_url)
+            }
+            _ if pattern == repository_id => matched = true,
+            _ => {}
+        }
+    }
+    matched
+}
+
+fn is_local(url: &str) -> bool {
+    match Url::parse(url) {
+        Ok(url) if url.scheme() == "file" => true,
+        Ok(url) => matches!(url.host_str(), Some("localhost" | "127.0.0.1" | "[::1]")),
+        Err(_) => false,
+    }
+}
+
+/// `*.example.com`, `example.*` or the exact host.
+#[cfg(not(target_arch = "wasm32"))]
+fn matches_host(pattern: &str, host: &str) -> bool {
+    if let Some(suffix) = pattern.strip_prefix('*') {
+        host.ends_with(suffix)
+    } else if let Some(prefix) = pattern.strip_suffix('*') {
+        host.starts_with(prefix)
+    } else {
+        pattern == host
+    }
+}
+
+/// Substitute `${env.NAME}` with the environment variable. The unknown variable is kept as is.
+fn expand_env(text: &str) -> String {
+    let mut expanded = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find("${env.") {
+        let end = match rest[start..].find('}') {
+            Some(data) => start + data,
+            None => break,
+        };
+        expanded.push_str(&rest[..start]);
+        match std::env::var(&rest[start + 6..end]) {
+            Ok(value) => expanded.push_str(&value),
+            Err(_) => expanded.push_str(&rest[start..=end]),
+        }
+        rest = &rest[end + 1..];
+    }
+    expanded.push_str(rest);
+    expanded
+}
+
+#[derive(Deserialize)]
+struct Settings {
+    mirrors: Option<Mirrors>,
+    servers: Option<Servers>,
+    proxies: Option<Proxies>,
+}
+
+#[derive(Deserialize)]
+struct Mirrors {
+    #[serde(rename = "$value", default)]
+    field: Vec<SettingsMirror>,
+}
+
+#[derive(Deserialize)]
+struct SettingsMirror {
+    id: String,
+
+    #[serde(rename = "mirrorOf")]
+    mirror_of: String,
+
+    url: String,
+}
+
+#[derive(Deserialize)]
+struct Servers {
+    #[serde(rename = "$value", default)]
+    field: Vec<SettingsServer>,
+}
+
+#[derive(Deserialize)]
+struct SettingsServer {
+    id: String,
+    username: Option<String>,
+    password: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct Proxies {
+    #[serde(rename = "$value", default)]
+    field: Vec<SettingsProxy>,
+}
+
+#[derive(Deserialize)]
+struct SettingsProxy {
+    id: Option<String>,
+    active: Option<String>,
+    protocol: Option<String>,
+    host: String,
+    port: Option<String>,
+    username: Option<String>,
+    password: Option<String>,
+
+    #[serde(rename = "nonProxyHosts")]
+    non_proxy_hosts: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SETTINGS: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<settings xmlns="http://maven.apache.org/SETTINGS/1.0.0">
+  <localRepository>/path/to/local/repo</localRepository>
+  <mirrors>
+    <mirror>
+      <id>internal</id>
+      <name>Internal Repository</name>
+      <url>https://nexus.example.com/repository/maven-public/</url>
+      <mirrorOf>external:*,!google</mirrorOf>
+    </mirror>
+  </mirrors>
+  <servers>
+    <server>
+      <id>internal</id>
+      <username>deployer</username>
+      <password>${env.OSS_INFO_MAVEN_TEST_SETTINGS_PASSWORD}</password>
+    </server>
+  </servers>
+  <proxies>
+    <proxy>
+      <id>inactive</id>
+      <active>false</active>
+      <host>old-proxy.example.com</host>
+    </proxy>
+    <proxy>
+      <id>corporate</id>
+      <active>true</active>
+      <protocol>http</protocol>
+      <host>proxy.example.com</host>
+      <port>3128</port>
+      <nonProxyHosts>localhost|*.example.com</nonProxyHosts>
+    </proxy>
+  </proxies>
+</settings>
+"#;
+
+    #[test]
+    fn parse_maven_settings_nexus() {
+        let actual = parse_maven_settings(SETTINGS).unwrap();
+
+        assert_eq!(
+            vec![Mirror {
+                id: "internal".into(),
+                mirror_of: "external:*,!google".into(),
+                url: "https://nexus.example.com/repository/maven-public/".into(),
+            }],
+            actual.mirrors
+        );
+        assert_eq!("deployer", actual.servers[0].username.as_deref().unwrap());
+        assert_eq!(2, actual.proxies.len());
+
+        let proxy = actual.active_proxy().unwrap();
+        assert_eq!(Some("corporate".into()), proxy.id);
+        assert_eq!(3128, proxy.port);
+        assert!(proxy.to_reqwest_proxy().is_ok());
+    }
+
+    #[test]
+    fn apply_mirrors_nexus() {
+        let settings = parse_maven_settings(SETTINGS).unwrap();
+
+        let actual = settings.apply_mirrors(&RepositoryConfig::default());
+        // `external:*` mirrors Gradle Plugin Portal too.
+        let expected = RepositoryConfig {
+            maven_central: "https://nexus.example.com/repository/maven-public/".into(),
+            gradle_plugin_portal: "https://nexus.example.com/repository/maven-public/".into(),
+            ..Default::default()
+        };
+        assert_eq!(expected, actual);
+
+        let credentials = settings.credentials(&RepositoryConfig::default());
+        assert_eq!(1, credentials.len());
+        assert_eq!(
+            "https://nexus.example.com/repository/maven-public",
+            credentials[0].url_prefix
+        );
+        assert_eq!("deployer", credentials[0].username);
+    }
+
+    #[test]
+    fn matches_mirror_of_patterns() {
+        let url = "https://repo1.maven.org/maven2";
+        assert!(matches_mirror_of("*", "central", url));
+        assert!(matches_mirror_of("google,central", "central", url));
+        assert!(!matches_mirror_of("*,!central", "central", url));
+        assert!(matches_mirror_of("external:*", "central", url));
+        assert!(!matches_mirror_of(
+            "external:*",
+            "local",
+            "http://localhost:8081"
+        ));
+        assert!(!matches_mirror_of("external:http:*", "central", url));
+        assert!(!matches_mirror_of("google", "central", url));
+    }
+
+    #[test]
+    fn matches_host_wildcard() {
+        assert!(matches_host("*.example.com", "nexus.example.com"));
+        assert!(matches_host("192.168.*", "192.168.0.1"));
+        assert!(matches_host("localhost", "localhost"));
+        assert!(!matches_host("*.example.com", "example.org"));
+    }
+
+    #[test]
+    fn expand_env_unknown() {
+        assert_eq!(
+            "${env.OSS_INFO_MAVEN_TEST_UNKNOWN}",
+            expand_env("${env.OSS_INFO_MAVEN_TEST_UNKNOWN}")
+        );
+        assert_eq!("plain", expand_env("plain"));
+    }
+}
@@ -0,0 +1,185 @@
+/*
+ * Copyright 2023 sukawasatoru
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::function::maven::Dependency;
+use crate::model::{RichVersion, Version};
+use crate::prelude::*;
+
+/// Choose the version of the POM to retrieve from the maven-metadata.xml.
+///
+/// Closures like `|metadata: &Dependency| metadata.latest_version.clone()` implement this trait.
+pub trait VersionSelector: Send + Sync {
+    fn select(&self, metadata: &Dependency) -> Option<String>;
+
+    /// Version that is selected regardless of the maven-metadata.xml. The POM of it is requested
+    /// concurrently with the maven-metadata.xml to save the round trip.
+    fn fixed_version(&self) -> Option<String> {
+        None
+    }
+}
+
+impl<F> VersionSelector for F
+where
+    F: Fn(&Dependency) -> Option<String> + Send + Sync,
+{
+    fn select(&self, metadata: &Dependency) -> Option<String> {
+        self(metadata)
+    }
+}
+
+/// Prefer `<release>`, then `<latest>` and then `<version>`.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct DefaultVersionSelector;
+
+impl VersionSelector for DefaultVersionSelector {
+    fn select(&self, metadata: &Dependency) -> Option<String> {
+        metadata
+            .release_version
+            .clone()
+            .or_else(|| metadata.latest_version.clone())
+            .or_else(|| {
+                info!("use version tag");
+                metadata.version.clone()
+            })
+    }
+}
+
+/// Prefer `<latest>` that may be a pre-release, then `<release>` and then `<version>`.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct LatestVersionSelector;
+
+impl VersionSelector for LatestVersionSelector {
+    fn select(&self, metadata: &Dependency) -> Option<String> {
+        metadata
+            .latest_version
+            .clone()
+            .or_else(|| DefaultVersionSelector.select(metadata))
+    }
+}
+
+/// Prefer the highest version of the `<versions>` that is not a pre-release because `<release>`
+/// of some repositories such as Google Maven points at the pre-release. Fall back to `<release>`
+/// and then `<version>` if the `<versions>` has no such version.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct ReleaseVersionSelector;
+
+impl VersionSelector for ReleaseVersionSelector {
+    fn select(&self, metadata: &Dependency) -> Option<String> {
+        metadata
+            .versions
+            .iter()
+            .map(|data| Version::new(data.as_str()))
+            .filter(|data| !data.is_pre_release())
+            .max()
+            .map(|data| data.to_string())
+            .or_else(|| metadata.release_version.clone())
+            .or_else(|| metadata.version.clone())
+    }
+}
+
+/// Select the version such as the version of the input regardless of the maven-metadata.xml.
+#[derive(Clone, Debug)]
+pub struct FixedVersionSelector(pub String);
+
+impl VersionSelector for FixedVersionSelector {
+    fn select(&self, _: &Dependency) -> Option<String> {
+        Some(self.0.clone())
+    }
+
+    fn fixed_version(&self) -> Option<String> {
+        Some(self.0.clone())
+    }
+}
+
+/// Resolve the effective version of the Gradle's rich version against the `<versions>`.
+#[derive(Clone, Debug)]
+pub struct RichVersionSelector(pub RichVersion);
+
+impl VersionSelector for RichVersionSelector {
+    fn select(&self, metadata: &Dependency) -> Option<String> {
+        self.0.resolve(&metadata.versions)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_metadata() -> Dependency {
+        Dependency {
+            group_id: "androidx.core".into(),
+            artifact_id: "core-ktx".into(),
+            version: None,
+            latest_version: Some("1.13.0-alpha01".into()),
+            release_version: Some("1.12.0".into()),
+            versions: vec!["1.12.0".into(), "1.13.0-alpha01".into()],
+            last_updated: None,
+        }
+    }
+
+    #[test]
+    fn default_version_selector_release() {
+        let actual = DefaultVersionSelector.select(&create_metadata());
+        assert_eq!(Some("1.12.0".into()), actual);
+    }
+
+    #[test]
+    fn latest_version_selector() {
+        let actual = LatestVersionSelector.select(&create_metadata());
+        assert_eq!(Some("1.13.0-alpha01".into()), actual);
+    }
+
+    #[test]
+    fn release_version_selector() {
+        let metadata = Dependency {
+            release_version: Some("1.13.0-alpha01".into()),
+            versions: vec![
+                "1.9.0".into(),
+                "1.12.0".into(),
+                "1.10.1".into(),
+                "1.13.0-alpha01".into(),
+            ],
+            ..create_metadata()
+        };
+        let actual = ReleaseVersionSelector.select(&metadata);
+        assert_eq!(Some("1.12.0".into()), actual);
+
+        let metadata = Dependency {
+            versions: vec!["1.13.0-alpha01".into()],
+            ..metadata
+        };
+        let actual = ReleaseVersionSelector.select(&metadata);
+        assert_eq!(Some("1.13.0-alpha01".into()), actual);
+    }
+
+    #[test]
+    fn rich_version_selector_strictly() {
+        let selector = RichVersionSelector("{strictly [1.0, 2.0)}".parse().unwrap());
+        let actual = selector.select(&create_metadata());
+        assert_eq!(Some("1.13.0-alpha01".into()), actual);
+
+        let selector = RichVersionSelector("{strictly 1.12.0}".parse().unwrap());
+        let actual = selector.select(&create_metadata());
+        assert_eq!(Some("1.12.0".into()), actual);
+    }
+
+    #[test]
+    fn closure_version_selector() {
+        let selector = |metadata: &Dependency| metadata.latest_version.clone();
+        let actual = selector.select(&create_metadata());
+        assert_eq!(Some("1.13.0-alpha01".into()), actual);
+    }
+}
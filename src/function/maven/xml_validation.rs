@@ -0,0 +1,394 @@
+/*
+ * Copyright 2023 sukawasatoru
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Validation of the rules from the official XSDs that catch the common publishing mistakes.
+//!
+//! This is not a full XSD validator. The element names, the cardinality and the required
+//! elements of the top level are checked.
+//!
+//! - https://maven.apache.org/xsd/maven-4.0.0.xsd
+//! - https://maven.apache.org/xsd/repository-metadata-1.1.0.xsd
+
+use crate::prelude::*;
+use quick_xml::events::Event;
+use quick_xml::Reader;
+
+const POM_NAMESPACE: &str = "http://maven.apache.org/POM/4.0.0";
+
+/// `Model` of the maven-4.0.0.xsd.
+const POM_ELEMENTS: &[&str] = &[
+    "modelVersion",
+    "parent",
+    "groupId",
+    "artifactId",
+    "version",
+    "packaging",
+    "name",
+    "description",
+    "url",
+    "inceptionYear",
+    "organization",
+    "licenses",
+    "developers",
+    "contributors",
+    "mailingLists",
+    "prerequisites",
+    "modules",
+    "scm",
+    "issueManagement",
+    "ciManagement",
+    "distributionManagement",
+    "properties",
+    "dependencyManagement",
+    "dependencies",
+    "repositories",
+    "pluginRepositories",
+    "build",
+    "reports",
+    "reporting",
+    "profiles",
+];
+
+/// `Metadata` of the repository-metadata-1.1.0.xsd.
+const METADATA_ELEMENTS: &[&str] = &["groupId", "artifactId", "versioning", "version", "plugins"];
+
+/// `Versioning` of the repository-metadata-1.1.0.xsd.
+const VERSIONING_ELEMENTS: &[&str] = &[
+    "latest",
+    "release",
+    "snapshot",
+    "versions",
+    "lastUpdated",
+    "snapshotVersions",
+];
+
+/// Returns the violations of the POM. Empty if valid.
+pub fn validate_pom(xml: &str) -> Vec<String> {
+    let root = match parse_element_tree(xml) {
+        Ok(data) => data,
+        Err(e) => return vec![format!("malformed XML: {}", e)],
+    };
+
+    let mut violations = vec![];
+    if root.name != "project" {
+        violations.push(format!("unexpected root element <{}>", root.name));
+        return violations;
+    }
+
+    if let Some(namespace) = root.attribute("xmlns") {
+        if namespace != POM_NAMESPACE {
+            violations.push(format!("unexpected namespace: {}", namespace));
+        }
+    }
+
+    validate_children(&root, POM_ELEMENTS, &mut violations);
+
+    match root.child("modelVersion") {
+        Some(data) if data.text == "4.0.0" => {}
+        Some(data) => violations.push(format!("unexpected modelVersion: {}", data.text)),
+        None => violations.push("missing <modelVersion>".into()),
+    }
+
+    let parent = root.child("parent");
+    for name in ["groupId", "artifactId", "version"] {
+        let inherited = name != "artifactId" && parent.and_then(|data| data.child(name)).is_some();
+        if root.child(name).map_or(true, |data| data.text.is_empty()) && !inherited {
+            violations.push(format!("missing <{}>", name));
+        }
+    }
+
+    if let Some(licenses) = root.child("licenses") {
+        validate_children(licenses, &["license"], &mut violations);
+    }
+
+    violations
+}
+
+/// Returns the violations of the maven-metadata.xml. Empty if valid.
+pub fn validate_maven_metadata(xml: &str) -> Vec<String> {
+    let root = match parse_element_tree(xml) {
+        Ok(data) => data,
+        Err(e) => return vec![format!("malformed XML: {}", e)],
+    };
+
+    let mut violations = vec![];
+    if root.name != "metadata" {
+        violations.push(format!("unexpected root element <{}>", root.name));
+        return violations;
+    }
+
+    validate_children(&root, METADATA_ELEMENTS, &mut violations);
+
+    if root
+        .child("artifactId")
+        .map_or(false, |data| data.text.is_empty())
+    {
+        violations.push("empty <artifactId>".into());
+    }
+
+    if let Some(versioning) = root.child("versioning") {
+        validate_children(versioning, VERSIONING_ELEMENTS, &mut violations);
+
+        if let Some(versions) = versioning.child("versions") {
+            validate_children(versions, &["version"], &mut violations);
+
+            if versions.children.is_empty() {
+                violations.push("empty <versions>".into());
+            }
+
+            // `<latest>` and `<release>` are the one of the `<versions>`.
+            for name in ["latest", "release"] {
+                if let Some(data) = versioning.child(name) {
+                    if !versions
+                        .children
+                        .iter()
+                        .any(|version| version.name == "version" && version.text == data.text)
+                    {
+                        violations.push(format!(
+                            "<{}> {} is not listed in <versions>",
+                            name, data.text
+                        ));
+                    }
+                }
+            }
+        }
+
+        // `yyyyMMddHHmmss`
+        if let Some(last_updated) = versioning.child("lastUpdated") {
+            if last_updated.text.len() != 14
+                || !last_updated.text.chars().all(|data| data.is_ascii_digit())
+            {
+                violations.push(format!("unexpected lastUpdated: {}", last_updated.text));
+            }
+        }
+    }
+
+    violations
+}
+
+/// Check the unknown elements and the duplicated elements except the list items.
+fn validate_children(element: &Element, names: &[&str], violations: &mut Vec<String>) {
+    let is_list = names.len() == 1;
+    for (index, child) in element.children.iter().enumerate() {
+        if !names.contains(&child.name.as_str()) {
+            violations.push(format!(
+                "unexpected element <{}> in <{}>",
+                child.name, element.name
+            ));
+        } else if !is_list
+            && element.children[..index]
+                .iter()
+                .any(|data| data.name == child.name)
+        {
+            violations.push(format!(
+                "duplicated element <{}> in <{}>",
+                child.name, element.name
+            ));
+        }
+    }
+}
+
+struct Element {
+    name: String,
+    attributes: Vec<(String, String)>,
+    children: Vec<Element>,
+    text: String,
+}
+
+impl Element {
+    fn new(name: String, attributes: Vec<(String, String)>) -> Self {
+        Self {
+            name,
+            attributes,
+            children: vec![],
+            text: String::new(),
+        }
+    }
+
+    fn attribute(&self, name: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    fn child(&self, name: &str) -> Option<&Element> {
+        self.children.iter().find(|data| data.name == name)
+    }
+}
+
+fn parse_element_tree(xml: &str) -> Fallible<Element> {
+    let mut reader = Reader::from_str(xml);
+    reader.trim_text(true);
+
+    let mut stack = Vec::<Element>::new();
+    loop {
+        match reader.read_event()? {
+            Event::Start(data) => {
+                let attributes = data
+                    .attributes()
+                    .map(|attribute| {
+                        let attribute = attribute?;
+                        Ok((
+                            String::from_utf8_lossy(attribute.key.as_ref()).into_owned(),
+                            attribute.unescape_value()?.into_owned(),
+                        ))
+                    })
+                    .collect::<Fallible<Vec<_>>>()?;
+                let name = String::from_utf8_lossy(data.local_name().as_ref()).into_owned();
+                stack.push(Element::new(name, attributes));
+            }
+            Event::Empty(data) => {
+                let name = String::from_utf8_lossy(data.local_name().as_ref()).into_owned();
+                match stack.last_mut() {
+                    Some(parent) => parent.children.push(Element::new(name, vec![])),
+                    None => return Ok(Element::new(name, vec![])),
+                }
+            }
+            Event::Text(data) => {
+                if let Some(current) = stack.last_mut() {
+                    current.text.push_str(&data.unescape()?);
+                }
+            }
+            Event::End(_) => {
+                let element = stack.pop().context("unexpected end tag")?;
+                match stack.last_mut() {
+                    Some(parent) => parent.children.push(element),
+                    None => return Ok(element),
+                }
+            }
+            Event::Eof => bail!("missing root element"),
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn validate_pom_valid() {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<project xmlns="http://maven.apache.org/POM/4.0.0" xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance">
+  <modelVersion>4.0.0</modelVersion>
+  <parent>
+    <groupId>org.example</groupId>
+    <artifactId>parent</artifactId>
+    <version>1.0.0</version>
+  </parent>
+  <artifactId>example</artifactId>
+  <licenses>
+    <license>
+      <name>Apache-2.0</name>
+    </license>
+  </licenses>
+</project>
+"#;
+        assert_eq!(Vec::<String>::new(), validate_pom(xml));
+    }
+
+    #[test]
+    fn validate_pom_violations() {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<project>
+  <groupId>org.example</groupId>
+  <artifactId>example</artifactId>
+  <name>first</name>
+  <name>second</name>
+  <licence>MIT</licence>
+</project>
+"#;
+        let expected = vec![
+            "duplicated element <name> in <project>".to_string(),
+            "unexpected element <licence> in <project>".into(),
+            "missing <modelVersion>".into(),
+            "missing <version>".into(),
+        ];
+        assert_eq!(expected, validate_pom(xml));
+    }
+
+    #[test]
+    fn validate_pom_malformed() {
+        let actual = validate_pom("<project><name></project>");
+        assert_eq!(1, actual.len());
+        assert!(actual[0].starts_with("malformed XML"));
+    }
+
+    #[test]
+    fn validate_maven_metadata_valid() {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<metadata>
+  <groupId>androidx.core</groupId>
+  <artifactId>core-ktx</artifactId>
+  <versioning>
+    <latest>1.13.0-alpha01</latest>
+    <release>1.12.0</release>
+    <versions>
+      <version>1.12.0</version>
+      <version>1.13.0-alpha01</version>
+    </versions>
+    <lastUpdated>20231004184540</lastUpdated>
+  </versioning>
+</metadata>
+"#;
+        assert_eq!(Vec::<String>::new(), validate_maven_metadata(xml));
+    }
+
+    #[test]
+    fn validate_maven_metadata_violations() {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<metadata>
+  <groupId>androidx.core</groupId>
+  <versioning>
+    <release>1.12.0</release>
+    <release>1.12.1</release>
+    <versions>
+      <v>1.12.0</v>
+    </versions>
+    <lastUpdated>2023-10-04</lastUpdated>
+  </versioning>
+</metadata>
+"#;
+        let expected = vec![
+            "duplicated element <release> in <versioning>".to_string(),
+            "unexpected element <v> in <versions>".into(),
+            "<release> 1.12.0 is not listed in <versions>".into(),
+            "unexpected lastUpdated: 2023-10-04".into(),
+        ];
+        assert_eq!(expected, validate_maven_metadata(xml));
+    }
+
+    #[test]
+    fn validate_maven_metadata_malformed_versioning() {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<metadata>
+  <groupId>androidx.core</groupId>
+  <artifactId></artifactId>
+  <versioning>
+    <latest>1.13.0</latest>
+    <versions/>
+  </versioning>
+</metadata>
+"#;
+        let expected = vec![
+            "empty <artifactId>".to_string(),
+            "empty <versions>".into(),
+            "<latest> 1.13.0 is not listed in <versions>".into(),
+        ];
+        assert_eq!(expected, validate_maven_metadata(xml));
+    }
+}
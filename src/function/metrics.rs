@@ -0,0 +1,212 @@
+/*
+ * Copyright 2023 sukawasatoru
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::prelude::*;
+use serde::Serialize;
+use std::io::Write;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+
+/// Number of the slowest artifacts of [RunMetrics].
+pub const SLOWEST_ARTIFACTS: usize = 5;
+
+/// Counters of the requests that the clones of the [crate::function::http_client::HttpClient]
+/// share.
+#[derive(Debug, Default)]
+pub struct RequestMetrics {
+    requests: AtomicUsize,
+    cache_hits: AtomicUsize,
+    retries: AtomicUsize,
+}
+
+impl RequestMetrics {
+    pub fn add_request(&self) {
+        self.requests.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Count the maven-metadata.xml that is revalidated with the cache.
+    pub fn add_cache_hit(&self) {
+        self.cache_hits.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Count the request that is re-issued after the failed one such as the shared
+    /// maven-metadata.xml.
+    pub fn add_retry(&self) {
+        self.retries.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn requests(&self) -> usize {
+        self.requests.load(Ordering::Relaxed)
+    }
+
+    pub fn cache_hits(&self) -> usize {
+        self.cache_hits.load(Ordering::Relaxed)
+    }
+
+    pub fn retries(&self) -> usize {
+        self.retries.load(Ordering::Relaxed)
+    }
+}
+
+/// Metrics of a run to track the resolution performance.
+#[derive(Debug, PartialEq, Serialize)]
+pub struct RunMetrics {
+    /// Number of the coordinates of the inputs.
+    pub coordinates: usize,
+
+    /// Number of the coordinates that are restored from the state file.
+    pub restored: usize,
+
+    /// Number of the maven-metadata.xml that are not modified since the cache.
+    pub cache_hits: usize,
+
+    /// Number of the HTTP requests including the cache revalidations.
+    pub requests: usize,
+
+    /// Number of the HTTP requests that are re-issued after the failed ones.
+    pub retries: usize,
+
+    /// Number of the coordinates that failed to resolve.
+    pub failures: usize,
+
+    pub wall_time_secs: f64,
+
+    /// Artifacts that took the longest time to resolve in descending order.
+    pub slowest: Vec<ArtifactTime>,
+}
+
+#[derive(Debug, PartialEq, Serialize)]
+pub struct ArtifactTime {
+    pub coordinate: String,
+    pub secs: f64,
+}
+
+/// Returns the `limit` artifacts of the longest time in descending order.
+pub fn slowest_artifacts<I>(durations: I, limit: usize) -> Vec<ArtifactTime>
+where
+    I: IntoIterator<Item = (String, Duration)>,
+{
+    let mut durations = durations.into_iter().collect::<Vec<_>>();
+    durations.sort_by(|(a_name, a_duration), (b_name, b_duration)| {
+        b_duration.cmp(a_duration).then_with(|| a_name.cmp(b_name))
+    });
+    durations
+        .into_iter()
+        .take(limit)
+        .map(|(coordinate, duration)| ArtifactTime {
+            coordinate,
+            secs: duration.as_secs_f64(),
+        })
+        .collect()
+}
+
+/// Write the metrics as the aligned text.
+pub fn write_metrics<W: Write>(mut writer: W, metrics: &RunMetrics) -> Fallible<()> {
+    writeln!(writer, "Metrics:")?;
+    for (name, value) in [
+        ("Coordinates", metrics.coordinates.to_string()),
+        ("Restored", metrics.restored.to_string()),
+        ("Cache hits", metrics.cache_hits.to_string()),
+        ("HTTP requests", metrics.requests.to_string()),
+        ("Retries", metrics.retries.to_string()),
+        ("Failures", metrics.failures.to_string()),
+        ("Wall time", format!("{:.3}s", metrics.wall_time_secs)),
+    ] {
+        writeln!(writer, "  {:<16}{}", format!("{}:", name), value)?;
+    }
+    if !metrics.slowest.is_empty() {
+        writeln!(writer, "Slowest artifacts:")?;
+        for data in &metrics.slowest {
+            writeln!(writer, "  {:>8.3}s  {}", data.secs, data.coordinate)?;
+        }
+    }
+    Ok(())
+}
+
+/// Write the metrics as a JSON object.
+pub fn write_metrics_json<W: Write>(writer: W, metrics: &RunMetrics) -> Fallible<()> {
+    serde_json::to_writer_pretty(writer, metrics)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn slowest_artifacts_limit() {
+        let actual = slowest_artifacts(
+            [
+                (
+                    "androidx.core:core-ktx:1.9.0".into(),
+                    Duration::from_millis(500),
+                ),
+                (
+                    "com.squareup.okhttp3:okhttp:4.11.0".into(),
+                    Duration::from_millis(1500),
+                ),
+                (
+                    "com.github.bumptech.glide:glide:4.16.0".into(),
+                    Duration::from_millis(250),
+                ),
+            ],
+            2,
+        );
+        let expected = vec![
+            ArtifactTime {
+                coordinate: "com.squareup.okhttp3:okhttp:4.11.0".into(),
+                secs: 1.5,
+            },
+            ArtifactTime {
+                coordinate: "androidx.core:core-ktx:1.9.0".into(),
+                secs: 0.5,
+            },
+        ];
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn write_metrics_text() {
+        let metrics = RunMetrics {
+            coordinates: 3,
+            restored: 1,
+            cache_hits: 1,
+            requests: 4,
+            retries: 1,
+            failures: 0,
+            wall_time_secs: 1.25,
+            slowest: vec![ArtifactTime {
+                coordinate: "com.squareup.okhttp3:okhttp:4.11.0".into(),
+                secs: 1.5,
+            }],
+        };
+        let mut actual = vec![];
+        write_metrics(&mut actual, &metrics).unwrap();
+        let expected = "\
+Metrics:
+  Coordinates:    3
+  Restored:       1
+  Cache hits:     1
+  HTTP requests:  4
+  Retries:        1
+  Failures:       0
+  Wall time:      1.250s
+Slowest artifacts:
+     1.500s  com.squareup.okhttp3:okhttp:4.11.0
+";
+        assert_eq!(expected, String::from_utf8(actual).unwrap());
+    }
+}
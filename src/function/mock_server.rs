@@ -14,10 +14,21 @@
  * limitations under the License.
  */
 
+//! Mock HTTP server of the tests.
+//!
+//! The other crates can use this with the `test-support` feature to test the integration with
+//! this crate without the network.
+
+use axum::http::{StatusCode, Uri};
+use axum::response::{IntoResponse, Response};
+use axum::routing::{IntoMakeService, Router};
+use std::net::SocketAddr;
 use std::ops::Deref;
-use std::sync::Mutex;
+use std::path::{Component, Path, PathBuf};
+use std::sync::{Arc, Mutex};
 use tokio::sync::Semaphore;
 use tokio::sync::SemaphorePermit;
+use tokio::task::JoinHandle;
 
 const DEFAULT_PORTS_LEN: usize = 9;
 const DEFAULT_PORTS: [u16; DEFAULT_PORTS_LEN] = [
@@ -30,6 +41,66 @@ pub async fn acquire_port() -> PortGuard {
     POOL.acquire().await
 }
 
+/// Launch the server on the port of the pool. Send to the sender and await the handle to stop
+/// the server.
+pub async fn launch_web_server(
+    make_service: IntoMakeService<Router>,
+) -> (JoinHandle<()>, tokio::sync::oneshot::Sender<()>, PortGuard) {
+    let (tx, rx) = tokio::sync::oneshot::channel::<()>();
+
+    let port_guard = acquire_port().await;
+    let port = *port_guard;
+    let handler = tokio::task::spawn(async move {
+        axum::Server::bind(&SocketAddr::from(([127, 0, 0, 1], port)))
+            .serve(make_service)
+            .with_graceful_shutdown(async {
+                rx.await.ok();
+            })
+            .await
+            .unwrap();
+    });
+
+    // yield for launching server.
+    tokio::task::yield_now().await;
+
+    (handler, tx, port_guard)
+}
+
+/// Launch the Maven repository that serves the fixture files of the `root` directory in the
+/// repository layout such as `com/example/lib/maven-metadata.xml`.
+///
+/// The repository root URL is `http://127.0.0.1:{port}`.
+pub async fn launch_maven_repository(
+    root: impl Into<PathBuf>,
+) -> (JoinHandle<()>, tokio::sync::oneshot::Sender<()>, PortGuard) {
+    let root = Arc::new(root.into());
+    launch_web_server(
+        Router::new()
+            .fallback(move |uri: Uri| {
+                let root = root.clone();
+                async move { serve_file(&root, uri.path()) }
+            })
+            .into_make_service(),
+    )
+    .await
+}
+
+fn serve_file(root: &Path, path: &str) -> Response {
+    let relative = Path::new(path.trim_start_matches('/'));
+    // never serve the files outside of the root.
+    if !relative
+        .components()
+        .all(|data| matches!(data, Component::Normal(_)))
+    {
+        return StatusCode::NOT_FOUND.into_response();
+    }
+
+    match std::fs::read(root.join(relative)) {
+        Ok(data) => data.into_response(),
+        Err(_) => StatusCode::NOT_FOUND.into_response(),
+    }
+}
+
 pub struct PortGuard {
     _permit: SemaphorePermit<'static>,
     pub port: u16,
@@ -96,6 +167,40 @@ impl PoolInternal {
     }
 }
 
+#[tokio::test]
+async fn launch_maven_repository_fixture() {
+    let root = std::env::temp_dir().join(format!("oss-info-maven-mock-{}", std::process::id()));
+    std::fs::create_dir_all(root.join("com/example/lib")).unwrap();
+    std::fs::write(
+        root.join("com/example/lib/maven-metadata.xml"),
+        "<metadata/>",
+    )
+    .unwrap();
+
+    let (handler, tx, port) = launch_maven_repository(&root).await;
+    let repo_root = format!("http://127.0.0.1:{}", *port);
+    let client = reqwest::Client::new();
+    let found = client
+        .get(format!("{}/com/example/lib/maven-metadata.xml", repo_root))
+        .send()
+        .await
+        .unwrap();
+    let found = (found.status(), found.text().await.unwrap());
+    let not_found = client
+        .get(format!("{}/com/example/lib/1.0.0/lib-1.0.0.pom", repo_root))
+        .send()
+        .await
+        .unwrap()
+        .status();
+
+    tx.send(()).unwrap();
+    handler.await.unwrap();
+    std::fs::remove_dir_all(&root).unwrap();
+
+    assert_eq!((reqwest::StatusCode::OK, "<metadata/>".into()), found);
+    assert_eq!(reqwest::StatusCode::NOT_FOUND, not_found);
+}
+
 #[tokio::test]
 #[ignore]
 async fn acquire_release() {
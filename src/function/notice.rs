@@ -0,0 +1,319 @@
+/*
+ * Copyright 2023 sukawasatoru
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::function::maven::POM;
+use crate::model::{Annotation, Coordinate, LicenseOrigin, SPDX};
+use crate::prelude::*;
+use std::collections::{BTreeMap, HashMap};
+use std::io::Write;
+
+pub struct NoticeEntry<'a> {
+    pub coordinate: &'a Coordinate,
+    pub pom: &'a POM,
+    pub annotation: Option<&'a Annotation>,
+    /// Origin of the `pom.licenses` that are not from the POM such as `--overrides`.
+    pub license_origin: Option<&'a LicenseOrigin>,
+}
+
+/// Write the plaintext NOTICE (THIRD-PARTY) file that has a section per license.
+pub fn write_notice<'a, W, I>(writer: W, entries: I) -> Fallible<()>
+where
+    W: Write,
+    I: IntoIterator<Item = NoticeEntry<'a>>,
+{
+    write_notice_with_template(writer, entries, None, None)
+}
+
+/// Same as [write_notice] but the `header` replaces the default title and the `footer` is
+/// written after the sections. Use [render_notice_template] to substitute the variables.
+pub fn write_notice_with_template<'a, W, I>(
+    mut writer: W,
+    entries: I,
+    header: Option<&str>,
+    footer: Option<&str>,
+) -> Fallible<()>
+where
+    W: Write,
+    I: IntoIterator<Item = NoticeEntry<'a>>,
+{
+    let mut sections = BTreeMap::<String, Vec<NoticeEntry>>::new();
+    for entry in entries {
+        let license = if entry.pom.licenses.is_empty() {
+            "Unknown".to_owned()
+        } else {
+            entry
+                .pom
+                .licenses
+                .iter()
+                .map(SPDX::to_string)
+                .collect::<Vec<_>>()
+                .join("/")
+        };
+        sections.entry(license).or_default().push(entry);
+    }
+
+    match header {
+        Some(header) => write!(writer, "{}", header.trim_end())?,
+        None => {
+            writeln!(writer, "THIRD-PARTY SOFTWARE NOTICES")?;
+            writeln!(writer, "============================")?;
+            writeln!(writer)?;
+            write!(
+                writer,
+                "This software includes the following third-party components."
+            )?;
+        }
+    }
+    writeln!(writer)?;
+
+    for (license, mut entries) in sections {
+        entries.sort_by(|a, b| a.coordinate.cmp(b.coordinate));
+
+        writeln!(writer)?;
+        writeln!(writer, "{}", license)?;
+        writeln!(writer, "{}", "-".repeat(license.chars().count()))?;
+        for entry in entries {
+            writeln!(writer)?;
+            match &entry.coordinate.version {
+                Some(version) => writeln!(writer, "{} {}", entry.coordinate.module_id(), version)?,
+                None => writeln!(writer, "{}", entry.coordinate.module_id())?,
+            }
+            if let Some(name) = &entry.pom.name {
+                writeln!(writer, "  {}", name)?;
+            }
+            if let Some(copyright) = entry.pom.copyright() {
+                writeln!(writer, "  {}", copyright)?;
+            }
+            if let Some(url) = &entry.pom.url {
+                writeln!(writer, "  {}", url)?;
+            }
+            if let Some(origin) = entry.license_origin {
+                writeln!(writer, "  License: {}", origin)?;
+            }
+            if let Some(annotation) = entry.annotation {
+                if !annotation.tags.is_empty() {
+                    writeln!(writer, "  Tags: {}", annotation.tags.join(", "))?;
+                }
+                if let Some(note) = &annotation.note {
+                    writeln!(writer, "  Note: {}", note)?;
+                }
+            }
+        }
+    }
+
+    if let Some(footer) = footer {
+        writeln!(writer)?;
+        writeln!(writer, "{}", footer.trim_end())?;
+    }
+
+    writer.flush()?;
+
+    Ok(())
+}
+
+/// Substitute `{{name}}` in the `template` with the value of the `vars`.
+///
+/// Returns an error if the `template` has the variable that is not in the `vars`.
+pub fn render_notice_template(template: &str, vars: &HashMap<String, String>) -> Fallible<String> {
+    let mut rendered = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find("{{") {
+        rendered.push_str(&rest[..start]);
+        let end = rest[start..]
+            .find("}}")
+            .with_context(|| format!("unclosed variable: {}", &rest[start..]))?;
+        let name = rest[start + 2..start + end].trim();
+        let value = vars
+            .get(name)
+            .with_context(|| format!("unknown variable: {}", name))?;
+        rendered.push_str(value);
+        rest = &rest[start + end + 2..];
+    }
+    rendered.push_str(rest);
+
+    Ok(rendered)
+}
+
+/// Returns the year in UTC of the `unix_time` (seconds) for the `{{year}}` of the template.
+pub fn year_of_unix_time(unix_time: u64) -> u64 {
+    // https://howardhinnant.github.io/date_algorithms.html#civil_from_days
+    let z = unix_time / 86400 + 719468;
+    let era = z / 146097;
+    let doe = z - era * 146097;
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let year = yoe + era * 400;
+    if 10 <= mp {
+        year + 1
+    } else {
+        year
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn write_notice_group_by_license() {
+        let core_ktx = POM {
+            group_id: Some("androidx.core".into()),
+            artifact_id: "core-ktx".into(),
+            version: Some("1.12.0".into()),
+            packaging: Some("aar".into()),
+            name: Some("Core Kotlin Extensions".into()),
+            url: Some("https://developer.android.com/jetpack/androidx/releases/core#1.12.0".into()),
+            inception_year: Some("2018".into()),
+            organization: Some("The Android Open Source Project".into()),
+            licenses: vec![SPDX::Apache20],
+            ..Default::default()
+        };
+        let okhttp = POM {
+            group_id: Some("com.squareup.okhttp3".into()),
+            artifact_id: "okhttp".into(),
+            version: Some("4.11.0".into()),
+            name: Some("okhttp".into()),
+            url: Some("https://square.github.io/okhttp/".into()),
+            licenses: vec![SPDX::Apache20],
+            ..Default::default()
+        };
+        let internal = POM {
+            group_id: Some("com.example".into()),
+            artifact_id: "internal".into(),
+            ..Default::default()
+        };
+
+        let mut actual = vec![];
+        write_notice(
+            &mut actual,
+            [
+                NoticeEntry {
+                    coordinate: &"com.squareup.okhttp3:okhttp:4.9.3".parse().unwrap(),
+                    pom: &okhttp,
+                    annotation: None,
+                    license_origin: Some(&LicenseOrigin::Overridden {
+                        note: Some("reviewed the LICENSE file".into()),
+                    }),
+                },
+                NoticeEntry {
+                    coordinate: &"com.example:internal".parse().unwrap(),
+                    pom: &internal,
+                    annotation: Some(&"[internal] bundled only in flavor X".parse().unwrap()),
+                    license_origin: None,
+                },
+                NoticeEntry {
+                    coordinate: &"androidx.core:core-ktx:1.9.0".parse().unwrap(),
+                    pom: &core_ktx,
+                    annotation: None,
+                    license_origin: None,
+                },
+            ],
+        )
+        .unwrap();
+
+        let expected = r#"THIRD-PARTY SOFTWARE NOTICES
+============================
+
+This software includes the following third-party components.
+
+Apache-2.0
+----------
+
+androidx.core:core-ktx 1.9.0
+  Core Kotlin Extensions
+  Copyright 2018 The Android Open Source Project
+  https://developer.android.com/jetpack/androidx/releases/core#1.12.0
+
+com.squareup.okhttp3:okhttp 4.9.3
+  okhttp
+  https://square.github.io/okhttp/
+  License: overridden (reviewed the LICENSE file)
+
+Unknown
+-------
+
+com.example:internal
+  Tags: internal
+  Note: bundled only in flavor X
+"#;
+
+        assert_eq!(expected, String::from_utf8(actual).unwrap());
+    }
+
+    #[test]
+    fn write_notice_with_template_header_footer() {
+        let okhttp = POM {
+            group_id: Some("com.squareup.okhttp3".into()),
+            artifact_id: "okhttp".into(),
+            version: Some("4.11.0".into()),
+            name: Some("okhttp".into()),
+            licenses: vec![SPDX::Apache20],
+            ..Default::default()
+        };
+
+        let mut actual = vec![];
+        write_notice_with_template(
+            &mut actual,
+            [NoticeEntry {
+                coordinate: &"com.squareup.okhttp3:okhttp:4.11.0".parse().unwrap(),
+                pom: &okhttp,
+                annotation: None,
+                license_origin: None,
+            }],
+            Some("Example App 1.0.0\nThird-party notices\n\n"),
+            Some("Copyright 2023 Example Inc.\n"),
+        )
+        .unwrap();
+
+        let expected = r#"Example App 1.0.0
+Third-party notices
+
+Apache-2.0
+----------
+
+com.squareup.okhttp3:okhttp 4.11.0
+  okhttp
+
+Copyright 2023 Example Inc.
+"#;
+
+        assert_eq!(expected, String::from_utf8(actual).unwrap());
+    }
+
+    #[test]
+    fn render_notice_template_vars() {
+        let vars = HashMap::from([
+            ("app_name".to_string(), "Example App".to_string()),
+            ("year".to_string(), "2023".to_string()),
+        ]);
+
+        assert_eq!(
+            "Example App (c) 2023",
+            render_notice_template("{{app_name}} (c) {{ year }}", &vars).unwrap(),
+        );
+        assert!(render_notice_template("{{version}}", &vars).is_err());
+        assert!(render_notice_template("{{app_name", &vars).is_err());
+    }
+
+    #[test]
+    fn year_of_unix_time_boundary() {
+        assert_eq!(1970, year_of_unix_time(0));
+        assert_eq!(2023, year_of_unix_time(1_704_067_199));
+        assert_eq!(2024, year_of_unix_time(1_704_067_200));
+        assert_eq!(2024, year_of_unix_time(1_709_164_800));
+    }
+}
@@ -0,0 +1,204 @@
+/*
+ * Copyright 2023 sukawasatoru
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::model::{Coordinate, LicenseOrigin, SPDX};
+use crate::prelude::*;
+use std::collections::BTreeMap;
+use std::io::Write;
+
+#[derive(Clone, Copy)]
+pub struct ObligationEntry<'a> {
+    pub coordinate: &'a Coordinate,
+    pub licenses: &'a [SPDX],
+    /// Origin of the `licenses` that are not from the POM such as `--overrides`.
+    pub license_origin: Option<&'a LicenseOrigin>,
+}
+
+/// Write the obligations checklist that has a section per distinct license.
+///
+/// The sections of the licenses that need the review list the artifacts, and the artifacts that
+/// have no license are listed in the last section. The other sections list only the artifacts
+/// whose licenses have the [LicenseOrigin].
+pub fn write_obligations<'a, W, I>(mut writer: W, entries: I) -> Fallible<()>
+where
+    W: Write,
+    I: IntoIterator<Item = ObligationEntry<'a>>,
+{
+    let mut sections = BTreeMap::<String, (&SPDX, Vec<ObligationEntry>)>::new();
+    let mut no_license = vec![];
+    for entry in entries {
+        if entry.licenses.is_empty() {
+            no_license.push(entry);
+        }
+        for license in entry.licenses {
+            sections
+                .entry(license.to_string())
+                .or_insert((license, vec![]))
+                .1
+                .push(entry);
+        }
+    }
+
+    writeln!(writer, "LICENSE OBLIGATIONS CHECKLIST")?;
+    writeln!(writer, "=============================")?;
+    writeln!(writer)?;
+    writeln!(
+        writer,
+        "Generated from a built-in table as a first pass. This is not legal advice."
+    )?;
+
+    let check = |value: bool| if value { "[x]" } else { "[ ]" };
+    for (name, (license, entries)) in sections {
+        write_title(&mut writer, &name, entries.len())?;
+        match license.obligations() {
+            Some(data) => {
+                writeln!(writer, "{} Attribution required", check(data.attribution))?;
+                writeln!(writer, "{} Source offer required", check(data.source_offer))?;
+                writeln!(
+                    writer,
+                    "{} Modification disclosure required",
+                    check(data.modification_disclosure),
+                )?;
+                writeln!(writer, "Patent: {}", data.patent)?;
+                let entries = entries
+                    .into_iter()
+                    .filter(|data| data.license_origin.is_some())
+                    .collect::<Vec<_>>();
+                write_artifacts(&mut writer, &entries)?;
+            }
+            None if license.is_proprietary() => {
+                writeln!(writer, "[!] Not open source, review the terms")?;
+                write_artifacts(&mut writer, &entries)?;
+            }
+            None => {
+                writeln!(writer, "[?] Unknown license, review required")?;
+                write_artifacts(&mut writer, &entries)?;
+            }
+        }
+    }
+
+    if !no_license.is_empty() {
+        write_title(&mut writer, "No license", no_license.len())?;
+        writeln!(writer, "[?] No license in the POM, review required")?;
+        write_artifacts(&mut writer, &no_license)?;
+    }
+
+    writer.flush()?;
+
+    Ok(())
+}
+
+fn write_title<W: Write>(mut writer: W, name: &str, count: usize) -> Fallible<()> {
+    let title = format!(
+        "{} ({} {})",
+        name,
+        count,
+        if count == 1 { "artifact" } else { "artifacts" }
+    );
+    writeln!(writer)?;
+    writeln!(writer, "{}", title)?;
+    writeln!(writer, "{}", "-".repeat(title.chars().count()))?;
+    Ok(())
+}
+
+fn write_artifacts<W: Write>(mut writer: W, entries: &[ObligationEntry]) -> Fallible<()> {
+    let mut entries = entries.to_vec();
+    entries.sort_by(|a, b| a.coordinate.cmp(b.coordinate));
+    for entry in entries {
+        match entry.license_origin {
+            Some(origin) => writeln!(writer, "- {} - {}", entry.coordinate, origin)?,
+            None => writeln!(writer, "- {}", entry.coordinate)?,
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn write_obligations_per_license() {
+        let coordinates = [
+            "com.example:a:1.0.0",
+            "com.example:b:1.0.0",
+            "com.example:c:1.0.0",
+            "com.example:d:1.0.0",
+            "com.example:e:1.0.0",
+            "com.example:f:1.0.0",
+        ]
+        .map(|data| data.parse::<Coordinate>().unwrap());
+        let licenses = [
+            vec![SPDX::Apache20],
+            vec![SPDX::MIT],
+            vec![SPDX::Apache20],
+            vec![SPDX::Other("Custom License".into())],
+            vec![],
+            vec![],
+        ];
+        let overridden = LicenseOrigin::Overridden {
+            note: Some("relicensed".into()),
+        };
+
+        let mut actual = vec![];
+        write_obligations(
+            &mut actual,
+            coordinates
+                .iter()
+                .zip(&licenses)
+                .map(|(coordinate, licenses)| ObligationEntry {
+                    coordinate,
+                    licenses,
+                    license_origin: Some(&overridden).filter(|_| coordinate.artifact_id == "b"),
+                }),
+        )
+        .unwrap();
+
+        let expected = r#"LICENSE OBLIGATIONS CHECKLIST
+=============================
+
+Generated from a built-in table as a first pass. This is not legal advice.
+
+Apache-2.0 (2 artifacts)
+------------------------
+[x] Attribution required
+[ ] Source offer required
+[x] Modification disclosure required
+Patent: Express patent grant, terminated if you initiate patent litigation.
+
+Custom License (1 artifact)
+---------------------------
+[?] Unknown license, review required
+- com.example:d:1.0.0
+
+MIT (1 artifact)
+----------------
+[x] Attribution required
+[ ] Source offer required
+[ ] Modification disclosure required
+Patent: No express patent grant.
+- com.example:b:1.0.0 - overridden (relicensed)
+
+No license (2 artifacts)
+------------------------
+[?] No license in the POM, review required
+- com.example:e:1.0.0
+- com.example:f:1.0.0
+"#;
+
+        assert_eq!(expected, String::from_utf8(actual).unwrap());
+    }
+}
@@ -0,0 +1,193 @@
+/*
+ * Copyright 2023 sukawasatoru
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! https://google.github.io/osv.dev/api/
+
+use crate::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter};
+
+/// Maximum number of the queries per request of the batch endpoint.
+pub const QUERY_BATCH_LIMIT: usize = 1000;
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Vulnerability {
+    /// e.g. `GHSA-xxxx-xxxx-xxxx`, `CVE-2023-12345`.
+    pub id: String,
+
+    /// `database_specific.severity` such as `HIGH` or the CVSS vector as fallback.
+    pub severity: Option<String>,
+}
+
+impl Display for Vulnerability {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match &self.severity {
+            Some(severity) => write!(f, "{} ({})", self.id, severity),
+            None => f.write_str(&self.id),
+        }
+    }
+}
+
+/// Returns the request body of the batch endpoint for the purls.
+pub fn query_batch_request(purls: &[String]) -> Fallible<String> {
+    let request = QueryBatchRequest {
+        queries: purls
+            .iter()
+            .map(|purl| Query {
+                package: QueryPackage { purl },
+            })
+            .collect(),
+    };
+    Ok(serde_json::to_string(&request)?)
+}
+
+/// Parse the response of the batch endpoint into the vulnerability IDs per query.
+pub fn parse_query_batch(json: &str) -> Fallible<Vec<Vec<String>>> {
+    let parsed = serde_json::from_str::<QueryBatchResponse>(json)?;
+    Ok(parsed
+        .results
+        .into_iter()
+        .map(|data| data.vulns.into_iter().map(|data| data.id).collect())
+        .collect())
+}
+
+/// Parse the response of the vulnerability endpoint.
+pub fn parse_vulnerability(json: &str) -> Fallible<Vulnerability> {
+    let parsed = serde_json::from_str::<VulnResponse>(json)?;
+    let severity = parsed
+        .database_specific
+        .and_then(|data| data.severity)
+        .or_else(|| parsed.severity.into_iter().next().map(|data| data.score));
+
+    Ok(Vulnerability {
+        id: parsed.id,
+        severity,
+    })
+}
+
+#[derive(Serialize)]
+struct QueryBatchRequest<'a> {
+    queries: Vec<Query<'a>>,
+}
+
+#[derive(Serialize)]
+struct Query<'a> {
+    package: QueryPackage<'a>,
+}
+
+#[derive(Serialize)]
+struct QueryPackage<'a> {
+    purl: &'a str,
+}
+
+#[derive(Deserialize)]
+struct QueryBatchResponse {
+    results: Vec<QueryResult>,
+}
+
+#[derive(Deserialize)]
+struct QueryResult {
+    #[serde(default)]
+    vulns: Vec<VulnId>,
+}
+
+#[derive(Deserialize)]
+struct VulnId {
+    id: String,
+}
+
+#[derive(Deserialize)]
+struct VulnResponse {
+    id: String,
+
+    #[serde(default)]
+    severity: Vec<Severity>,
+
+    database_specific: Option<DatabaseSpecific>,
+}
+
+#[derive(Deserialize)]
+struct Severity {
+    score: String,
+}
+
+#[derive(Deserialize)]
+struct DatabaseSpecific {
+    severity: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn query_batch_request_purls() {
+        let actual =
+            query_batch_request(&["pkg:maven/com.google.guava/guava@31.1-jre".into()]).unwrap();
+        assert_eq!(
+            r#"{"queries":[{"package":{"purl":"pkg:maven/com.google.guava/guava@31.1-jre"}}]}"#,
+            actual,
+        );
+    }
+
+    #[test]
+    fn parse_query_batch_results() {
+        let json = r#"{
+  "results": [
+    {
+      "vulns": [
+        {"id": "GHSA-7g45-4rm6-3mm3", "modified": "2023-10-06T05:28:37.474386Z"},
+        {"id": "GHSA-5mg8-w23w-74h3", "modified": "2023-10-06T05:28:37.474386Z"}
+      ]
+    },
+    {}
+  ]
+}"#;
+        let actual = parse_query_batch(json).unwrap();
+        let expected = vec![
+            vec![
+                "GHSA-7g45-4rm6-3mm3".to_string(),
+                "GHSA-5mg8-w23w-74h3".to_string(),
+            ],
+            vec![],
+        ];
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn parse_vulnerability_severity() {
+        let json = r#"{
+  "id": "GHSA-7g45-4rm6-3mm3",
+  "summary": "Guava vulnerable to insecure use of temporary directory",
+  "severity": [
+    {"type": "CVSS_V3", "score": "CVSS:3.1/AV:L/AC:L/PR:L/UI:N/S:U/C:H/I:H/A:N"}
+  ],
+  "database_specific": {"severity": "MODERATE"}
+}"#;
+        let actual = parse_vulnerability(json).unwrap();
+        assert_eq!(
+            Vulnerability {
+                id: "GHSA-7g45-4rm6-3mm3".into(),
+                severity: Some("MODERATE".into()),
+            },
+            actual,
+        );
+        assert_eq!("GHSA-7g45-4rm6-3mm3 (MODERATE)", actual.to_string());
+
+        let actual = parse_vulnerability(r#"{"id": "OSV-2020-1"}"#).unwrap();
+        assert_eq!(None, actual.severity);
+    }
+}
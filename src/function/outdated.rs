@@ -0,0 +1,140 @@
+/*
+ * Copyright 2023 sukawasatoru
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::function::maven::Dependency;
+use crate::model::Version;
+use std::fmt::{Display, Formatter};
+
+/// Position of the leftmost number that differs between the current and the latest version.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum VersionDelta {
+    Major,
+    Minor,
+    Patch,
+
+    /// Only the fourth or later number or the qualifier differs. e.g. `1.0.0-rc01` to `1.0.0`
+    Other,
+}
+
+impl Display for VersionDelta {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Major => "major",
+            Self::Minor => "minor",
+            Self::Patch => "patch",
+            Self::Other => "other",
+        })
+    }
+}
+
+/// Find the highest version from the maven-metadata.xml to compare with `current`.
+///
+/// The pre-releases are ignored unless `current` is also a pre-release. Falls back to
+/// `<release>` and `<latest>` if `<versions>` is empty.
+pub fn latest_version(metadata: &Dependency, current: Option<&str>) -> Option<String> {
+    let allow_pre_release = current
+        .map(|data| Version::from(data).is_pre_release())
+        .unwrap_or_default();
+    metadata
+        .versions
+        .iter()
+        .map(|data| Version::from(data.as_str()))
+        .filter(|data| allow_pre_release || !data.is_pre_release())
+        .max()
+        .map(|data| data.to_string())
+        .or_else(|| metadata.release_version.clone())
+        .or_else(|| metadata.latest_version.clone())
+}
+
+/// Classify how far `latest` is ahead of `current`. Returns `None` if `current` is up to date.
+pub fn classify_delta(current: &str, latest: &str) -> Option<VersionDelta> {
+    if Version::from(latest) <= Version::from(current) {
+        return None;
+    }
+
+    let current = numbers(current);
+    let latest = numbers(latest);
+    let delta = [
+        VersionDelta::Major,
+        VersionDelta::Minor,
+        VersionDelta::Patch,
+    ]
+    .into_iter()
+    .enumerate()
+    .find(|(index, _)| {
+        current.get(*index).copied().unwrap_or_default()
+            != latest.get(*index).copied().unwrap_or_default()
+    })
+    .map(|(_, delta)| delta)
+    .unwrap_or(VersionDelta::Other);
+    Some(delta)
+}
+
+/// Leading dot-separated numbers such as `[1, 12, 0]` of `1.12.0-alpha01`.
+fn numbers(version: &str) -> Vec<u64> {
+    version
+        .split(['-', '+'])
+        .next()
+        .unwrap_or_default()
+        .split('.')
+        .map_while(|data| data.parse().ok())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn latest_version_ignores_pre_release() {
+        let metadata = Dependency {
+            group_id: "androidx.core".into(),
+            artifact_id: "core-ktx".into(),
+            version: None,
+            latest_version: Some("1.13.0-alpha01".into()),
+            release_version: Some("1.13.0-alpha01".into()),
+            versions: vec![
+                "1.9.0".into(),
+                "1.12.0".into(),
+                "1.10.1".into(),
+                "1.13.0-alpha01".into(),
+            ],
+            last_updated: None,
+        };
+
+        assert_eq!(
+            Some("1.12.0".to_string()),
+            latest_version(&metadata, Some("1.9.0"))
+        );
+        assert_eq!(
+            Some("1.13.0-alpha01".to_string()),
+            latest_version(&metadata, Some("1.12.0-rc01"))
+        );
+    }
+
+    #[test]
+    fn classify_delta_position() {
+        assert_eq!(Some(VersionDelta::Major), classify_delta("4.12.0", "5.0.0"));
+        assert_eq!(Some(VersionDelta::Minor), classify_delta("1.9.0", "1.12.0"));
+        assert_eq!(Some(VersionDelta::Patch), classify_delta("1.9", "1.9.1"));
+        assert_eq!(
+            Some(VersionDelta::Other),
+            classify_delta("1.0.0-rc01", "1.0.0")
+        );
+        assert_eq!(None, classify_delta("1.12.0", "1.12.0"));
+        assert_eq!(None, classify_delta("1.13.0", "1.12.0"));
+    }
+}
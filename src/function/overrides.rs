@@ -0,0 +1,243 @@
+/*
+ * Copyright 2023 sukawasatoru
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Manually reviewed licenses that replace the licenses of the POMs that have wrong metadata.
+
+use crate::model::{Coordinate, SPDX};
+use crate::prelude::*;
+use serde::Deserialize;
+
+/// Reviewed licenses of the `coordinate`.
+///
+/// The `coordinate` without the version matches all versions of the artifact.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LicenseOverride {
+    pub coordinate: Coordinate,
+    pub licenses: Vec<SPDX>,
+    pub note: Option<String>,
+}
+
+impl LicenseOverride {
+    fn matches(&self, coordinate: &Coordinate) -> bool {
+        self.coordinate.group_id == coordinate.group_id
+            && self.coordinate.artifact_id == coordinate.artifact_id
+            && (self.coordinate.version.is_none() || self.coordinate.version == coordinate.version)
+    }
+}
+
+/// Returns the override of the `coordinate`. The override that has the version is preferred to
+/// the one without the version.
+pub fn find_override<'a>(
+    overrides: &'a [LicenseOverride],
+    coordinate: &Coordinate,
+) -> Option<&'a LicenseOverride> {
+    overrides
+        .iter()
+        .filter(|data| data.matches(coordinate))
+        .max_by_key(|data| data.coordinate.version.is_some())
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct OverrideTable {
+    #[serde(alias = "licenses")]
+    license: OverrideLicenses,
+    note: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum OverrideLicenses {
+    One(String),
+    Many(Vec<String>),
+}
+
+/// Parse the overrides file of the TOML that has a table per coordinate.
+///
+/// ```toml
+/// ["com.example:lib"]
+/// license = "MIT"
+/// note = "the POM says GPL-2.0 but the repository is relicensed"
+///
+/// ["com.example:dual:1.0.0"]
+/// license = ["Apache-2.0", "MIT"]
+/// ```
+pub fn parse_overrides_toml(source: &str) -> Fallible<Vec<LicenseOverride>> {
+    // `preserve_order` keeps the tables in the order of the file.
+    let tables = toml::from_str::<toml::Table>(source)?;
+
+    let mut overrides = vec![];
+    for (name, value) in tables {
+        let parse_table = || -> Fallible<LicenseOverride> {
+            let table = value.try_into::<OverrideTable>()?;
+            let licenses = match table.license {
+                OverrideLicenses::One(data) => vec![data.parse()?],
+                OverrideLicenses::Many(data) => data
+                    .iter()
+                    .map(|data| data.parse())
+                    .collect::<Result<Vec<SPDX>, _>>()?,
+            };
+            ensure!(!licenses.is_empty(), "missing license");
+            Ok(LicenseOverride {
+                coordinate: name.parse()?,
+                licenses,
+                note: table.note,
+            })
+        };
+        overrides.push(parse_table().with_context(|| format!("table: {}", name))?);
+    }
+
+    Ok(overrides)
+}
+
+/// Parse the overrides file of the CSV that has the `coordinate`, `license` and `note` columns.
+///
+/// The header line is required. The licenses of a coordinate are separated by `;`.
+pub fn parse_overrides_csv(source: &str) -> Fallible<Vec<LicenseOverride>> {
+    let mut reader = csv::ReaderBuilder::new()
+        .flexible(true)
+        .trim(csv::Trim::All)
+        .from_reader(source.as_bytes());
+    let header = reader.headers()?.clone();
+    let position = |name: &str| {
+        header
+            .iter()
+            .position(|data| data.eq_ignore_ascii_case(name))
+    };
+    let coordinate_index = position("coordinate").context("missing `coordinate` column")?;
+    let license_index = position("license").context("missing `license` column")?;
+    let note_index = position("note");
+
+    let mut overrides = vec![];
+    for record in reader.records() {
+        let record = record?;
+        let parse_record = || -> Fallible<LicenseOverride> {
+            let field = |index: usize| record.get(index).unwrap_or("");
+            let licenses = field(license_index)
+                .split(';')
+                .map(str::trim)
+                .filter(|data| !data.is_empty())
+                .map(|data| data.parse())
+                .collect::<Result<Vec<SPDX>, _>>()?;
+            ensure!(!licenses.is_empty(), "missing license");
+            Ok(LicenseOverride {
+                coordinate: field(coordinate_index).parse()?,
+                licenses,
+                note: note_index
+                    .map(field)
+                    .filter(|data| !data.is_empty())
+                    .map(str::to_owned),
+            })
+        };
+        let line = record.position().map_or(0, |data| data.line());
+        overrides.push(parse_record().with_context(|| format!("line {}", line))?);
+    }
+
+    Ok(overrides)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_overrides_toml_tables() {
+        let source = r#"
+# reviewed by the legal team
+["com.example:lib"]
+license = "MIT"
+note = "the POM says GPL-2.0 but the repository is relicensed"
+
+["com.example:dual:1.0.0"]
+license = ["Apache-2.0", "MIT"] # dual licensed
+"#;
+
+        let actual = parse_overrides_toml(source).unwrap();
+        assert_eq!(
+            vec![
+                LicenseOverride {
+                    coordinate: "com.example:lib".parse().unwrap(),
+                    licenses: vec![SPDX::MIT],
+                    note: Some("the POM says GPL-2.0 but the repository is relicensed".into()),
+                },
+                LicenseOverride {
+                    coordinate: "com.example:dual:1.0.0".parse().unwrap(),
+                    licenses: vec![SPDX::Apache20, SPDX::MIT],
+                    note: None,
+                },
+            ],
+            actual,
+        );
+    }
+
+    #[test]
+    fn parse_overrides_toml_error() {
+        assert!(parse_overrides_toml("license = \"MIT\"").is_err());
+        assert!(parse_overrides_toml("[\"com.example:lib\"]").is_err());
+        assert!(parse_overrides_toml("[\"com.example:lib\"]\nname = \"lib\"").is_err());
+        assert!(parse_overrides_toml("[\"com.example:lib\"\nlicense = \"MIT\"").is_err());
+    }
+
+    #[test]
+    fn parse_overrides_csv_quoted() {
+        let source = r#"coordinate,license,note
+com.example:lib,MIT,"reviewed, see ""LICENSE"" of the repository"
+com.example:dual:1.0.0,Apache-2.0; MIT,
+"#;
+
+        let actual = parse_overrides_csv(source).unwrap();
+        assert_eq!(2, actual.len());
+        assert_eq!(
+            Some(r#"reviewed, see "LICENSE" of the repository"#.into()),
+            actual[0].note,
+        );
+        assert_eq!(vec![SPDX::Apache20, SPDX::MIT], actual[1].licenses);
+        assert_eq!(None, actual[1].note);
+    }
+
+    #[test]
+    fn parse_overrides_csv_multiline_note() {
+        let source = "coordinate,license,note\ncom.example:lib,MIT,\"first line\nsecond line\"\ncom.example:other,Apache-2.0\n";
+
+        let actual = parse_overrides_csv(source).unwrap();
+        assert_eq!(2, actual.len());
+        assert_eq!(Some("first line\nsecond line".into()), actual[0].note);
+        assert_eq!(vec![SPDX::Apache20], actual[1].licenses);
+        assert_eq!(None, actual[1].note);
+    }
+
+    #[test]
+    fn find_override_prefers_version() {
+        let overrides = parse_overrides_toml(
+            r#"
+["com.example:lib"]
+license = "MIT"
+
+["com.example:lib:1.0.0"]
+license = "Apache-2.0"
+"#,
+        )
+        .unwrap();
+
+        let find = |coordinate: &str| {
+            find_override(&overrides, &coordinate.parse().unwrap())
+                .map(|data| data.licenses.clone())
+        };
+        assert_eq!(Some(vec![SPDX::Apache20]), find("com.example:lib:1.0.0"));
+        assert_eq!(Some(vec![SPDX::MIT]), find("com.example:lib:2.0.0"));
+        assert_eq!(None, find("com.example:other:1.0.0"));
+    }
+}
@@ -0,0 +1,133 @@
+/*
+ * Copyright 2023 sukawasatoru
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::io::Write;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+
+const MAX_IN_FLIGHT_WIDTH: usize = 80;
+
+/// Single-line progress display for stderr that shows the completed/failed counts and the
+/// currently resolving dependencies.
+pub struct Progress {
+    total: AtomicUsize,
+    state: Mutex<State>,
+}
+
+#[derive(Default)]
+struct State {
+    completed: usize,
+    failed: usize,
+    in_flight: Vec<String>,
+}
+
+impl Progress {
+    pub fn new(total: usize) -> Self {
+        Self {
+            total: AtomicUsize::new(total),
+            state: Mutex::new(State::default()),
+        }
+    }
+
+    /// Add the dependencies that are found during the run such as the managed artifacts of the
+    /// BOM.
+    pub fn add_total(&self, count: usize) {
+        self.total.fetch_add(count, Ordering::Relaxed);
+    }
+
+    pub fn start(&self, name: &str) {
+        let mut state = self.state.lock().unwrap();
+        state.in_flight.push(name.to_owned());
+        self.draw(&state);
+    }
+
+    pub fn finish(&self, name: &str, success: bool) {
+        let mut state = self.state.lock().unwrap();
+        if let Some(index) = state.in_flight.iter().position(|data| data == name) {
+            state.in_flight.remove(index);
+        }
+        if success {
+            state.completed += 1;
+        } else {
+            state.failed += 1;
+        }
+        self.draw(&state);
+    }
+
+    /// Clear the line for the following outputs.
+    pub fn clear(&self) {
+        let mut stderr = std::io::stderr().lock();
+        write!(stderr, "\r\x1b[2K").ok();
+        stderr.flush().ok();
+    }
+
+    fn draw(&self, state: &State) {
+        let mut stderr = std::io::stderr().lock();
+        write!(
+            stderr,
+            "\r\x1b[2K{}",
+            format_line(self.total.load(Ordering::Relaxed), state)
+        )
+        .ok();
+        stderr.flush().ok();
+    }
+}
+
+fn format_line(total: usize, state: &State) -> String {
+    let mut in_flight = state.in_flight.join(", ");
+    if MAX_IN_FLIGHT_WIDTH < in_flight.chars().count() {
+        in_flight = in_flight.chars().take(MAX_IN_FLIGHT_WIDTH - 3).collect();
+        in_flight.push_str("...");
+    }
+
+    format!(
+        "[{}/{}] completed: {}, failed: {} | {}",
+        state.completed + state.failed,
+        total,
+        state.completed,
+        state.failed,
+        in_flight,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn format_line_in_flight() {
+        let state = State {
+            completed: 3,
+            failed: 1,
+            in_flight: vec!["androidx.core:core-ktx:1.9.0".into(), "a:b:1".into()],
+        };
+        assert_eq!(
+            "[4/10] completed: 3, failed: 1 | androidx.core:core-ktx:1.9.0, a:b:1",
+            format_line(10, &state),
+        );
+    }
+
+    #[test]
+    fn format_line_truncate() {
+        let state = State {
+            completed: 0,
+            failed: 0,
+            in_flight: vec!["a".repeat(100)],
+        };
+        let actual = format_line(1, &state);
+        assert!(actual.ends_with(&format!("{}...", "a".repeat(77))));
+    }
+}
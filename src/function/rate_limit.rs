@@ -0,0 +1,195 @@
+/*
+ * Copyright 2023 sukawasatoru
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::prelude::*;
+use futures::future::BoxFuture;
+use futures::FutureExt;
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::Instant;
+#[cfg(target_arch = "wasm32")]
+use web_time::Instant;
+
+/// `<count>/<unit>` that the unit is `s`, `m` or `h`. e.g. `10/s`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Rate {
+    pub count: u32,
+    pub per: Duration,
+}
+
+impl FromStr for Rate {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (count, unit) = s.split_once('/').unwrap_or((s, "s"));
+        let count = count
+            .trim()
+            .parse::<u32>()
+            .with_context(|| format!("unexpected count: {}", s))?;
+        ensure!(0 < count, "count must be greater than 0: {}", s);
+
+        let per = match unit.trim() {
+            "s" => Duration::from_secs(1),
+            "m" => Duration::from_secs(60),
+            "h" => Duration::from_secs(60 * 60),
+            _ => bail!("unexpected unit: {}", s),
+        };
+
+        Ok(Self { count, per })
+    }
+}
+
+/// Timer to wait for the next token. Provide it to run on other than tokio.
+pub type Sleep = Arc<dyn Fn(Duration) -> BoxFuture<'static, ()> + Send + Sync>;
+
+/// Token bucket rate limiter keyed by the repository host.
+pub struct RateLimiter {
+    rate: Rate,
+    buckets: Mutex<HashMap<String, Bucket>>,
+    sleep: Sleep,
+}
+
+struct Bucket {
+    tokens: f64,
+    updated: Instant,
+}
+
+impl RateLimiter {
+    /// Create with the timer of tokio.
+    pub fn new(rate: Rate) -> Self {
+        Self::with_sleep(rate, Arc::new(|wait| tokio::time::sleep(wait).boxed()))
+    }
+
+    pub fn with_sleep(rate: Rate, sleep: Sleep) -> Self {
+        Self {
+            rate,
+            buckets: Mutex::new(HashMap::new()),
+            sleep,
+        }
+    }
+
+    /// Wait until a request to the `host` is allowed.
+    pub async fn acquire(&self, host: &str) {
+        while let Some(wait) = self.try_acquire(host, Instant::now()) {
+            trace!(%host, ?wait, "rate limited");
+            (self.sleep)(wait).await;
+        }
+    }
+
+    /// Returns the duration to wait if no token is available.
+    fn try_acquire(&self, host: &str, now: Instant) -> Option<Duration> {
+        let capacity = self.rate.count as f64;
+        let tokens_per_sec = capacity / self.rate.per.as_secs_f64();
+
+        let mut buckets = self.buckets.lock().unwrap();
+        let bucket = buckets.entry(host.to_owned()).or_insert(Bucket {
+            tokens: capacity,
+            updated: now,
+        });
+
+        let elapsed = now.saturating_duration_since(bucket.updated).as_secs_f64();
+        bucket.tokens = (bucket.tokens + elapsed * tokens_per_sec).min(capacity);
+        bucket.updated = now;
+
+        if 1.0 <= bucket.tokens {
+            bucket.tokens -= 1.0;
+            None
+        } else {
+            Some(Duration::from_secs_f64(
+                (1.0 - bucket.tokens) / tokens_per_sec,
+            ))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rate_from_str() {
+        assert_eq!(
+            Rate {
+                count: 10,
+                per: Duration::from_secs(1),
+            },
+            "10/s".parse().unwrap(),
+        );
+        assert_eq!(
+            Rate {
+                count: 600,
+                per: Duration::from_secs(60),
+            },
+            "600/m".parse().unwrap(),
+        );
+        assert_eq!(
+            Rate {
+                count: 5,
+                per: Duration::from_secs(1),
+            },
+            "5".parse().unwrap(),
+        );
+        assert!("0/s".parse::<Rate>().is_err());
+        assert!("10/d".parse::<Rate>().is_err());
+    }
+
+    #[test]
+    fn try_acquire_per_host() {
+        let limiter = RateLimiter::new("2/s".parse().unwrap());
+        let now = Instant::now();
+
+        assert_eq!(None, limiter.try_acquire("repo1.maven.org", now));
+        assert_eq!(None, limiter.try_acquire("repo1.maven.org", now));
+        assert_eq!(
+            Some(Duration::from_millis(500)),
+            limiter.try_acquire("repo1.maven.org", now),
+        );
+
+        // other host has own bucket.
+        assert_eq!(None, limiter.try_acquire("dl.google.com", now));
+
+        // refilled.
+        assert_eq!(
+            None,
+            limiter.try_acquire("repo1.maven.org", now + Duration::from_millis(500)),
+        );
+    }
+
+    #[test]
+    fn acquire_without_tokio() {
+        let waits = Arc::new(Mutex::new(vec![]));
+        let limiter = RateLimiter::with_sleep("20/s".parse().unwrap(), {
+            let waits = waits.clone();
+            Arc::new(move |wait| {
+                waits.lock().unwrap().push(wait);
+                std::thread::sleep(wait);
+                futures::future::ready(()).boxed()
+            })
+        });
+
+        futures::executor::block_on(async {
+            for _ in 0..21 {
+                limiter.acquire("repo1.maven.org").await;
+            }
+        });
+
+        assert!(!waits.lock().unwrap().is_empty());
+    }
+}
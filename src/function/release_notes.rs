@@ -0,0 +1,91 @@
+/*
+ * Copyright 2023 sukawasatoru
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::function::maven::POM;
+use crate::model::Coordinate;
+
+const ANDROIDX_RELEASES: &str = "https://developer.android.com/jetpack/androidx/releases/";
+
+/// Returns the release notes URL of the androidx artifact such as
+/// `https://developer.android.com/jetpack/androidx/releases/core#1.9.0`.
+///
+/// The `<url>` of the POM is used if it points to the release notes, otherwise the URL is
+/// constructed from the group. The version of `coordinate` is preferred to the anchor.
+pub fn androidx_release_notes_url(coordinate: &Coordinate, pom: &POM) -> Option<String> {
+    let library = coordinate.group_id.strip_prefix("androidx.")?;
+    let version = coordinate.version.as_deref().or(pom.version.as_deref());
+
+    let base = match pom.url.as_deref() {
+        Some(url) if url.starts_with(ANDROIDX_RELEASES) => {
+            url.split_once('#').map_or(url, |(base, _)| base).to_owned()
+        }
+        // e.g. `androidx.compose.ui` is `compose-ui`.
+        _ => format!("{}{}", ANDROIDX_RELEASES, library.replace('.', "-")),
+    };
+
+    Some(match version {
+        Some(version) => format!("{}#{}", base, version),
+        None => base,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn androidx_release_notes_url_from_pom() {
+        let actual = androidx_release_notes_url(
+            &"androidx.core:core-ktx:1.9.0".parse().unwrap(),
+            &POM {
+                url: Some(
+                    "https://developer.android.com/jetpack/androidx/releases/core#1.12.0".into(),
+                ),
+                ..POM::new(None, "dummy", "1.12.0")
+            },
+        );
+        assert_eq!(
+            Some("https://developer.android.com/jetpack/androidx/releases/core#1.9.0".into()),
+            actual,
+        );
+    }
+
+    #[test]
+    fn androidx_release_notes_url_construct() {
+        let actual = androidx_release_notes_url(
+            &"androidx.compose.ui:ui-tooling".parse().unwrap(),
+            &POM {
+                url: Some("https://developer.android.com/jetpack/compose".into()),
+                ..POM::new(None, "dummy", "1.12.0")
+            },
+        );
+        assert_eq!(
+            Some(
+                "https://developer.android.com/jetpack/androidx/releases/compose-ui#1.12.0".into()
+            ),
+            actual,
+        );
+    }
+
+    #[test]
+    fn androidx_release_notes_url_not_androidx() {
+        let actual = androidx_release_notes_url(
+            &"com.squareup.okhttp3:okhttp:4.11.0".parse().unwrap(),
+            &POM::new(None, "dummy", "1.12.0"),
+        );
+        assert_eq!(None, actual);
+    }
+}
@@ -0,0 +1,91 @@
+/*
+ * Copyright 2023 sukawasatoru
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+//! Health of the repositories to tell the network and the authentication problems from the
+//! resolution problems.
+
+use std::fmt::{Display, Formatter};
+
+/// Result of the request of the maven-metadata.xml of the probe artifact.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum RepositoryStatus {
+    /// The probe artifact is resolved.
+    Healthy,
+
+    /// `401 Unauthorized` or `403 Forbidden` that means the credentials are missing or wrong.
+    Unauthorized(u16),
+
+    /// The repository is reachable but has no probe artifact.
+    ProbeNotFound,
+
+    /// The domain is gone, the proxy refused the connection or the server doesn't respond.
+    Unreachable(String),
+
+    /// The server returned an error or the maven-metadata.xml that cannot be parsed.
+    Error(String),
+}
+
+impl RepositoryStatus {
+    /// Status of the response that is not successful.
+    pub fn from_status(status: u16) -> Self {
+        match status {
+            401 | 403 => Self::Unauthorized(status),
+            404 => Self::ProbeNotFound,
+            _ => Self::Error(format!("HTTP {}", status)),
+        }
+    }
+
+    pub fn is_healthy(&self) -> bool {
+        matches!(self, Self::Healthy)
+    }
+}
+
+impl Display for RepositoryStatus {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Healthy => f.write_str("ok"),
+            Self::Unauthorized(status) => write!(f, "unauthorized (HTTP {})", status),
+            Self::ProbeNotFound => f.write_str("probe not found"),
+            Self::Unreachable(reason) => write!(f, "unreachable ({})", reason),
+            Self::Error(reason) => write!(f, "error ({})", reason),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_status() {
+        assert_eq!(
+            RepositoryStatus::Unauthorized(401),
+            RepositoryStatus::from_status(401)
+        );
+        assert_eq!(
+            RepositoryStatus::Unauthorized(403),
+            RepositoryStatus::from_status(403)
+        );
+        assert_eq!(
+            RepositoryStatus::ProbeNotFound,
+            RepositoryStatus::from_status(404)
+        );
+        assert_eq!(
+            "error (HTTP 502)",
+            RepositoryStatus::from_status(502).to_string()
+        );
+        assert!(RepositoryStatus::Healthy.is_healthy());
+    }
+}
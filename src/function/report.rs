@@ -0,0 +1,162 @@
+/*
+ * Copyright 2023 sukawasatoru
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Formatter of the report that has a row per dependency.
+
+use crate::prelude::*;
+use std::io::Write;
+
+/// Destination of the rows of the report.
+///
+/// The fields of the rows are in the same order as the header. The columns of the rows are
+/// decided by the command line tool, so the crate provides no builder of the rows.
+pub trait ReportFormatter {
+    fn write_header(&mut self, header: &[&str]) -> Fallible<()>;
+
+    fn write_row(&mut self, row: &[String]) -> Fallible<()>;
+
+    /// Write the rest of the report such as the closing tags and flush it.
+    fn finish(&mut self) -> Fallible<()>;
+}
+
+/// Quoting of the CSV fields.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "cli", derive(clap::ValueEnum))]
+pub enum QuoteStyle {
+    /// Quote the fields that have the delimiter, the quote or the line break.
+    #[default]
+    Necessary,
+
+    Always,
+
+    /// Quote the fields that are not numbers.
+    NonNumeric,
+
+    /// Never quote the fields even if the output cannot be parsed back.
+    Never,
+}
+
+impl From<QuoteStyle> for csv::QuoteStyle {
+    fn from(value: QuoteStyle) -> Self {
+        match value {
+            QuoteStyle::Necessary => csv::QuoteStyle::Necessary,
+            QuoteStyle::Always => csv::QuoteStyle::Always,
+            QuoteStyle::NonNumeric => csv::QuoteStyle::NonNumeric,
+            QuoteStyle::Never => csv::QuoteStyle::Never,
+        }
+    }
+}
+
+/// Returns the name of the column in snake case, e.g. `version_input` for "Version (Input)".
+pub fn column_key(name: &str) -> String {
+    name.split(|data: char| !data.is_ascii_alphanumeric())
+        .filter(|data| !data.is_empty())
+        .map(str::to_ascii_lowercase)
+        .collect::<Vec<_>>()
+        .join("_")
+}
+
+/// [ReportFormatter] of the CSV that has the header line.
+pub struct CsvFormatter<W: Write> {
+    writer: csv::Writer<W>,
+}
+
+impl<W: Write> CsvFormatter<W> {
+    pub fn new(writer: W) -> Self {
+        Self::with_dialect(writer, b',', QuoteStyle::Necessary)
+    }
+
+    /// Same as [CsvFormatter::new] but the fields are separated by the `delimiter` such as `;`
+    /// and quoted by the `quote_style`.
+    pub fn with_dialect(writer: W, delimiter: u8, quote_style: QuoteStyle) -> Self {
+        Self {
+            writer: csv::WriterBuilder::new()
+                .delimiter(delimiter)
+                .quote_style(quote_style.into())
+                .from_writer(writer),
+        }
+    }
+
+    /// Flush the buffered rows without finishing the report.
+    pub fn flush(&mut self) -> Fallible<()> {
+        self.writer.flush()?;
+        Ok(())
+    }
+
+    /// Flush the buffered rows and returns the underlying writer.
+    pub fn into_inner(self) -> Fallible<W> {
+        self.writer
+            .into_inner()
+            .map_err(|e| anyhow!("failed to flush the CSV: {}", e.error()))
+    }
+}
+
+impl<W: Write> ReportFormatter for CsvFormatter<W> {
+    fn write_header(&mut self, header: &[&str]) -> Fallible<()> {
+        self.writer.write_record(header)?;
+        Ok(())
+    }
+
+    fn write_row(&mut self, row: &[String]) -> Fallible<()> {
+        self.writer.write_record(row)?;
+        Ok(())
+    }
+
+    fn finish(&mut self) -> Fallible<()> {
+        self.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn csv_formatter() {
+        let mut formatter = CsvFormatter::new(vec![]);
+        formatter.write_header(&["Dependency", "Note"]).unwrap();
+        formatter
+            .write_row(&["com.example:lib".into(), "a, b".into()])
+            .unwrap();
+        formatter.finish().unwrap();
+
+        assert_eq!(
+            "Dependency,Note\ncom.example:lib,\"a, b\"\n",
+            String::from_utf8(formatter.into_inner().unwrap()).unwrap()
+        );
+    }
+
+    #[test]
+    fn csv_formatter_dialect() {
+        let mut formatter = CsvFormatter::with_dialect(vec![], b';', QuoteStyle::Always);
+        formatter.write_header(&["Dependency", "Size"]).unwrap();
+        formatter
+            .write_row(&["com.example:lib".into(), "1,5".into()])
+            .unwrap();
+        formatter.finish().unwrap();
+
+        assert_eq!(
+            "\"Dependency\";\"Size\"\n\"com.example:lib\";\"1,5\"\n",
+            String::from_utf8(formatter.into_inner().unwrap()).unwrap()
+        );
+    }
+
+    #[test]
+    fn column_key_version_input() {
+        assert_eq!("version_input", column_key("Version (Input)"));
+        assert_eq!("sha_256", column_key("SHA-256"));
+    }
+}
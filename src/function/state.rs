@@ -0,0 +1,137 @@
+/*
+ * Copyright 2023 sukawasatoru
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! State file that records the resolved results so that a re-run resolves only the dependencies
+//! that failed.
+
+use crate::function::maven::{ArtifactFile, POM};
+use crate::model::Coordinate;
+use crate::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::io::{Read, Write};
+
+const STATE_VERSION: u32 = 1;
+
+#[derive(Serialize)]
+struct StateRef<'a> {
+    version: u32,
+    resolved: Vec<ResolvedRef<'a>>,
+}
+
+#[derive(Serialize)]
+struct ResolvedRef<'a> {
+    coordinate: String,
+    pom: &'a POM,
+    artifact_file: Option<&'a ArtifactFile>,
+}
+
+#[derive(Deserialize)]
+struct State {
+    version: u32,
+    resolved: Vec<Resolved>,
+}
+
+#[derive(Deserialize)]
+struct Resolved {
+    coordinate: String,
+    pom: POM,
+    artifact_file: Option<ArtifactFile>,
+}
+
+/// Returns the resolved results of the previous run.
+pub fn read_state<R: Read>(
+    reader: R,
+) -> Fallible<BTreeMap<Coordinate, (POM, Option<ArtifactFile>)>> {
+    let state = serde_json::from_reader::<_, State>(reader)?;
+    ensure!(
+        state.version == STATE_VERSION,
+        "unsupported state version: {}",
+        state.version,
+    );
+
+    state
+        .resolved
+        .into_iter()
+        .map(|data| {
+            Ok((
+                data.coordinate.parse::<Coordinate>()?,
+                (data.pom, data.artifact_file),
+            ))
+        })
+        .collect()
+}
+
+/// Write the resolved results for the next run.
+pub fn write_state<'a, W, I>(writer: W, resolved: I) -> Fallible<()>
+where
+    W: Write,
+    I: IntoIterator<Item = (&'a Coordinate, &'a POM, Option<&'a ArtifactFile>)>,
+{
+    let state = StateRef {
+        version: STATE_VERSION,
+        resolved: resolved
+            .into_iter()
+            .map(|(coordinate, pom, artifact_file)| ResolvedRef {
+                coordinate: coordinate.to_string(),
+                pom,
+                artifact_file,
+            })
+            .collect(),
+    };
+    serde_json::to_writer_pretty(writer, &state)?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::SPDX;
+
+    #[test]
+    fn write_and_read_state() {
+        let coordinate = "com.github.bumptech.glide:glide:4.16.0"
+            .parse::<Coordinate>()
+            .unwrap();
+        let pom = POM {
+            group_id: Some("com.github.bumptech.glide".into()),
+            artifact_id: "glide".into(),
+            version: Some("4.16.0".into()),
+            packaging: Some("aar".into()),
+            name: Some("Glide".into()),
+            licenses: vec![SPDX::BSD2, SPDX::Other("Custom".into())],
+            ..Default::default()
+        };
+        let artifact_file = ArtifactFile {
+            file_name: "glide-4.16.0.aar".into(),
+            size: Some(9),
+            sha256: None,
+        };
+
+        let mut buf = vec![];
+        write_state(&mut buf, [(&coordinate, &pom, Some(&artifact_file))]).unwrap();
+        let actual = read_state(buf.as_slice()).unwrap();
+
+        let expected = BTreeMap::from([(coordinate, (pom, Some(artifact_file)))]);
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn read_state_unsupported_version() {
+        assert!(read_state(r#"{"version":0,"resolved":[]}"#.as_bytes()).is_err());
+    }
+}
@@ -0,0 +1,169 @@
+/*
+ * Copyright 2023 sukawasatoru
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::model::{Coordinate, SPDX};
+use crate::prelude::*;
+use std::io::Write;
+
+/// Returns the number of the artifacts per license in descending order of the number.
+///
+/// The artifact that has multiple licenses is counted for each license and the artifact that has
+/// no license is counted as `Unknown`.
+pub fn count_licenses<'a, I>(licenses: I) -> Vec<(String, usize)>
+where
+    I: IntoIterator<Item = &'a [SPDX]>,
+{
+    let mut counts = Vec::<(String, usize)>::new();
+    for licenses in licenses {
+        let names = if licenses.is_empty() {
+            vec!["Unknown".to_string()]
+        } else {
+            licenses.iter().map(SPDX::to_string).collect()
+        };
+        for name in names {
+            match counts.iter_mut().find(|(data, _)| *data == name) {
+                Some((_, count)) => *count += 1,
+                None => counts.push((name, 1)),
+            }
+        }
+    }
+
+    counts.sort_by(|(a_name, a_count), (b_name, b_count)| {
+        b_count.cmp(a_count).then_with(|| a_name.cmp(b_name))
+    });
+    counts
+}
+
+/// Whether the `licenses` is empty or has the license that is not recognized.
+pub fn has_unknown_license(licenses: &[SPDX]) -> bool {
+    licenses.is_empty() || licenses.iter().any(|data| matches!(data, SPDX::Other(_)))
+}
+
+/// Write the number of the artifacts per license, the artifacts that have the license that is not
+/// open source and the artifacts that have no license or the license that is not recognized.
+pub fn write_summary<'a, W, I>(mut writer: W, entries: I) -> Fallible<()>
+where
+    W: Write,
+    I: IntoIterator<Item = (&'a Coordinate, &'a [SPDX])>,
+{
+    let entries = entries.into_iter().collect::<Vec<_>>();
+
+    writeln!(writer, "Licenses:")?;
+    for (license, count) in count_licenses(entries.iter().map(|(_, licenses)| *licenses)) {
+        writeln!(writer, "  {}: {}", license, count)?;
+    }
+
+    let proprietaries = entries
+        .iter()
+        .filter(|(_, licenses)| licenses.iter().any(SPDX::is_proprietary))
+        .collect::<Vec<_>>();
+    if !proprietaries.is_empty() {
+        writeln!(writer)?;
+        writeln!(writer, "Not open source:")?;
+        for (coordinate, licenses) in proprietaries {
+            let licenses = licenses
+                .iter()
+                .filter(|data| data.is_proprietary())
+                .map(SPDX::to_string)
+                .collect::<Vec<_>>()
+                .join("/");
+            writeln!(writer, "  {} ({})", coordinate, licenses)?;
+        }
+    }
+
+    let unknowns = entries
+        .iter()
+        .filter(|(_, licenses)| has_unknown_license(licenses))
+        .collect::<Vec<_>>();
+    if unknowns.is_empty() {
+        return Ok(());
+    }
+
+    writeln!(writer)?;
+    writeln!(writer, "Unknown or other licenses:")?;
+    for (coordinate, licenses) in unknowns {
+        let licenses = if licenses.is_empty() {
+            "none".to_string()
+        } else {
+            licenses
+                .iter()
+                .map(SPDX::to_string)
+                .collect::<Vec<_>>()
+                .join("/")
+        };
+        writeln!(writer, "  {} ({})", coordinate, licenses)?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn count_licenses_sorted() {
+        let licenses = [
+            vec![SPDX::Apache20],
+            vec![SPDX::MIT, SPDX::Apache20],
+            vec![],
+            vec![SPDX::MIT],
+            vec![SPDX::BSD2],
+        ];
+        let actual = count_licenses(licenses.iter().map(Vec::as_slice));
+        let expected = vec![
+            ("Apache-2.0".to_string(), 2),
+            ("MIT".to_string(), 2),
+            ("BSD-2-Clause".to_string(), 1),
+            ("Unknown".to_string(), 1),
+        ];
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn write_summary_unknowns() {
+        let glide = "com.github.bumptech.glide:glide:4.16.0"
+            .parse::<Coordinate>()
+            .unwrap();
+        let custom = "com.example:custom:1.0.0".parse::<Coordinate>().unwrap();
+        let none = "com.example:none:1.0.0".parse::<Coordinate>().unwrap();
+        let glide_licenses = [SPDX::BSD2, SPDX::Apache20];
+        let custom_licenses = [SPDX::Other("Custom License".into())];
+
+        let mut actual = vec![];
+        write_summary(
+            &mut actual,
+            [
+                (&glide, glide_licenses.as_slice()),
+                (&custom, custom_licenses.as_slice()),
+                (&none, [].as_slice()),
+            ],
+        )
+        .unwrap();
+
+        let expected = r#"Licenses:
+  Apache-2.0: 1
+  BSD-2-Clause: 1
+  Custom License: 1
+  Unknown: 1
+
+Unknown or other licenses:
+  com.example:custom:1.0.0 (Custom License)
+  com.example:none:1.0.0 (none)
+"#;
+        assert_eq!(expected, String::from_utf8(actual).unwrap());
+    }
+}
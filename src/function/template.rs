@@ -0,0 +1,372 @@
+/*
+ * Copyright 2023 sukawasatoru
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Report that is rendered from the user-supplied template.
+//!
+//! The syntax is a subset of Mustache/Handlebars:
+//!
+//! - `{{name}}` is substituted with the variable that is HTML-escaped.
+//! - `{{{name}}}` is substituted with the variable as is.
+//! - `{{#each rows}}...{{/each}}` repeats the block for each item of the list. The fields of the
+//!   item shadow the other variables in the block.
+//! - `{{#if name}}...{{/if}}` writes the block only if the variable is not empty.
+//!
+//! The line that has only a block tag such as `{{#each rows}}` is removed with its line break.
+
+use crate::function::report::{column_key, ReportFormatter};
+use crate::prelude::*;
+use std::collections::HashMap;
+use std::io::Write;
+
+type Item = HashMap<String, String>;
+
+/// [ReportFormatter] that renders the `template` when the report is finished.
+///
+/// The rows are exposed as the `rows` list whose fields are named after the header in snake
+/// case, e.g. `version_input` for "Version (Input)". `count` is the number of the rows.
+pub struct TemplateFormatter<W: Write> {
+    writer: W,
+    template: String,
+    vars: Item,
+    lists: HashMap<String, Vec<Item>>,
+    keys: Vec<String>,
+    rows: Vec<Item>,
+}
+
+impl<W: Write> TemplateFormatter<W> {
+    /// Returns an error if the `template` has the syntax error.
+    pub fn new(writer: W, template: String, vars: Item) -> Fallible<Self> {
+        check_template(&template)?;
+        Ok(Self {
+            writer,
+            template,
+            vars,
+            lists: HashMap::new(),
+            keys: vec![],
+            rows: vec![],
+        })
+    }
+
+    /// Expose the list for `{{#each name}}` in addition to the `rows`.
+    pub fn with_list(mut self, name: impl Into<String>, items: Vec<Item>) -> Self {
+        self.lists.insert(name.into(), items);
+        self
+    }
+}
+
+impl<W: Write> ReportFormatter for TemplateFormatter<W> {
+    fn write_header(&mut self, header: &[&str]) -> Fallible<()> {
+        self.keys = header.iter().map(|data| column_key(data)).collect();
+        Ok(())
+    }
+
+    fn write_row(&mut self, row: &[String]) -> Fallible<()> {
+        self.rows
+            .push(self.keys.iter().cloned().zip(row.iter().cloned()).collect());
+        Ok(())
+    }
+
+    fn finish(&mut self) -> Fallible<()> {
+        let mut vars = self.vars.clone();
+        vars.insert("count".into(), self.rows.len().to_string());
+        let mut lists = self.lists.clone();
+        lists.insert("rows".into(), std::mem::take(&mut self.rows));
+
+        let mut rendered = String::new();
+        render(&mut rendered, &parse(&self.template)?, &[&vars], &lists)?;
+        self.writer.write_all(rendered.as_bytes())?;
+        self.writer.flush()?;
+        Ok(())
+    }
+}
+
+/// Returns an error if the `template` has the syntax error such as the unclosed block.
+pub fn check_template(template: &str) -> Fallible<()> {
+    parse(template).map(|_| ())
+}
+
+#[derive(Debug, Eq, PartialEq)]
+enum Node<'a> {
+    Text(&'a str),
+    Var(&'a str),
+    RawVar(&'a str),
+    Each(&'a str, Vec<Node<'a>>),
+    If(&'a str, Vec<Node<'a>>),
+}
+
+#[derive(Debug, Eq, PartialEq)]
+enum Token<'a> {
+    Text(&'a str),
+    Var(&'a str),
+    RawVar(&'a str),
+    Open(&'a str, &'a str),
+    Close(&'a str),
+}
+
+fn parse(template: &str) -> Fallible<Vec<Node>> {
+    let mut tokens = tokenize(template)?.into_iter();
+    let (nodes, closing) = parse_block(&mut tokens)?;
+    if let Some(closing) = closing {
+        bail!("unexpected {{{{/{}}}}}", closing);
+    }
+    Ok(nodes)
+}
+
+/// Split the `template` into the texts and the tags, and remove the lines that have only a block
+/// tag.
+fn tokenize(template: &str) -> Fallible<Vec<Token>> {
+    let mut tokens = vec![];
+    let mut cursor = 0;
+    while let Some(start) = template[cursor..].find("{{").map(|data| cursor + data) {
+        let raw = template[start..].starts_with("{{{");
+        let (open, close) = if raw { ("{{{", "}}}") } else { ("{{", "}}") };
+        let end = template[start..]
+            .find(close)
+            .map(|data| start + data + close.len())
+            .with_context(|| format!("unclosed tag: {}", &template[start..]))?;
+        let tag = template[start + open.len()..end - close.len()].trim();
+
+        let token = if raw {
+            Token::RawVar(tag)
+        } else if let Some(closing) = tag.strip_prefix('/') {
+            Token::Close(closing.trim())
+        } else if let Some(data) = tag.strip_prefix('#') {
+            let (block, name) = data
+                .trim()
+                .split_once(char::is_whitespace)
+                .with_context(|| format!("missing name: {{{{{}}}}}", tag))?;
+            Token::Open(block, name.trim())
+        } else {
+            Token::Var(tag)
+        };
+
+        let mut text_end = start;
+        let mut next = end;
+        if matches!(token, Token::Open(..) | Token::Close(_)) {
+            let line_start = template[..start].rfind('\n').map_or(0, |data| data + 1);
+            let line_end = template[end..]
+                .find('\n')
+                .map_or(template.len(), |data| end + data + 1);
+            if cursor <= line_start
+                && template[line_start..start].trim().is_empty()
+                && template[end..line_end].trim().is_empty()
+            {
+                text_end = line_start;
+                next = line_end;
+            }
+        }
+        if cursor < text_end {
+            tokens.push(Token::Text(&template[cursor..text_end]));
+        }
+        tokens.push(token);
+        cursor = next;
+    }
+    if cursor < template.len() {
+        tokens.push(Token::Text(&template[cursor..]));
+    }
+    Ok(tokens)
+}
+
+/// Parse until the end of the `tokens` or the closing tag such as `{{/each}}`.
+///
+/// Returns the nodes and the name of the closing tag.
+fn parse_block<'a, I>(tokens: &mut I) -> Fallible<(Vec<Node<'a>>, Option<&'a str>)>
+where
+    I: Iterator<Item = Token<'a>>,
+{
+    let mut nodes = vec![];
+    while let Some(token) = tokens.next() {
+        match token {
+            Token::Text(text) => nodes.push(Node::Text(text)),
+            Token::Var(name) => nodes.push(Node::Var(name)),
+            Token::RawVar(name) => nodes.push(Node::RawVar(name)),
+            Token::Close(closing) => return Ok((nodes, Some(closing))),
+            Token::Open(block @ ("each" | "if"), name) => {
+                let (children, closing) = parse_block(tokens)?;
+                ensure!(
+                    closing == Some(block),
+                    "unclosed {{{{#{} {}}}}}",
+                    block,
+                    name,
+                );
+                nodes.push(match block {
+                    "each" => Node::Each(name, children),
+                    _ => Node::If(name, children),
+                });
+            }
+            Token::Open(block, _) => bail!("unknown block: {}", block),
+        }
+    }
+    Ok((nodes, None))
+}
+
+fn render(
+    rendered: &mut String,
+    nodes: &[Node],
+    scopes: &[&Item],
+    lists: &HashMap<String, Vec<Item>>,
+) -> Fallible<()> {
+    let lookup = |name: &str| {
+        scopes
+            .iter()
+            .rev()
+            .find_map(|scope| scope.get(name))
+            .with_context(|| format!("unknown variable: {}", name))
+    };
+    for node in nodes {
+        match node {
+            Node::Text(text) => rendered.push_str(text),
+            Node::Var(name) => escape_html(rendered, lookup(name)?),
+            Node::RawVar(name) => rendered.push_str(lookup(name)?),
+            Node::Each(name, children) => {
+                let items = lists
+                    .get(*name)
+                    .with_context(|| format!("unknown list: {}", name))?;
+                for item in items {
+                    let mut scopes = scopes.to_vec();
+                    scopes.push(item);
+                    render(rendered, children, &scopes, lists)?;
+                }
+            }
+            Node::If(name, children) => {
+                if !lookup(name)?.is_empty() {
+                    render(rendered, children, scopes, lists)?;
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+fn escape_html(rendered: &mut String, value: &str) {
+    for c in value.chars() {
+        match c {
+            '&' => rendered.push_str("&amp;"),
+            '<' => rendered.push_str("&lt;"),
+            '>' => rendered.push_str("&gt;"),
+            '"' => rendered.push_str("&quot;"),
+            '\'' => rendered.push_str("&#39;"),
+            _ => rendered.push(c),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn template_formatter() {
+        let template = "\
+{{title}} ({{count}})
+{{#each rows}}
+- {{dependency}} {{version_input}}{{#if notes}} # {{notes}}{{/if}}
+{{/each}}
+{{#each licenses}}{{license}}: {{count}}
+{{/each}}";
+        let mut formatter = TemplateFormatter::new(
+            vec![],
+            template.into(),
+            HashMap::from([("title".into(), "Example".into())]),
+        )
+        .unwrap()
+        .with_list(
+            "licenses",
+            vec![HashMap::from([
+                ("license".into(), "Apache-2.0".into()),
+                ("count".into(), "2".into()),
+            ])],
+        );
+        formatter
+            .write_header(&["Dependency", "Version (Input)", "Notes"])
+            .unwrap();
+        formatter
+            .write_row(&["androidx.core:core".into(), "1.12.0".into(), "".into()])
+            .unwrap();
+        formatter
+            .write_row(&[
+                "androidx.core:core-ktx".into(),
+                "1.12.0".into(),
+                "ui".into(),
+            ])
+            .unwrap();
+        formatter.finish().unwrap();
+
+        let expected = "\
+Example (2)
+- androidx.core:core 1.12.0
+- androidx.core:core-ktx 1.12.0 # ui
+Apache-2.0: 2
+";
+        assert_eq!(expected, String::from_utf8(formatter.writer).unwrap());
+    }
+
+    #[test]
+    fn parse_error() {
+        assert!(parse("{{#each rows}}{{name}}").is_err());
+        assert!(parse("{{#each rows}}{{/if}}").is_err());
+        assert!(parse("{{/each}}").is_err());
+        assert!(parse("{{#unless name}}{{/unless}}").is_err());
+        assert!(parse("{{name").is_err());
+    }
+
+    #[test]
+    fn render_escape() {
+        let mut rendered = String::new();
+        let nodes = parse("{{name}} {{{name}}}").unwrap();
+        let vars = HashMap::from([("name".into(), "<a href=\"x\">R&D's</a>".into())]);
+        render(&mut rendered, &nodes, &[&vars], &HashMap::new()).unwrap();
+        assert_eq!(
+            "&lt;a href=&quot;x&quot;&gt;R&amp;D&#39;s&lt;/a&gt; <a href=\"x\">R&D's</a>",
+            rendered,
+        );
+    }
+
+    #[test]
+    fn render_standalone_block_tags() {
+        let template = "\
+<ul>
+  {{#each rows}}
+  <li>{{name}}</li>
+  {{/each}}
+</ul>
+{{#if empty}}none{{/if}}
+{{#each rows}}{{name}} {{/each}}
+";
+        let mut rendered = String::new();
+        let nodes = parse(template).unwrap();
+        let vars = HashMap::from([("empty".into(), "".into())]);
+        let lists = HashMap::from([(
+            "rows".into(),
+            vec![
+                HashMap::from([("name".into(), "a".into())]),
+                HashMap::from([("name".into(), "b".into())]),
+            ],
+        )]);
+        render(&mut rendered, &nodes, &[&vars], &lists).unwrap();
+        assert_eq!(
+            "<ul>\n  <li>a</li>\n  <li>b</li>\n</ul>\n\na b \n",
+            rendered
+        );
+    }
+
+    #[test]
+    fn render_unknown_variable() {
+        let mut rendered = String::new();
+        let nodes = parse("{{missing}}").unwrap();
+        assert!(render(&mut rendered, &nodes, &[&HashMap::new()], &HashMap::new()).is_err());
+    }
+}
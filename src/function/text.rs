@@ -0,0 +1,172 @@
+/*
+ * Copyright 2023 sukawasatoru
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::function::maven::POM;
+use crate::prelude::*;
+
+/// Transform (translate, transliterate, etc.) the human-readable text of the POM.
+///
+/// Returns `None` if the `text` cannot be transformed.
+pub trait TextTransformer: Send + Sync {
+    fn transform(&self, text: &str) -> Option<String>;
+}
+
+impl<F> TextTransformer for F
+where
+    F: Fn(&str) -> Option<String> + Send + Sync,
+{
+    fn transform(&self, text: &str) -> Option<String> {
+        self(text)
+    }
+}
+
+/// Fold the latin letters with diacritics and the typographic punctuations to ASCII.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct AsciiTransformer;
+
+impl TextTransformer for AsciiTransformer {
+    fn transform(&self, text: &str) -> Option<String> {
+        let mut ret = String::with_capacity(text.len());
+        for c in text.chars() {
+            if c.is_ascii() {
+                ret.push(c);
+                continue;
+            }
+
+            ret.push_str(fold_to_ascii(c)?);
+        }
+
+        Some(ret)
+    }
+}
+
+/// Apply the `transformer` to `<name>` and `<description>`.
+///
+/// The `<artifactId>` is used for the name and the description is dropped if the `transformer`
+/// cannot transform them.
+pub fn transform_pom_text(pom: &mut POM, transformer: &dyn TextTransformer) {
+    if let Some(name) = pom.name.take() {
+        pom.name = Some(transformer.transform(&name).unwrap_or_else(|| {
+            info!(%name, "use artifact id instead of name");
+            pom.artifact_id.clone()
+        }));
+    }
+
+    if let Some(description) = pom.description.take() {
+        pom.description = transformer.transform(&description);
+        if pom.description.is_none() {
+            info!(%description, "drop description");
+        }
+    }
+}
+
+fn fold_to_ascii(c: char) -> Option<&'static str> {
+    Some(match c {
+        'À' | 'Á' | 'Â' | 'Ã' | 'Ä' | 'Å' | 'Ā' | 'Ă' | 'Ą' => "A",
+        'à' | 'á' | 'â' | 'ã' | 'ä' | 'å' | 'ā' | 'ă' | 'ą' => "a",
+        'Æ' => "AE",
+        'æ' => "ae",
+        'Ç' | 'Ć' | 'Č' => "C",
+        'ç' | 'ć' | 'č' => "c",
+        'Ď' | 'Đ' => "D",
+        'ď' | 'đ' => "d",
+        'È' | 'É' | 'Ê' | 'Ë' | 'Ē' | 'Ė' | 'Ę' | 'Ě' => "E",
+        'è' | 'é' | 'ê' | 'ë' | 'ē' | 'ė' | 'ę' | 'ě' => "e",
+        'Ğ' => "G",
+        'ğ' => "g",
+        'Ì' | 'Í' | 'Î' | 'Ï' | 'Ī' | 'İ' => "I",
+        'ì' | 'í' | 'î' | 'ï' | 'ī' | 'ı' => "i",
+        'Ł' | 'Ľ' => "L",
+        'ł' | 'ľ' => "l",
+        'Ñ' | 'Ń' | 'Ň' => "N",
+        'ñ' | 'ń' | 'ň' => "n",
+        'Ò' | 'Ó' | 'Ô' | 'Õ' | 'Ö' | 'Ø' | 'Ō' | 'Ő' => "O",
+        'ò' | 'ó' | 'ô' | 'õ' | 'ö' | 'ø' | 'ō' | 'ő' => "o",
+        'Œ' => "OE",
+        'œ' => "oe",
+        'Ř' => "R",
+        'ř' => "r",
+        'Ś' | 'Ş' | 'Š' => "S",
+        'ś' | 'ş' | 'š' => "s",
+        'ß' => "ss",
+        'Ť' | 'Ţ' => "T",
+        'ť' | 'ţ' => "t",
+        'Ù' | 'Ú' | 'Û' | 'Ü' | 'Ū' | 'Ů' | 'Ű' => "U",
+        'ù' | 'ú' | 'û' | 'ü' | 'ū' | 'ů' | 'ű' => "u",
+        'Ý' | 'Ÿ' => "Y",
+        'ý' | 'ÿ' => "y",
+        'Ź' | 'Ż' | 'Ž' => "Z",
+        'ź' | 'ż' | 'ž' => "z",
+        '\u{00A0}' | '\u{2002}' | '\u{2003}' | '\u{2009}' | '\u{3000}' => " ",
+        '‘' | '’' | '‚' | '′' => "'",
+        '“' | '”' | '„' | '″' => "\"",
+        '‐' | '‑' | '‒' | '–' | '—' | '―' => "-",
+        '…' => "...",
+        '•' => "*",
+        '©' => "(c)",
+        '®' => "(R)",
+        '™' => "(TM)",
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ascii_transformer_fold() {
+        let actual = AsciiTransformer.transform("Señor Café — “Zürich” …");
+        assert_eq!(Some("Senor Cafe - \"Zurich\" ...".into()), actual);
+    }
+
+    #[test]
+    fn ascii_transformer_unsupported() {
+        assert_eq!(None, AsciiTransformer.transform("日本語"));
+    }
+
+    #[test]
+    fn transform_pom_text_fallback() {
+        let mut pom = POM {
+            group_id: Some("com.example".into()),
+            artifact_id: "example".into(),
+            name: Some("例".into()),
+            description: Some("説明".into()),
+            ..Default::default()
+        };
+
+        transform_pom_text(&mut pom, &AsciiTransformer);
+
+        assert_eq!(Some("example".into()), pom.name);
+        assert_eq!(None, pom.description);
+    }
+
+    #[test]
+    fn transform_pom_text_closure() {
+        let mut pom = POM {
+            group_id: Some("com.example".into()),
+            artifact_id: "example".into(),
+            name: Some("name".into()),
+            description: Some("description".into()),
+            ..Default::default()
+        };
+
+        transform_pom_text(&mut pom, &|text: &str| Some(text.to_uppercase()));
+
+        assert_eq!(Some("NAME".into()), pom.name);
+        assert_eq!(Some("DESCRIPTION".into()), pom.description);
+    }
+}
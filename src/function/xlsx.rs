@@ -0,0 +1,115 @@
+/*
+ * Copyright 2023 sukawasatoru
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Excel workbook that has the sheet of the dependencies and the summary sheet of the licenses.
+
+use crate::prelude::*;
+use rust_xlsxwriter::{Format, Workbook};
+use std::borrow::Cow;
+use std::io::Write;
+
+/// Maximum characters of a cell of Excel.
+const MAX_CELL_CHARS: usize = 32_767;
+
+const TRUNCATED_MARKER: &str = "... (truncated)";
+
+/// Write the workbook that has the "Dependencies" sheet of the `header` and the `records`, and
+/// the "Summary" sheet of the `license_counts`.
+///
+/// The fields are written as strings so that spreadsheets never evaluate them as formulas. The
+/// field that exceeds the limit of a cell of Excel is truncated with "... (truncated)".
+pub fn write_xlsx<W, I>(
+    mut writer: W,
+    header: &[&str],
+    records: I,
+    license_counts: &[(String, usize)],
+) -> Fallible<()>
+where
+    W: Write,
+    I: IntoIterator<Item = Vec<String>>,
+{
+    let bold = Format::new().set_bold();
+    let mut workbook = Workbook::new();
+
+    let sheet = workbook.add_worksheet();
+    sheet.set_name("Dependencies")?;
+    for (col, name) in header.iter().enumerate() {
+        sheet.write_string_with_format(0, col as u16, *name, &bold)?;
+    }
+    for (row, record) in records.into_iter().enumerate() {
+        for (col, field) in record.iter().enumerate() {
+            sheet.write_string(row as u32 + 1, col as u16, truncate_cell(field))?;
+        }
+    }
+
+    let sheet = workbook.add_worksheet();
+    sheet.set_name("Summary")?;
+    sheet.write_string_with_format(0, 0, "License", &bold)?;
+    sheet.write_string_with_format(0, 1, "Artifacts", &bold)?;
+    for (row, (license, count)) in license_counts.iter().enumerate() {
+        sheet.write_string(row as u32 + 1, 0, truncate_cell(license))?;
+        sheet.write_number(row as u32 + 1, 1, *count as f64)?;
+    }
+
+    writer.write_all(&workbook.save_to_buffer()?)?;
+    writer.flush()?;
+
+    Ok(())
+}
+
+fn truncate_cell(field: &str) -> Cow<str> {
+    if field.chars().count() <= MAX_CELL_CHARS {
+        return Cow::Borrowed(field);
+    }
+
+    let mut truncated = field
+        .chars()
+        .take(MAX_CELL_CHARS - TRUNCATED_MARKER.len())
+        .collect::<String>();
+    truncated.push_str(TRUNCATED_MARKER);
+    Cow::Owned(truncated)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn write_xlsx_long_field() {
+        let description = "a".repeat(MAX_CELL_CHARS + 1);
+        let mut actual = vec![];
+        write_xlsx(
+            &mut actual,
+            &["Dependency", "Description"],
+            vec![vec!["androidx.core:core".to_string(), description]],
+            &[("Apache-2.0".to_string(), 1)],
+        )
+        .unwrap();
+
+        assert!(actual.starts_with(b"PK"), "the workbook is the zip archive");
+    }
+
+    #[test]
+    fn truncate_cell_limit() {
+        let field = "あ".repeat(MAX_CELL_CHARS);
+        assert_eq!(field, truncate_cell(&field));
+
+        let field = "あ".repeat(MAX_CELL_CHARS + 1);
+        let actual = truncate_cell(&field);
+        assert_eq!(MAX_CELL_CHARS, actual.chars().count());
+        assert!(actual.ends_with(TRUNCATED_MARKER));
+    }
+}
@@ -14,153 +14,972 @@
  * limitations under the License.
  */
 
-use crate::function::maven::{parse_maven_metadata, parse_pom, POM};
+use crate::function::clearly_defined::parse_definition;
+use crate::function::digest::sha256_hex;
+use crate::function::gradle::parse_module_metadata;
+use crate::function::http_cache::CacheEntry;
+use crate::function::http_client::HttpClient;
+use crate::function::ivy::{ivy_file_url, parse_ivy};
+use crate::function::link::LinkStatus;
+use crate::function::lock::LockEntry;
+use crate::function::maven::{
+    artifact_extension, is_google_maven_artifact, parse_checksum, parse_maven_metadata, parse_pom,
+    validate_maven_metadata, validate_pom, ArtifactFile, DefaultVersionSelector, Dependency,
+    FixedVersionSelector, Relocation, RepositoryConfig, VersionSelector, POM,
+};
+use crate::function::osv::{
+    parse_query_batch, parse_vulnerability, query_batch_request, Vulnerability, QUERY_BATCH_LIMIT,
+};
+use crate::function::outdated::latest_version;
+use crate::function::repo_health::RepositoryStatus;
+use crate::model::{Coordinate, Version, SPDX};
 pub use crate::prelude::*;
+use futures::StreamExt;
+use std::collections::{BTreeSet, HashMap};
+use std::time::Duration;
 
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::Instant;
+#[cfg(target_arch = "wasm32")]
+use web_time::Instant;
+
+#[cfg(feature = "blocking")]
+pub use crate::blocking::{retrieve_maven_lib_blocking, retrieve_maven_libs_blocking};
+
+#[cfg(feature = "blocking")]
+mod blocking;
 pub mod function;
 pub mod model;
 pub mod prelude;
 
-#[tracing::instrument(skip_all)]
-pub async fn retrieve_maven_lib(client: reqwest::Client, dependency_name: &str) -> Fallible<POM> {
-    let repo_root = match dependency_name {
-        data if data.starts_with("androidx") => get_google_maven_repo(),
-        data if data.starts_with("com.google.android") => get_google_maven_repo(),
-        _ => get_maven_central_repo(),
+/// Retrieve the POM from the repository of `repositories` that is selected by the group id.
+#[tracing::instrument(skip_all, fields(coordinate = %coordinate))]
+pub async fn retrieve_maven_lib(
+    client: impl Into<HttpClient>,
+    coordinate: &Coordinate,
+    repositories: &RepositoryConfig,
+) -> Fallible<POM> {
+    retrieve_maven_lib_with_selector(client, coordinate, repositories, &DefaultVersionSelector)
+        .await
+}
+
+/// Same as [retrieve_maven_lib] but the `selector` overrides the version of the POM to retrieve.
+#[tracing::instrument(skip_all, fields(coordinate = %coordinate))]
+pub async fn retrieve_maven_lib_with_selector(
+    client: impl Into<HttpClient>,
+    coordinate: &Coordinate,
+    repositories: &RepositoryConfig,
+    selector: &dyn VersionSelector,
+) -> Fallible<POM> {
+    retrieve_maven_lib_impl(
+        &client.into(),
+        coordinate,
+        repositories.select(coordinate),
+        selector,
+    )
+    .await
+    .map(|(pom, _)| pom)
+}
+
+/// Same as [retrieve_maven_lib] but also returns how the POM was resolved for the lock file.
+#[tracing::instrument(skip_all, fields(coordinate = %coordinate))]
+pub async fn retrieve_maven_lib_with_lock_entry(
+    client: impl Into<HttpClient>,
+    coordinate: &Coordinate,
+    repositories: &RepositoryConfig,
+) -> Fallible<(POM, LockEntry)> {
+    retrieve_maven_lib_with_selector_and_lock_entry(
+        client,
+        coordinate,
+        repositories,
+        &DefaultVersionSelector,
+    )
+    .await
+}
+
+/// Same as [retrieve_maven_lib_with_lock_entry] but the `selector` overrides the version of the
+/// POM to retrieve.
+#[tracing::instrument(skip_all, fields(coordinate = %coordinate))]
+pub async fn retrieve_maven_lib_with_selector_and_lock_entry(
+    client: impl Into<HttpClient>,
+    coordinate: &Coordinate,
+    repositories: &RepositoryConfig,
+    selector: &dyn VersionSelector,
+) -> Fallible<(POM, LockEntry)> {
+    retrieve_maven_lib_impl(
+        &client.into(),
+        coordinate,
+        repositories.select(coordinate),
+        selector,
+    )
+    .await
+}
+
+/// Same as [retrieve_maven_lib] but retrieve from the Maven repository of `repo_url` regardless of
+/// the group id. e.g. `https://repo.example.com/maven2`
+#[tracing::instrument(skip_all, fields(coordinate = %coordinate, repo = repo_url))]
+pub async fn retrieve_maven_lib_from(
+    client: impl Into<HttpClient>,
+    coordinate: &Coordinate,
+    repo_url: &str,
+) -> Fallible<POM> {
+    retrieve_maven_lib_impl(
+        &client.into(),
+        coordinate,
+        repo_url.trim_end_matches('/'),
+        &DefaultVersionSelector,
+    )
+    .await
+    .map(|(pom, _)| pom)
+}
+
+/// Retrieve the Ivy file of the `gradle` layout from the Ivy repository of `repo_url` and map it
+/// into the [POM]. e.g. `https://ivy.example.com/repo`
+///
+/// The version of `coordinate` is required because the Ivy repository has no
+/// maven-metadata.xml.
+#[tracing::instrument(skip_all, fields(coordinate = %coordinate, repo = repo_url))]
+pub async fn retrieve_ivy_lib(
+    client: impl Into<HttpClient>,
+    coordinate: &Coordinate,
+    repo_url: &str,
+) -> Fallible<POM> {
+    retrieve_ivy_lib_impl(&client.into(), coordinate, repo_url.trim_end_matches('/'))
+        .await
+        .map(|(pom, _)| pom)
+}
+
+/// Same as [retrieve_ivy_lib] but also returns how the POM was resolved for the lock file.
+#[tracing::instrument(skip_all, fields(coordinate = %coordinate, repo = repo_url))]
+pub async fn retrieve_ivy_lib_with_lock_entry(
+    client: impl Into<HttpClient>,
+    coordinate: &Coordinate,
+    repo_url: &str,
+) -> Fallible<(POM, LockEntry)> {
+    retrieve_ivy_lib_impl(&client.into(), coordinate, repo_url.trim_end_matches('/')).await
+}
+
+/// List the `<versions>` of the maven-metadata.xml in the published order.
+///
+/// The version of `coordinate` is ignored.
+#[tracing::instrument(skip_all, fields(coordinate = %coordinate))]
+pub async fn list_versions(
+    client: impl Into<HttpClient>,
+    coordinate: &Coordinate,
+    repositories: &RepositoryConfig,
+) -> Fallible<Vec<String>> {
+    let artifact_root_path = format!(
+        "{}/{}",
+        repositories.select(coordinate),
+        split_dependency_name_to_path(coordinate),
+    );
+
+    Ok(retrieve_maven_metadata(&client.into(), &artifact_root_path)
+        .await?
+        .versions)
+}
+
+/// Same as [list_versions] but in ascending order of [Version] such as
+/// `1.0.0-alpha01 < 1.0.0-rc01 < 1.0.0 < 1.0.1`.
+///
+/// The maven-metadata.xml is revalidated with the cache of the `client` if any.
+#[tracing::instrument(skip_all, fields(coordinate = %coordinate))]
+pub async fn retrieve_versions(
+    client: impl Into<HttpClient>,
+    coordinate: &Coordinate,
+    repositories: &RepositoryConfig,
+) -> Fallible<Vec<Version>> {
+    let mut versions = list_versions(client, coordinate, repositories)
+        .await?
+        .into_iter()
+        .map(Version::new)
+        .collect::<Vec<_>>();
+    versions.sort();
+    Ok(versions)
+}
+
+/// Retrieve the highest version of the maven-metadata.xml to compare with the version of
+/// `coordinate`. See [function::outdated::latest_version].
+#[tracing::instrument(skip_all, fields(coordinate = %coordinate))]
+pub async fn retrieve_latest_version(
+    client: impl Into<HttpClient>,
+    coordinate: &Coordinate,
+    repositories: &RepositoryConfig,
+) -> Fallible<Option<String>> {
+    let artifact_root_path = format!(
+        "{}/{}",
+        repositories.select(coordinate),
+        split_dependency_name_to_path(coordinate),
+    );
+
+    let metadata = retrieve_maven_metadata(&client.into(), &artifact_root_path).await?;
+    Ok(latest_version(&metadata, coordinate.version.as_deref()))
+}
+
+/// Retrieve the size and the published SHA-256 of the main artifact (jar/aar).
+///
+/// The version of `coordinate` is preferred and the version of `pom` is used as fallback.
+/// The classifier and the `@ext` of `coordinate` select the artifact other than the main one.
+/// Returns `None` if the `pom` has no main artifact (e.g. `<packaging>pom</packaging>`) or the
+/// artifact is not found in the repository.
+#[tracing::instrument(skip_all, fields(coordinate = %coordinate))]
+pub async fn retrieve_artifact_file(
+    client: impl Into<HttpClient>,
+    coordinate: &Coordinate,
+    repositories: &RepositoryConfig,
+    pom: &POM,
+) -> Fallible<Option<ArtifactFile>> {
+    retrieve_artifact_file_impl(
+        &client.into(),
+        coordinate,
+        pom,
+        repositories.select(coordinate),
+    )
+    .await
+}
+
+/// Retrieve the licenses from the ClearlyDefined definition for the POM that has no licenses.
+///
+/// The version of `coordinate` is preferred and the version of `pom` is used as fallback.
+/// Returns an empty list if ClearlyDefined has no license for the component.
+#[tracing::instrument(skip_all, fields(coordinate = %coordinate))]
+pub async fn retrieve_clearly_defined_licenses(
+    client: impl Into<HttpClient>,
+    coordinate: &Coordinate,
+    pom: &POM,
+) -> Fallible<Vec<SPDX>> {
+    retrieve_clearly_defined_licenses_impl(
+        &client.into(),
+        coordinate,
+        pom,
+        "https://api.clearlydefined.io",
+    )
+    .await
+}
+
+/// Retrieve the POM of the platform artifact such as `kotlinx-coroutines-core-jvm` for the root
+/// artifact of the Kotlin Multiplatform library that has `<packaging>pom</packaging>` and no
+/// licenses.
+///
+/// The `platform` is the suffix of the artifact id such as `jvm` and the version of `pom` is used.
+/// Returns `None` if the `pom` is not the root artifact.
+#[tracing::instrument(skip(client, coordinate, repositories, pom), fields(coordinate = %coordinate))]
+pub async fn retrieve_platform_maven_lib(
+    client: impl Into<HttpClient>,
+    coordinate: &Coordinate,
+    repositories: &RepositoryConfig,
+    pom: &POM,
+    platform: &str,
+) -> Fallible<Option<POM>> {
+    if pom.packaging_or_default() != "pom" || !pom.licenses.is_empty() {
+        return Ok(None);
+    }
+
+    let target = Coordinate::new(
+        coordinate.group_id.clone(),
+        format!("{}-{}", coordinate.artifact_id, platform),
+    );
+    let (pom, _) = match pom.version.clone().or_else(|| coordinate.version.clone()) {
+        Some(version) => {
+            let fixed = FixedVersionSelector(version);
+            retrieve_maven_lib_impl(
+                &client.into(),
+                &target,
+                repositories.select(&target),
+                &fixed,
+            )
+            .await?
+        }
+        None => {
+            retrieve_maven_lib_impl(
+                &client.into(),
+                &target,
+                repositories.select(&target),
+                &DefaultVersionSelector,
+            )
+            .await?
+        }
     };
+    Ok(Some(pom))
+}
 
-    retrieve_maven_lib_impl(client, dependency_name, repo_root).await
+/// Retrieve the vulnerabilities of the `coordinates` from OSV.
+///
+/// The result is in the same order as the `coordinates`. All coordinates must have the version
+/// because OSV returns the vulnerabilities of all versions for the package without the version.
+#[tracing::instrument(skip_all, fields(count = coordinates.len()))]
+pub async fn retrieve_vulnerabilities(
+    client: impl Into<HttpClient>,
+    coordinates: &[Coordinate],
+) -> Fallible<Vec<Vec<Vulnerability>>> {
+    retrieve_vulnerabilities_impl(&client.into(), coordinates, "https://api.osv.dev").await
 }
 
-/// https://maven.google.com/web/index.html
-fn get_google_maven_repo() -> &'static str {
-    #[cfg(not(test))]
-    let repo_root = "https://dl.google.com/android/maven2";
+/// Check the liveness of the `url` such as the license URL.
+///
+/// HEAD is used and GET is used as fallback for the server that doesn't allow HEAD.
+#[tracing::instrument(skip(client))]
+pub async fn check_link(client: impl Into<HttpClient>, url: &str) -> LinkStatus {
+    let client = client.into();
+    let mut res = client.send(client.head(url)).await;
+    if let Ok(data) = &res {
+        if data.status() == 405 {
+            debug!("fall back to GET");
+            res = client.send(client.get(url)).await;
+        }
+    }
+
+    match res {
+        Ok(data) => LinkStatus::from_status(data.status()),
+        Err(e) => {
+            debug!(?e);
+            LinkStatus::Unreachable(e.to_string())
+        }
+    }
+}
 
-    #[cfg(test)]
-    let repo_root = "http://127.0.0.1";
+/// Check that the repository of `repo_url` is reachable with the credentials of the `client` and
+/// resolves the maven-metadata.xml of the `probe` artifact, and returns the latency of the
+/// request.
+///
+/// The maven-metadata.xml is requested without the cache of the `client`.
+#[tracing::instrument(skip(client, probe), fields(probe = %probe))]
+pub async fn check_repository(
+    client: impl Into<HttpClient>,
+    repo_url: &str,
+    probe: &Coordinate,
+) -> (RepositoryStatus, Duration) {
+    let client = client.into();
+    let url = format!(
+        "{}/{}/maven-metadata.xml",
+        repo_url.trim_end_matches('/'),
+        split_dependency_name_to_path(probe),
+    );
+    let started = Instant::now();
+    let res = client
+        .send(
+            client
+                .get(&url)
+                .header("Accept", "application/xml,text/xml"),
+        )
+        .await;
+    let latency = started.elapsed();
 
-    repo_root
+    let status = match res {
+        Ok(res) if (200..300).contains(&res.status()) => match res
+            .error_for_html()
+            .and_then(|res| parse_maven_metadata(&res.text()))
+        {
+            Ok(_) => RepositoryStatus::Healthy,
+            Err(e) => {
+                debug!(?e);
+                RepositoryStatus::Error(format!("{:#}", e))
+            }
+        },
+        Ok(res) => RepositoryStatus::from_status(res.status()),
+        Err(e) => {
+            debug!(?e);
+            RepositoryStatus::Unreachable(format!("{:#}", e))
+        }
+    };
+    (status, latency)
 }
 
-/// https://central.sonatype.com/
-fn get_maven_central_repo() -> &'static str {
-    #[cfg(not(test))]
-    let repo_root = "https://repo1.maven.org/maven2";
+/// Upper limit of the chained relocations to stop the relocation loop.
+const MAX_RELOCATIONS: usize = 4;
+
+/// Retrieve the POM and follow the `<relocation>` of the relocation stub.
+///
+/// https://maven.apache.org/guides/mini/guide-relocation.html
+#[tracing::instrument(name = "resolve", skip_all, fields(coordinate = %coordinate, repo = repo_root))]
+async fn retrieve_maven_lib_impl(
+    client: &HttpClient,
+    coordinate: &Coordinate,
+    repo_root: &str,
+    selector: &dyn VersionSelector,
+) -> Fallible<(POM, LockEntry)> {
+    let (mut pom, mut lock_entry) =
+        retrieve_pom_impl(client, coordinate, repo_root, selector).await?;
+    let mut followed = None::<Relocation>;
+    for _ in 0..MAX_RELOCATIONS {
+        let relocation = match pom.relocation.take() {
+            Some(data) => data,
+            None => {
+                pom.relocation = followed.map(|data| Relocation {
+                    version: Some(lock_entry.version.clone()),
+                    ..data
+                });
+                pom.repository = Some(lock_entry.repository.clone());
+                return Ok((pom, lock_entry));
+            }
+        };
+
+        let source = followed
+            .as_ref()
+            .map_or_else(|| coordinate.module_id(), |data| data.to_string());
+        let target = Coordinate::new(
+            relocation
+                .group_id
+                .clone()
+                .or_else(|| followed.as_ref().and_then(|data| data.group_id.clone()))
+                .unwrap_or_else(|| coordinate.group_id.clone()),
+            relocation
+                .artifact_id
+                .clone()
+                .or_else(|| followed.as_ref().and_then(|data| data.artifact_id.clone()))
+                .unwrap_or_else(|| coordinate.artifact_id.clone()),
+        );
+        warn!(
+            %source,
+            target = %target.module_id(),
+            message = relocation.message.as_deref().unwrap_or_default(),
+            "follow the relocation"
+        );
 
-    #[cfg(test)]
-    let repo_root = "http://127.0.0.1";
+        (pom, lock_entry) = match &relocation.version {
+            Some(version) => {
+                let fixed = FixedVersionSelector(version.clone());
+                retrieve_pom_impl(client, &target, repo_root, &fixed).await?
+            }
+            None => retrieve_pom_impl(client, &target, repo_root, selector).await?,
+        };
+        followed = Some(Relocation {
+            group_id: Some(target.group_id),
+            artifact_id: Some(target.artifact_id),
+            ..relocation
+        });
+    }
 
-    repo_root
+    bail!("too many relocations: {}", coordinate.module_id())
 }
 
 /// https://maven.apache.org/repository/layout.html
-#[tracing::instrument(skip(client, dependency_name))]
-async fn retrieve_maven_lib_impl(
-    client: reqwest::Client,
-    dependency_name: &str,
+async fn retrieve_pom_impl(
+    client: &HttpClient,
+    coordinate: &Coordinate,
     repo_root: &str,
-) -> Fallible<POM> {
+    selector: &dyn VersionSelector,
+) -> Fallible<(POM, LockEntry)> {
     let artifact_root_path = format!(
         "{}/{}",
         repo_root,
-        split_dependency_name_to_path(dependency_name)?,
+        split_dependency_name_to_path(coordinate),
     );
 
-    let artifact_metadata_path = format!("{}/{}", artifact_root_path, "maven-metadata.xml");
+    // the POM of the fixed version is requested with the maven-metadata.xml that is still
+    // required for the file name of the POM.
+    let (maven_metadata, prefetched) = match selector.fixed_version() {
+        Some(version) => {
+            let pom_path = format!(
+                "{}/{}/{}-{}.pom",
+                artifact_root_path, version, coordinate.artifact_id, version,
+            );
+            let (maven_metadata, pom_xml) = futures::join!(
+                retrieve_maven_metadata(client, &artifact_root_path),
+                request_pom(client, &pom_path),
+            );
+            (maven_metadata?, Some((version, pom_xml)))
+        }
+        None => (
+            retrieve_maven_metadata(client, &artifact_root_path).await?,
+            None,
+        ),
+    };
+
+    let version = selector.select(&maven_metadata).with_context(|| {
+        format!(
+            "no version is selected by the selector: {}/maven-metadata.xml",
+            artifact_root_path
+        )
+    })?;
+    // the artifactId of the maven-metadata.xml may differ in case from the directory name.
+    // POM and module don't have the classifier.
+    let file_path_prefix = format!(
+        "{}/{}/{}-{}",
+        artifact_root_path, version, coordinate.artifact_id, version,
+    );
+    let mut pom_path = format!("{}.pom", file_path_prefix);
+    let mut prefetched = prefetched
+        .filter(|(data, _)| *data == version)
+        .map(|(_, data)| data);
+    // the file name of the POM is the artifactId of the maven-metadata.xml and the misbehaving
+    // repositories publish it with the artifactId of the coordinate.
+    let pom_xml = if !maven_metadata.artifact_id.is_empty()
+        && maven_metadata.artifact_id != coordinate.artifact_id
+    {
+        let metadata_pom_path = format!(
+            "{}/{}/{}-{}.pom",
+            artifact_root_path, version, maven_metadata.artifact_id, version,
+        );
+        match request_pom(client, &metadata_pom_path).await? {
+            Some(data) => {
+                pom_path = metadata_pom_path;
+                Some(data)
+            }
+            None => {
+                let pom_xml = match prefetched.take() {
+                    Some(data) => data?,
+                    None => request_pom(client, &pom_path).await?,
+                };
+                if pom_xml.is_some() {
+                    warn!(
+                        %pom_path,
+                        %metadata_pom_path,
+                        "the file name of the POM differs from the artifactId of the maven-metadata.xml"
+                    );
+                }
+                pom_xml
+            }
+        }
+    } else {
+        match prefetched.take() {
+            Some(data) => data?,
+            None => request_pom(client, &pom_path).await?,
+        }
+    };
+    let pom_xml = match pom_xml {
+        Some(data) => data,
+        None => {
+            info!(%pom_path, "fall back to Gradle Module Metadata");
+            let module_path = format!("{}.module", file_path_prefix);
+            let module_json = request_module(client, &module_path).await?;
+            let lock_entry = LockEntry {
+                repository: repo_root.into(),
+                version,
+                pom_url: module_path,
+                sha256: sha256_hex(module_json.as_bytes()),
+            };
+            let pom = parse_module_metadata(&module_json).context("failed to parse module")?;
+            return Ok((pom, lock_entry));
+        }
+    };
+    trace!(%pom_xml);
+
+    if client.validates_xml() {
+        let violations = validate_pom(&pom_xml);
+        if client.is_strict_xml() && !violations.is_empty() {
+            bail!(
+                "pom.xml violates the schema: {}: {}",
+                pom_path,
+                violations.join(", ")
+            );
+        }
+        for violation in violations {
+            warn!(%pom_path, %violation, "pom.xml violates the schema");
+        }
+    }
+
+    let lock_entry = LockEntry {
+        repository: repo_root.into(),
+        version,
+        pom_url: pom_path,
+        sha256: sha256_hex(pom_xml.as_bytes()),
+    };
+    let pom = parse_pom(&pom_xml).context("failed to parse pom.xml")?;
+
+    Ok((pom, lock_entry))
+}
+
+/// https://ant.apache.org/ivy/history/latest-milestone/ivyfile.html
+async fn retrieve_ivy_lib_impl(
+    client: &HttpClient,
+    coordinate: &Coordinate,
+    repo_root: &str,
+) -> Fallible<(POM, LockEntry)> {
+    let version = coordinate
+        .version
+        .clone()
+        .with_context(|| format!("the version is required for Ivy: {}", coordinate))?;
+    let ivy_url = ivy_file_url(repo_root, coordinate, &version);
     let res = client
-        .get(&artifact_metadata_path)
-        .header(reqwest::header::ACCEPT, "application/xml,text/xml")
-        .send()
+        .send(
+            client
+                .get(&ivy_url)
+                .header("Accept", "application/xml,text/xml"),
+        )
         .await
-        .with_context(|| {
-            format!(
-                "failed to request maven-metadata.xml. url: {}",
-                artifact_metadata_path,
-            )
-        })?;
-    let maven_metadata_xml = res
+        .with_context(|| format!("failed to request ivy.xml. url: {}", ivy_url))?;
+    let ivy_xml = res
         .error_for_status()
-        .context("server returned an error for maven-metadata.xml")?
-        .text()
+        .context("server returned an error for ivy.xml")?
+        .error_for_html()?
+        .text();
+    trace!(%ivy_xml);
+
+    let lock_entry = LockEntry {
+        repository: repo_root.into(),
+        version,
+        pom_url: ivy_url,
+        sha256: sha256_hex(ivy_xml.as_bytes()),
+    };
+    let mut pom = parse_ivy(&ivy_xml).context("failed to parse ivy.xml")?;
+    pom.repository = Some(repo_root.into());
+
+    Ok((pom, lock_entry))
+}
+
+/// Returns `None` if the POM is not found.
+async fn request_pom(client: &HttpClient, pom_path: &str) -> Fallible<Option<String>> {
+    let res = client
+        .send(
+            client
+                .get(pom_path)
+                .header("Accept", "application/xml,text/xml"),
+        )
         .await
-        .context("failed to parse response to maven-metadata.xml's string")?;
-    trace!(%maven_metadata_xml);
+        .with_context(|| format!("failed to request pom.xml. url: {}", pom_path))?;
+    if res.status() == 404 {
+        return Ok(None);
+    }
+
+    Ok(Some(
+        res.error_for_status()
+            .context("server returned an error for pom.xml")?
+            .error_for_html()?
+            .text(),
+    ))
+}
+
+async fn request_module(client: &HttpClient, module_path: &str) -> Fallible<String> {
+    let res = client
+        .send(client.get(module_path).header("Accept", "application/json"))
+        .await
+        .with_context(|| format!("failed to request module. url: {}", module_path))?;
+    let module_json = res
+        .error_for_status()
+        .context("server returned an error for module")?
+        .error_for_html()?
+        .text();
+    trace!(%module_json);
+
+    Ok(module_json)
+}
+
+async fn retrieve_maven_metadata(
+    client: &HttpClient,
+    artifact_root_path: &str,
+) -> Fallible<Dependency> {
+    let artifact_metadata_path = format!("{}/{}", artifact_root_path, "maven-metadata.xml");
+    let maven_metadata_xml = match client.shared_metadata(&artifact_metadata_path) {
+        Some(shared) => {
+            if shared.value.initialized() {
+                debug!(%artifact_metadata_path, "share the requested maven-metadata.xml");
+            }
+            shared
+                .value
+                .get_or_try_init(|| async {
+                    if shared.start_attempt() {
+                        debug!(%artifact_metadata_path, "retry the failed maven-metadata.xml");
+                        client.metrics().add_retry();
+                    }
+                    request_maven_metadata(client, &artifact_metadata_path).await
+                })
+                .await?
+                .clone()
+        }
+        None => request_maven_metadata(client, &artifact_metadata_path).await?,
+    };
 
     let maven_metadata =
         parse_maven_metadata(&maven_metadata_xml).context("failed to parse maven-metadata.xml")?;
     debug!(?maven_metadata);
 
-    let pom_path = format!(
-        "{base}/{version}/{artifact}-{version}.pom",
-        base = artifact_root_path,
-        version = maven_metadata
-            .release_version
-            .or(maven_metadata.latest_version)
-            .or_else(|| {
-                info!("use version tag");
-                maven_metadata.version
-            })
-            .with_context(|| format!(
-                "missing release, latest and version: {}",
-                artifact_metadata_path
-            ))?,
-        artifact = maven_metadata.artifact_id,
+    Ok(maven_metadata)
+}
+
+/// Request the maven-metadata.xml with the cache of the `client` if any.
+async fn request_maven_metadata(
+    client: &HttpClient,
+    artifact_metadata_path: &str,
+) -> Fallible<String> {
+    let cached = client
+        .cache()
+        .and_then(|cache| cache.load(artifact_metadata_path));
+    let mut request = client
+        .get(artifact_metadata_path)
+        .header("Accept", "application/xml,text/xml");
+    if let Some(cached) = &cached {
+        if let Some(etag) = &cached.etag {
+            request = request.header("If-None-Match", etag);
+        }
+        if let Some(last_modified) = &cached.last_modified {
+            request = request.header("If-Modified-Since", last_modified);
+        }
+    }
+    let res = client.send(request).await.with_context(|| {
+        format!(
+            "failed to request maven-metadata.xml. url: {}",
+            artifact_metadata_path,
+        )
+    })?;
+    let maven_metadata_xml = match cached {
+        Some(cached) if res.status() == 304 => {
+            debug!(%artifact_metadata_path, "not modified");
+            client.metrics().add_cache_hit();
+            cached.body
+        }
+        _ => {
+            let res = res
+                .error_for_status()
+                .context("server returned an error for maven-metadata.xml")?
+                .error_for_html()?;
+            let etag = res.header("ETag").map(str::to_owned);
+            let last_modified = res.header("Last-Modified").map(str::to_owned);
+            let body = res.text();
+            if let Some(cache) = client
+                .cache()
+                .filter(|_| etag.is_some() || last_modified.is_some())
+            {
+                let entry = CacheEntry {
+                    url: artifact_metadata_path.into(),
+                    etag,
+                    last_modified,
+                    body: body.clone(),
+                };
+                if let Err(e) = cache.store(&entry) {
+                    warn!(?e, "failed to store maven-metadata.xml to the cache");
+                }
+            }
+            body
+        }
+    };
+    trace!(%maven_metadata_xml);
+
+    if client.validates_xml() {
+        let violations = validate_maven_metadata(&maven_metadata_xml);
+        if client.is_strict_xml() && !violations.is_empty() {
+            bail!(
+                "maven-metadata.xml violates the schema: {}: {}",
+                artifact_metadata_path,
+                violations.join(", ")
+            );
+        }
+        for violation in violations {
+            warn!(%artifact_metadata_path, %violation, "maven-metadata.xml violates the schema");
+        }
+    }
+
+    Ok(maven_metadata_xml)
+}
+
+async fn retrieve_artifact_file_impl(
+    client: &HttpClient,
+    coordinate: &Coordinate,
+    pom: &POM,
+    repo_root: &str,
+) -> Fallible<Option<ArtifactFile>> {
+    // `@ext` of the Gradle notation is preferred to `<packaging>`.
+    let packaging = coordinate.packaging.as_deref().or(pom.packaging.as_deref());
+    let extension = match artifact_extension(packaging) {
+        Some(data) => data,
+        None => {
+            debug!("no artifact file for packaging");
+            return Ok(None);
+        }
+    };
+
+    let version = coordinate
+        .version
+        .as_deref()
+        .or(pom.version.as_deref())
+        .with_context(|| format!("missing version: {}", coordinate))?;
+
+    let file_name = artifact_file_name(coordinate, version, extension);
+    let artifact_path = format!(
+        "{}/{}/{}/{}",
+        repo_root,
+        split_dependency_name_to_path(coordinate),
+        version,
+        file_name,
     );
 
     let res = client
-        .get(&pom_path)
-        .header(reqwest::header::ACCEPT, "application/xml,text/xml")
-        .send()
+        .send(client.head(&artifact_path))
         .await
-        .with_context(|| format!("failed to request pom.xml. url: {}", pom_path))?;
-    let pom_xml = res
+        .with_context(|| format!("failed to request artifact. url: {}", artifact_path))?;
+    if res.status() == 404 {
+        // e.g. the artifact that is published with the other classifier or extension.
+        warn!(%artifact_path, "artifact is not found");
+        return Ok(None);
+    }
+    let res = res
         .error_for_status()
-        .context("server returned an error for pom.xml")?
-        .text()
+        .context("server returned an error for artifact")?;
+
+    // use header instead of the length of the body that is empty for HEAD.
+    let size = res
+        .header("Content-Length")
+        .and_then(|data| data.parse().ok());
+
+    let checksum_path = format!("{}.sha256", artifact_path);
+    let res = client
+        .send(client.get(&checksum_path))
         .await
-        .context("failed to parse response to pom.xml's string")?;
-    trace!(%pom_xml);
+        .with_context(|| format!("failed to request sha256. url: {}", checksum_path))?;
+    let sha256 = if res.is_success() {
+        let checksum = res.text();
+        Some(
+            parse_checksum(&checksum)
+                .with_context(|| format!("failed to parse sha256: {}", checksum_path))?,
+        )
+    } else {
+        info!(status = %res.status(), %checksum_path, "sha256 is not published");
+        None
+    };
 
-    parse_pom(&pom_xml).context("failed to parse pom.xml")
+    Ok(Some(ArtifactFile {
+        file_name,
+        size,
+        sha256,
+    }))
 }
 
-fn split_dependency_name_to_path(dependency_name: &str) -> Fallible<String> {
-    let mut segments = dependency_name.split(':');
-    let group_id = segments
-        .next()
-        .expect("unexpected format?")
-        .trim()
-        .replace('.', "/");
-    ensure!(
-        !group_id.is_empty(),
-        "missing group id: {}",
-        dependency_name
-    );
+/// https://api.clearlydefined.io/api-docs/#/definitions/get_definitions__type___provider___namespace___name___revision_
+async fn retrieve_clearly_defined_licenses_impl(
+    client: &HttpClient,
+    coordinate: &Coordinate,
+    pom: &POM,
+    api_root: &str,
+) -> Fallible<Vec<SPDX>> {
+    let version = coordinate
+        .version
+        .as_deref()
+        .or(pom.version.as_deref())
+        .with_context(|| format!("missing version: {}", coordinate))?;
 
-    let artifact_id = segments.next().context("missing artifact id")?.trim();
-    ensure!(
-        !artifact_id.is_empty(),
-        "missing artifact id: {}",
-        dependency_name
+    let provider = if is_google_maven_artifact(coordinate) {
+        "mavengoogle"
+    } else {
+        "mavencentral"
+    };
+
+    let definition_path = format!(
+        "{}/definitions/maven/{}/{}/{}/{}",
+        api_root, provider, coordinate.group_id, coordinate.artifact_id, version,
     );
+    let definition_json = client
+        .send(
+            client
+                .get(&definition_path)
+                .header("Accept", "application/json"),
+        )
+        .await
+        .with_context(|| format!("failed to request definition. url: {}", definition_path))?
+        .error_for_status()
+        .context("server returned an error for definition")?
+        .text();
+    trace!(%definition_json);
+
+    parse_definition(&definition_json).context("failed to parse definition")
+}
+
+async fn retrieve_vulnerabilities_impl(
+    client: &HttpClient,
+    coordinates: &[Coordinate],
+    api_root: &str,
+) -> Fallible<Vec<Vec<Vulnerability>>> {
+    if let Some(coordinate) = coordinates.iter().find(|data| data.version.is_none()) {
+        bail!("missing version: {}", coordinate);
+    }
+
+    let query_batch_path = format!("{}/v1/querybatch", api_root);
+    let mut ids = Vec::with_capacity(coordinates.len());
+    for chunk in coordinates.chunks(QUERY_BATCH_LIMIT) {
+        let purls = chunk.iter().map(Coordinate::to_purl).collect::<Vec<_>>();
+        let query_batch_json = client
+            .send(
+                client
+                    .post(&query_batch_path)
+                    .header("Content-Type", "application/json")
+                    .header("Accept", "application/json")
+                    .body(query_batch_request(&purls)?),
+            )
+            .await
+            .with_context(|| format!("failed to request querybatch. url: {}", query_batch_path))?
+            .error_for_status()
+            .context("server returned an error for querybatch")?
+            .text();
+        trace!(%query_batch_json);
 
-    if segments.next().is_some() {
-        debug!("ignore version of {}", dependency_name);
+        let results = parse_query_batch(&query_batch_json).context("failed to parse querybatch")?;
+        ensure!(
+            results.len() == chunk.len(),
+            "unexpected number of the querybatch results: {}",
+            results.len(),
+        );
+        ids.extend(results);
     }
 
-    Ok(format!("{}/{}", group_id, artifact_id))
+    // the batch endpoint returns only the IDs.
+    let vulnerabilities = futures::stream::iter(ids.iter().flatten().collect::<BTreeSet<_>>())
+        .map(|id| async move {
+            let vulnerability_path = format!("{}/v1/vulns/{}", api_root, id);
+            let vulnerability_json = client
+                .send(
+                    client
+                        .get(&vulnerability_path)
+                        .header("Accept", "application/json"),
+                )
+                .await
+                .with_context(|| {
+                    format!(
+                        "failed to request vulnerability. url: {}",
+                        vulnerability_path
+                    )
+                })?
+                .error_for_status()
+                .context("server returned an error for vulnerability")?
+                .text();
+            trace!(%vulnerability_json);
+
+            let vulnerability = parse_vulnerability(&vulnerability_json)
+                .context("failed to parse vulnerability")?;
+            Ok::<_, anyhow::Error>((id.clone(), vulnerability))
+        })
+        .buffer_unordered(8)
+        .collect::<Vec<_>>()
+        .await
+        .into_iter()
+        .collect::<Fallible<HashMap<_, _>>>()?;
+
+    Ok(ids
+        .iter()
+        .map(|ids| {
+            ids.iter()
+                .filter_map(|id| vulnerabilities.get(id).cloned())
+                .collect()
+        })
+        .collect())
+}
+
+fn split_dependency_name_to_path(coordinate: &Coordinate) -> String {
+    format!(
+        "{}/{}",
+        coordinate.group_id.replace('.', "/"),
+        coordinate.artifact_id,
+    )
+}
+
+/// `{artifact}-{version}[-{classifier}].{extension}`
+///
+/// https://maven.apache.org/repository/layout.html
+fn artifact_file_name(coordinate: &Coordinate, version: &str, extension: &str) -> String {
+    match &coordinate.classifier {
+        Some(classifier) => format!(
+            "{}-{}-{}.{}",
+            coordinate.artifact_id, version, classifier, extension,
+        ),
+        None => format!("{}-{}.{}", coordinate.artifact_id, version, extension),
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::function::mock_server::{acquire_port, PortGuard};
-    use crate::model::SPDX;
+    use crate::function::maven::PomDependency;
+    use crate::function::mock_server::launch_web_server;
     use axum::response::Html;
-    use axum::routing::{get, IntoMakeService, Router};
-    use std::net::SocketAddr;
-    use tokio::task::JoinHandle;
+    use axum::routing::{get, post, Router};
 
     #[tokio::test]
     async fn retrieve_maven_lib_impl_core_ktx_1_12_0() {
@@ -364,14 +1183,18 @@ mod tests {
         .await;
 
         let repo_root = format!("http://127.0.0.1:{}", *port);
-        let actual =
-            retrieve_maven_lib_impl(reqwest::Client::new(), "androidx.core:core-ktx", &repo_root)
-                .await;
+        let actual = retrieve_maven_lib_impl(
+            &reqwest::Client::new().into(),
+            &"androidx.core:core-ktx".parse().unwrap(),
+            &repo_root,
+            &DefaultVersionSelector,
+        )
+        .await;
 
         tx.send(()).unwrap();
         handler.await.unwrap();
 
-        let actual = actual.unwrap();
+        let (actual, lock_entry) = actual.unwrap();
         let expected = POM {
             group_id: Some("androidx.core".into()),
             artifact_id: "core-ktx".into(),
@@ -379,14 +1202,60 @@ mod tests {
             packaging: Some("aar".into()),
             name: Some("Core Kotlin Extensions".into()),
             description: Some("Kotlin extensions for 'core' artifact".into()),
+            url: Some("https://developer.android.com/jetpack/androidx/releases/core#1.12.0".into()),
+            inception_year: Some("2018".into()),
             licenses: vec![SPDX::Apache20],
+            license_urls: vec!["http://www.apache.org/licenses/LICENSE-2.0.txt".into()],
+            scm_url: Some("https://cs.android.com/androidx/platform/frameworks/support".into()),
+            repository: Some(repo_root.clone()),
+            dependencies: vec![
+                PomDependency {
+                    group_id: "androidx.annotation".into(),
+                    artifact_id: "annotation".into(),
+                    version: Some("1.1.0".into()),
+                    scope: Some("compile".into()),
+                    optional: false,
+                },
+                PomDependency {
+                    group_id: "androidx.core".into(),
+                    artifact_id: "core".into(),
+                    version: Some("1.12.0".into()),
+                    scope: Some("compile".into()),
+                    optional: false,
+                },
+                PomDependency {
+                    group_id: "org.jetbrains.kotlin".into(),
+                    artifact_id: "kotlin-stdlib".into(),
+                    version: Some("1.8.22".into()),
+                    scope: Some("compile".into()),
+                    optional: false,
+                },
+            ],
+            dependency_management: vec![PomDependency {
+                group_id: "androidx.core".into(),
+                artifact_id: "core".into(),
+                version: Some("1.12.0".into()),
+                scope: None,
+                optional: false,
+            }],
+            ..Default::default()
         };
 
         assert_eq!(expected, actual);
+        assert_eq!(repo_root, lock_entry.repository);
+        assert_eq!("1.12.0", lock_entry.version);
+        assert_eq!(
+            format!(
+                "{}/androidx/core/core-ktx/1.12.0/core-ktx-1.12.0.pom",
+                repo_root
+            ),
+            lock_entry.pom_url
+        );
+        assert_eq!(64, lock_entry.sha256.len());
     }
 
     #[tokio::test]
-    async fn retrieve_maven_lib_impl_glide_4_16_0() {
+    async fn retrieve_maven_lib_glide_4_16_0() {
         async fn get_maven_metadata() -> Html<&'static str> {
             Html(
                 r#"<?xml version="1.0" encoding="UTF-8"?>
@@ -543,10 +1412,10 @@ mod tests {
         .await;
 
         let repo_root = format!("http://127.0.0.1:{}", *port);
-        let actual = retrieve_maven_lib_impl(
+        let actual = retrieve_maven_lib(
             reqwest::Client::new(),
-            "com.github.bumptech.glide:glide",
-            &repo_root,
+            &"com.github.bumptech.glide:glide".parse().unwrap(),
+            &RepositoryConfig::single(repo_root.clone()),
         )
         .await;
 
@@ -561,65 +1430,1434 @@ mod tests {
             packaging: Some("aar".into()),
             name: Some("Glide".into()),
             description: Some("A fast and efficient image loading library for Android focused on smooth scrolling.".into()),
+            url: Some("https://github.com/bumptech/glide".into()),
             licenses: vec![SPDX::BSD2, SPDX::Apache20],
+            license_urls: vec![
+                "http://www.opensource.org/licenses/bsd-license".into(),
+                "http://www.apache.org/licenses/LICENSE-2.0.txt".into(),
+            ],
+            scm_url: Some("https://github.com/bumptech/glide".into()),
+            repository: Some(repo_root),
+            dependencies: vec![
+                PomDependency {
+                    group_id: "com.github.bumptech.glide".into(),
+                    artifact_id: "gifdecoder".into(),
+                    version: Some("4.16.0".into()),
+                    scope: Some("compile".into()),
+                    optional: false,
+                },
+                PomDependency {
+                    group_id: "com.github.bumptech.glide".into(),
+                    artifact_id: "disklrucache".into(),
+                    version: Some("4.16.0".into()),
+                    scope: Some("compile".into()),
+                    optional: false,
+                },
+                PomDependency {
+                    group_id: "com.github.bumptech.glide".into(),
+                    artifact_id: "annotations".into(),
+                    version: Some("4.16.0".into()),
+                    scope: Some("compile".into()),
+                    optional: false,
+                },
+                PomDependency {
+                    group_id: "androidx.fragment".into(),
+                    artifact_id: "fragment".into(),
+                    version: Some("1.3.6".into()),
+                    scope: Some("compile".into()),
+                    optional: false,
+                },
+                PomDependency {
+                    group_id: "androidx.vectordrawable".into(),
+                    artifact_id: "vectordrawable-animated".into(),
+                    version: Some("1.1.0".into()),
+                    scope: Some("compile".into()),
+                    optional: false,
+                },
+                PomDependency {
+                    group_id: "androidx.exifinterface".into(),
+                    artifact_id: "exifinterface".into(),
+                    version: Some("1.3.6".into()),
+                    scope: Some("compile".into()),
+                    optional: false,
+                },
+                PomDependency {
+                    group_id: "androidx.tracing".into(),
+                    artifact_id: "tracing".into(),
+                    version: Some("1.0.0".into()),
+                    scope: Some("compile".into()),
+                    optional: false,
+                },
+            ],
+            ..Default::default()
         };
 
         assert_eq!(expected, actual);
     }
 
-    #[test]
-    fn split_dependency_name_to_path_core_ktx() {
-        let source = "androidx.core:core-ktx";
-        let expected = "androidx/core/core-ktx";
+    #[tokio::test]
+    async fn retrieve_maven_lib_gradle_plugin_marker() {
+        async fn get_maven_metadata() -> Html<&'static str> {
+            Html(
+                r#"<?xml version="1.0" encoding="UTF-8"?>
+<metadata>
+  <groupId>org.jetbrains.kotlin.android</groupId>
+  <artifactId>org.jetbrains.kotlin.android.gradle.plugin</artifactId>
+  <versioning>
+    <latest>1.9.20</latest>
+    <release>1.9.20</release>
+    <versions>
+      <version>1.9.20</version>
+    </versions>
+  </versioning>
+</metadata>
+"#,
+            )
+        }
 
-        let actual = split_dependency_name_to_path(source).unwrap();
-        assert_eq!(expected, actual);
-    }
+        async fn get_pom() -> Html<&'static str> {
+            Html(
+                r#"<?xml version="1.0" encoding="UTF-8"?>
+<project xmlns="http://maven.apache.org/POM/4.0.0">
+  <modelVersion>4.0.0</modelVersion>
+  <groupId>org.jetbrains.kotlin.android</groupId>
+  <artifactId>org.jetbrains.kotlin.android.gradle.plugin</artifactId>
+  <version>1.9.20</version>
+  <packaging>pom</packaging>
+  <name>Kotlin Android Gradle Plugin</name>
+  <licenses>
+    <license>
+      <name>The Apache License, Version 2.0</name>
+      <url>http://www.apache.org/licenses/LICENSE-2.0.txt</url>
+    </license>
+  </licenses>
+  <dependencies>
+    <dependency>
+      <groupId>org.jetbrains.kotlin</groupId>
+      <artifactId>kotlin-gradle-plugin</artifactId>
+      <version>1.9.20</version>
+    </dependency>
+  </dependencies>
+</project>
+"#,
+            )
+        }
 
-    #[test]
-    fn split_dependency_name_to_path_core_ktx_version() {
-        let source = "androidx.core:core-ktx:1.1.0";
-        let expected = "androidx/core/core-ktx";
+        let (handler, tx, port) = launch_web_server(
+            Router::new()
+                .route(
+                    "/m2/org/jetbrains/kotlin/android/org.jetbrains.kotlin.android.gradle.plugin/maven-metadata.xml",
+                    get(get_maven_metadata),
+                )
+                .route(
+                    "/m2/org/jetbrains/kotlin/android/org.jetbrains.kotlin.android.gradle.plugin/1.9.20/org.jetbrains.kotlin.android.gradle.plugin-1.9.20.pom",
+                    get(get_pom),
+                )
+                .into_make_service(),
+        )
+        .await;
 
-        let actual = split_dependency_name_to_path(source).unwrap();
-        assert_eq!(expected, actual);
-    }
+        let repo_root = format!("http://127.0.0.1:{}", *port);
+        let repositories = RepositoryConfig {
+            google_maven: format!("{}/google", repo_root),
+            maven_central: format!("{}/central", repo_root),
+            gradle_plugin_portal: format!("{}/m2", repo_root),
+        };
+        let actual = retrieve_maven_lib(
+            reqwest::Client::new(),
+            &"org.jetbrains.kotlin.android:org.jetbrains.kotlin.android.gradle.plugin"
+                .parse()
+                .unwrap(),
+            &repositories,
+        )
+        .await;
 
-    #[test]
-    fn split_dependency_name_to_path_javax_inject() {
-        let source = "javax.inject:javax.inject";
-        let expected = "javax/inject/javax.inject";
+        tx.send(()).unwrap();
+        handler.await.unwrap();
 
-        let actual = split_dependency_name_to_path(source).unwrap();
-        assert_eq!(expected, actual);
+        let actual = actual.unwrap();
+        assert_eq!(Some("1.9.20".into()), actual.version);
+        assert_eq!(vec![SPDX::Apache20], actual.licenses);
+        assert_eq!(Some(format!("{}/m2", repo_root)), actual.repository);
+        assert_eq!(
+            "org.jetbrains.kotlin:kotlin-gradle-plugin:1.9.20",
+            actual.dependencies[0].coordinate().to_string()
+        );
     }
 
-    #[test]
-    fn split_dependency_name_to_path_unexpected_format() {
-        let actual = split_dependency_name_to_path("aaa");
-        assert!(actual.is_err());
-    }
-
-    async fn launch_web_server(
-        make_service: IntoMakeService<Router>,
-    ) -> (JoinHandle<()>, tokio::sync::oneshot::Sender<()>, PortGuard) {
-        let (tx, rx) = tokio::sync::oneshot::channel::<()>();
-
-        let port_guard = acquire_port().await;
-        let port = *port_guard;
-        let handler = tokio::task::spawn(async move {
-            axum::Server::bind(&SocketAddr::from(([127, 0, 0, 1], port)))
-                .serve(make_service)
-                .with_graceful_shutdown(async {
-                    rx.await.ok();
-                })
-                .await
-                .unwrap();
-        });
+    #[tokio::test]
+    async fn retrieve_maven_lib_impl_fall_back_to_module() {
+        async fn get_maven_metadata() -> Html<&'static str> {
+            Html(
+                r#"<?xml version='1.0' encoding='UTF-8'?>
+<metadata>
+  <groupId>com.example</groupId>
+  <artifactId>gradle-only</artifactId>
+  <versioning>
+    <latest>1.0.0</latest>
+    <release>1.0.0</release>
+    <versions>
+      <version>1.0.0</version>
+    </versions>
+    <lastUpdated>20230904154022</lastUpdated>
+  </versioning>
+</metadata>
+"#,
+            )
+        }
+
+        async fn get_module() -> &'static str {
+            r#"{
+  "formatVersion": "1.1",
+  "component": {
+    "group": "com.example",
+    "module": "gradle-only",
+    "version": "1.0.0"
+  },
+  "variants": [
+    {
+      "name": "apiElements",
+      "files": [
+        {
+          "name": "gradle-only-1.0.0.jar",
+          "url": "gradle-only-1.0.0.jar"
+        }
+      ]
+    }
+  ]
+}"#
+        }
+
+        let (handler, tx, port) = launch_web_server(
+            Router::new()
+                .route(
+                    "/com/example/gradle-only/maven-metadata.xml",
+                    get(get_maven_metadata),
+                )
+                .route(
+                    "/com/example/gradle-only/1.0.0/gradle-only-1.0.0.module",
+                    get(get_module),
+                )
+                .into_make_service(),
+        )
+        .await;
+
+        let repo_root = format!("http://127.0.0.1:{}", *port);
+        let actual = retrieve_maven_lib_from(
+            reqwest::Client::new(),
+            &"com.example:gradle-only".parse().unwrap(),
+            &format!("{}/", repo_root),
+        )
+        .await;
+
+        tx.send(()).unwrap();
+        handler.await.unwrap();
+
+        let actual = actual.unwrap();
+        assert_eq!("gradle-only", actual.artifact_id);
+        assert_eq!(Some("1.0.0".into()), actual.version);
+        assert_eq!(Some("jar".into()), actual.packaging);
+        assert!(actual.licenses.is_empty());
+    }
+
+    #[tokio::test]
+    async fn retrieve_maven_lib_impl_strict_xml() {
+        async fn get_maven_metadata() -> Html<&'static str> {
+            Html(
+                r#"<?xml version='1.0' encoding='UTF-8'?>
+<metadata>
+  <groupId>com.example</groupId>
+  <artifactId></artifactId>
+  <versioning>
+    <release>1.0.0</release>
+    <versions/>
+  </versioning>
+</metadata>
+"#,
+            )
+        }
+
+        let (handler, tx, port) = launch_web_server(
+            Router::new()
+                .route(
+                    "/com/example/broken/maven-metadata.xml",
+                    get(get_maven_metadata),
+                )
+                .into_make_service(),
+        )
+        .await;
+
+        let repo_root = format!("http://127.0.0.1:{}", *port);
+        let actual = retrieve_maven_lib_from(
+            HttpClient::new(reqwest::Client::new()).with_strict_xml(true),
+            &"com.example:broken".parse().unwrap(),
+            &format!("{}/", repo_root),
+        )
+        .await;
+
+        tx.send(()).unwrap();
+        handler.await.unwrap();
+
+        let actual = format!("{:#}", actual.unwrap_err());
+        assert!(
+            actual.contains(&format!(
+                "maven-metadata.xml violates the schema: {}/com/example/broken/maven-metadata.xml: empty <artifactId>, empty <versions>, <release> 1.0.0 is not listed in <versions>",
+                repo_root
+            )),
+            "{}",
+            actual
+        );
+    }
+
+    #[tokio::test]
+    async fn retrieve_maven_lib_impl_html_error_page() {
+        async fn get_login_page() -> Html<&'static str> {
+            Html(
+                r#"<!DOCTYPE html>
+<html>
+  <head><title>Sign in to the proxy</title></head>
+  <body><form action="/login"></form></body>
+</html>
+"#,
+            )
+        }
+
+        let (handler, tx, port) = launch_web_server(
+            Router::new()
+                .route(
+                    "/com/example/behind-proxy/maven-metadata.xml",
+                    get(get_login_page),
+                )
+                .into_make_service(),
+        )
+        .await;
+
+        let repo_root = format!("http://127.0.0.1:{}", *port);
+        let actual = retrieve_maven_lib_from(
+            reqwest::Client::new(),
+            &"com.example:behind-proxy".parse().unwrap(),
+            &format!("{}/", repo_root),
+        )
+        .await;
+
+        tx.send(()).unwrap();
+        handler.await.unwrap();
+
+        let actual = format!("{:#}", actual.unwrap_err());
+        assert!(
+            actual.contains(&format!(
+                "HTML page is returned instead of the content for url ({}/com/example/behind-proxy/maven-metadata.xml)",
+                repo_root
+            )),
+            "{}",
+            actual
+        );
+        assert!(
+            actual.contains("<title>Sign in to the proxy</title>"),
+            "{}",
+            actual
+        );
+    }
+
+    #[tokio::test]
+    async fn retrieve_maven_lib_impl_pom_file_name_of_maven_metadata() {
+        async fn get_maven_metadata() -> Html<&'static str> {
+            Html(
+                r#"<?xml version='1.0' encoding='UTF-8'?>
+<metadata>
+  <groupId>com.example</groupId>
+  <artifactId>LegacyLib</artifactId>
+  <versioning>
+    <release>1.0.0</release>
+    <versions>
+      <version>1.0.0</version>
+    </versions>
+  </versioning>
+</metadata>
+"#,
+            )
+        }
+
+        async fn get_pom() -> Html<&'static str> {
+            Html(
+                r#"<?xml version="1.0" encoding="UTF-8"?>
+<project>
+  <modelVersion>4.0.0</modelVersion>
+  <groupId>com.example</groupId>
+  <artifactId>LegacyLib</artifactId>
+  <version>1.0.0</version>
+  <licenses>
+    <license>
+      <name>MIT License</name>
+    </license>
+  </licenses>
+</project>
+"#,
+            )
+        }
+
+        let (handler, tx, port) = launch_web_server(
+            Router::new()
+                .route(
+                    "/com/example/legacylib/maven-metadata.xml",
+                    get(get_maven_metadata),
+                )
+                .route(
+                    "/com/example/legacylib/1.0.0/LegacyLib-1.0.0.pom",
+                    get(get_pom),
+                )
+                .into_make_service(),
+        )
+        .await;
+
+        let repo_root = format!("http://127.0.0.1:{}", *port);
+        let actual = retrieve_maven_lib_impl(
+            &reqwest::Client::new().into(),
+            &"com.example:legacylib".parse().unwrap(),
+            &repo_root,
+            &DefaultVersionSelector,
+        )
+        .await;
+
+        tx.send(()).unwrap();
+        handler.await.unwrap();
+
+        let (actual, lock_entry) = actual.unwrap();
+        assert_eq!("LegacyLib", actual.artifact_id);
+        assert_eq!(
+            format!(
+                "{}/com/example/legacylib/1.0.0/LegacyLib-1.0.0.pom",
+                repo_root
+            ),
+            lock_entry.pom_url
+        );
+        assert_eq!(vec![SPDX::MIT], actual.licenses);
+    }
+
+    #[tokio::test]
+    async fn retrieve_maven_lib_impl_pom_file_name_of_coordinate() {
+        async fn get_maven_metadata() -> Html<&'static str> {
+            Html(
+                r#"<?xml version='1.0' encoding='UTF-8'?>
+<metadata>
+  <groupId>com.example</groupId>
+  <artifactId>LegacyLib</artifactId>
+  <versioning>
+    <release>1.0.0</release>
+    <versions>
+      <version>1.0.0</version>
+    </versions>
+  </versioning>
+</metadata>
+"#,
+            )
+        }
+
+        async fn get_pom() -> Html<&'static str> {
+            Html(
+                r#"<?xml version="1.0" encoding="UTF-8"?>
+<project>
+  <modelVersion>4.0.0</modelVersion>
+  <groupId>com.example</groupId>
+  <artifactId>legacylib</artifactId>
+  <version>1.0.0</version>
+</project>
+"#,
+            )
+        }
+
+        let (handler, tx, port) = launch_web_server(
+            Router::new()
+                .route(
+                    "/com/example/legacylib/maven-metadata.xml",
+                    get(get_maven_metadata),
+                )
+                .route(
+                    "/com/example/legacylib/1.0.0/legacylib-1.0.0.pom",
+                    get(get_pom),
+                )
+                .into_make_service(),
+        )
+        .await;
+
+        let repo_root = format!("http://127.0.0.1:{}", *port);
+        let actual = retrieve_maven_lib_impl(
+            &reqwest::Client::new().into(),
+            &"com.example:legacylib".parse().unwrap(),
+            &repo_root,
+            &DefaultVersionSelector,
+        )
+        .await;
+
+        tx.send(()).unwrap();
+        handler.await.unwrap();
+
+        let (actual, lock_entry) = actual.unwrap();
+        assert_eq!("legacylib", actual.artifact_id);
+        assert_eq!(
+            format!(
+                "{}/com/example/legacylib/1.0.0/legacylib-1.0.0.pom",
+                repo_root
+            ),
+            lock_entry.pom_url
+        );
+    }
+
+    #[tokio::test]
+    async fn retrieve_maven_lib_impl_relocation() {
+        async fn get_stub_maven_metadata() -> Html<&'static str> {
+            Html(
+                r#"<?xml version='1.0' encoding='UTF-8'?>
+<metadata>
+  <groupId>mysql</groupId>
+  <artifactId>mysql-connector-java</artifactId>
+  <versioning>
+    <release>8.0.33</release>
+    <versions>
+      <version>8.0.33</version>
+    </versions>
+  </versioning>
+</metadata>
+"#,
+            )
+        }
+
+        async fn get_stub_pom() -> Html<&'static str> {
+            Html(
+                r#"<?xml version="1.0" encoding="UTF-8"?>
+<project>
+  <modelVersion>4.0.0</modelVersion>
+  <groupId>mysql</groupId>
+  <artifactId>mysql-connector-java</artifactId>
+  <version>8.0.33</version>
+  <distributionManagement>
+    <relocation>
+      <groupId>com.mysql</groupId>
+      <artifactId>mysql-connector-j</artifactId>
+      <message>MySQL Connector/J artifacts moved to reverse-DNS compliant Maven 2+ coordinates.</message>
+    </relocation>
+  </distributionManagement>
+</project>
+"#,
+            )
+        }
+
+        async fn get_maven_metadata() -> Html<&'static str> {
+            Html(
+                r#"<?xml version='1.0' encoding='UTF-8'?>
+<metadata>
+  <groupId>com.mysql</groupId>
+  <artifactId>mysql-connector-j</artifactId>
+  <versioning>
+    <release>8.2.0</release>
+    <versions>
+      <version>8.0.33</version>
+      <version>8.2.0</version>
+    </versions>
+  </versioning>
+</metadata>
+"#,
+            )
+        }
+
+        async fn get_pom() -> Html<&'static str> {
+            Html(
+                r#"<?xml version="1.0" encoding="UTF-8"?>
+<project>
+  <modelVersion>4.0.0</modelVersion>
+  <groupId>com.mysql</groupId>
+  <artifactId>mysql-connector-j</artifactId>
+  <version>8.2.0</version>
+  <name>MySQL Connector/J</name>
+  <licenses>
+    <license>
+      <name>The GNU General Public License, v2 with Universal FOSS Exception, v1.0</name>
+    </license>
+  </licenses>
+</project>
+"#,
+            )
+        }
+
+        let (handler, tx, port) = launch_web_server(
+            Router::new()
+                .route(
+                    "/mysql/mysql-connector-java/maven-metadata.xml",
+                    get(get_stub_maven_metadata),
+                )
+                .route(
+                    "/mysql/mysql-connector-java/8.0.33/mysql-connector-java-8.0.33.pom",
+                    get(get_stub_pom),
+                )
+                .route(
+                    "/com/mysql/mysql-connector-j/maven-metadata.xml",
+                    get(get_maven_metadata),
+                )
+                .route(
+                    "/com/mysql/mysql-connector-j/8.2.0/mysql-connector-j-8.2.0.pom",
+                    get(get_pom),
+                )
+                .into_make_service(),
+        )
+        .await;
+
+        let repo_root = format!("http://127.0.0.1:{}", *port);
+        let actual = retrieve_maven_lib_impl(
+            &reqwest::Client::new().into(),
+            &"mysql:mysql-connector-java".parse().unwrap(),
+            &repo_root,
+            &DefaultVersionSelector,
+        )
+        .await;
+
+        tx.send(()).unwrap();
+        handler.await.unwrap();
+
+        let (actual, lock_entry) = actual.unwrap();
+        assert_eq!(Some("MySQL Connector/J".into()), actual.name);
+        assert_eq!(
+            "com.mysql:mysql-connector-j:8.2.0",
+            actual.relocation.unwrap().to_string()
+        );
+        assert_eq!("8.2.0", lock_entry.version);
+    }
+
+    #[tokio::test]
+    async fn retrieve_ivy_lib_internal_lib() {
+        async fn get_ivy() -> Html<&'static str> {
+            Html(
+                r#"<?xml version="1.0" encoding="UTF-8"?>
+<ivy-module version="2.0">
+  <info organisation="com.example" module="internal-lib" revision="1.2.0">
+    <license name="MIT License" url="https://opensource.org/licenses/MIT"/>
+  </info>
+</ivy-module>
+"#,
+            )
+        }
+
+        let (handler, tx, port) = launch_web_server(
+            Router::new()
+                .route(
+                    "/com.example/internal-lib/1.2.0/ivy-1.2.0.xml",
+                    get(get_ivy),
+                )
+                .into_make_service(),
+        )
+        .await;
+
+        let repo_root = format!("http://127.0.0.1:{}", *port);
+        let actual = retrieve_ivy_lib_with_lock_entry(
+            reqwest::Client::new(),
+            &"com.example:internal-lib:1.2.0".parse().unwrap(),
+            &repo_root,
+        )
+        .await;
+        let missing_version = retrieve_ivy_lib(
+            reqwest::Client::new(),
+            &"com.example:internal-lib".parse().unwrap(),
+            &repo_root,
+        )
+        .await;
+
+        tx.send(()).unwrap();
+        handler.await.unwrap();
+
+        let (pom, lock_entry) = actual.unwrap();
+        assert_eq!("internal-lib", pom.artifact_id);
+        assert_eq!(vec![SPDX::MIT], pom.licenses);
+        assert_eq!(Some(repo_root.clone()), pom.repository);
+        assert_eq!("1.2.0", lock_entry.version);
+        assert_eq!(
+            format!("{}/com.example/internal-lib/1.2.0/ivy-1.2.0.xml", repo_root),
+            lock_entry.pom_url,
+        );
+        assert!(missing_version.is_err());
+    }
+
+    #[tokio::test]
+    async fn retrieve_artifact_file_impl_glide_4_16_0() {
+        async fn get_aar() -> &'static str {
+            "dummy aar"
+        }
+
+        async fn get_sha256() -> &'static str {
+            "45ae4a0b6c3b9b8d3e4a4b0f2ab9e3a3a8b8b0cfc8e2c1de1d0b2d1e8f7c6a5b"
+        }
+
+        let (handler, tx, port) = launch_web_server(
+            Router::new()
+                .route(
+                    "/com/github/bumptech/glide/glide/4.15.1/glide-4.15.1.aar",
+                    get(get_aar),
+                )
+                .route(
+                    "/com/github/bumptech/glide/glide/4.15.1/glide-4.15.1.aar.sha256",
+                    get(get_sha256),
+                )
+                .into_make_service(),
+        )
+        .await;
+
+        let pom = POM {
+            group_id: Some("com.github.bumptech.glide".into()),
+            artifact_id: "glide".into(),
+            version: Some("4.16.0".into()),
+            packaging: Some("aar".into()),
+            name: Some("Glide".into()),
+            ..Default::default()
+        };
+
+        let repo_root = format!("http://127.0.0.1:{}", *port);
+        let actual = retrieve_artifact_file_impl(
+            &reqwest::Client::new().into(),
+            &"com.github.bumptech.glide:glide:4.15.1".parse().unwrap(),
+            &pom,
+            &repo_root,
+        )
+        .await;
+
+        tx.send(()).unwrap();
+        handler.await.unwrap();
+
+        let expected = ArtifactFile {
+            file_name: "glide-4.15.1.aar".into(),
+            size: Some(9),
+            sha256: Some("45ae4a0b6c3b9b8d3e4a4b0f2ab9e3a3a8b8b0cfc8e2c1de1d0b2d1e8f7c6a5b".into()),
+        };
+
+        assert_eq!(Some(expected), actual.unwrap());
+    }
+
+    #[tokio::test]
+    async fn retrieve_artifact_file_impl_classifier_packaging() {
+        async fn get_jar() -> &'static str {
+            "dummy natives"
+        }
+
+        let (handler, tx, port) = launch_web_server(
+            Router::new()
+                .route(
+                    "/org/lwjgl/lwjgl/3.3.3/lwjgl-3.3.3-natives-linux.jar",
+                    get(get_jar),
+                )
+                .into_make_service(),
+        )
+        .await;
+
+        let pom = POM {
+            group_id: Some("org.lwjgl".into()),
+            artifact_id: "lwjgl".into(),
+            version: Some("3.3.3".into()),
+            name: Some("LWJGL".into()),
+            ..Default::default()
+        };
+
+        let repo_root = format!("http://127.0.0.1:{}", *port);
+        let actual = retrieve_artifact_file_impl(
+            &reqwest::Client::new().into(),
+            &"org.lwjgl:lwjgl:3.3.3:natives-linux@jar".parse().unwrap(),
+            &pom,
+            &repo_root,
+        )
+        .await;
+
+        tx.send(()).unwrap();
+        handler.await.unwrap();
+
+        let expected = ArtifactFile {
+            file_name: "lwjgl-3.3.3-natives-linux.jar".into(),
+            size: Some(13),
+            sha256: None,
+        };
+
+        assert_eq!(Some(expected), actual.unwrap());
+    }
+
+    #[tokio::test]
+    async fn retrieve_artifact_file_impl_not_found() {
+        let (handler, tx, port) = launch_web_server(Router::new().into_make_service()).await;
+
+        let pom = POM {
+            group_id: Some("org.lwjgl".into()),
+            artifact_id: "lwjgl".into(),
+            version: Some("3.3.3".into()),
+            name: Some("LWJGL".into()),
+            ..Default::default()
+        };
+
+        let repo_root = format!("http://127.0.0.1:{}", *port);
+        let actual = retrieve_artifact_file_impl(
+            &reqwest::Client::new().into(),
+            &"org.lwjgl:lwjgl:3.3.3".parse().unwrap(),
+            &pom,
+            &repo_root,
+        )
+        .await;
+
+        tx.send(()).unwrap();
+        handler.await.unwrap();
+
+        assert_eq!(None, actual.unwrap());
+    }
+
+    #[tokio::test]
+    async fn retrieve_clearly_defined_licenses_impl_javax_inject() {
+        async fn get_definition() -> &'static str {
+            r#"{"licensed": {"declared": "Apache-2.0"}}"#
+        }
+
+        let (handler, tx, port) = launch_web_server(
+            Router::new()
+                .route(
+                    "/definitions/maven/mavencentral/javax.inject/javax.inject/1",
+                    get(get_definition),
+                )
+                .into_make_service(),
+        )
+        .await;
+
+        let pom = POM {
+            group_id: Some("javax.inject".into()),
+            artifact_id: "javax.inject".into(),
+            version: Some("1".into()),
+            packaging: Some("jar".into()),
+            name: Some("javax.inject".into()),
+            ..Default::default()
+        };
+
+        let api_root = format!("http://127.0.0.1:{}", *port);
+        let actual = retrieve_clearly_defined_licenses_impl(
+            &reqwest::Client::new().into(),
+            &"javax.inject:javax.inject".parse().unwrap(),
+            &pom,
+            &api_root,
+        )
+        .await;
+
+        tx.send(()).unwrap();
+        handler.await.unwrap();
+
+        assert_eq!(vec![SPDX::Apache20], actual.unwrap());
+    }
+
+    #[tokio::test]
+    async fn retrieve_vulnerabilities_impl_guava() {
+        async fn post_query_batch(body: String) -> &'static str {
+            assert_eq!(
+                r#"{"queries":[{"package":{"purl":"pkg:maven/com.google.guava/guava@31.1-jre"}},{"package":{"purl":"pkg:maven/androidx.core/core-ktx@1.12.0"}}]}"#,
+                body,
+            );
+            r#"{"results":[{"vulns":[{"id":"GHSA-7g45-4rm6-3mm3"}]},{}]}"#
+        }
+
+        async fn get_vulnerability() -> &'static str {
+            r#"{"id":"GHSA-7g45-4rm6-3mm3","database_specific":{"severity":"MODERATE"}}"#
+        }
+
+        let (handler, tx, port) = launch_web_server(
+            Router::new()
+                .route("/v1/querybatch", post(post_query_batch))
+                .route("/v1/vulns/GHSA-7g45-4rm6-3mm3", get(get_vulnerability))
+                .into_make_service(),
+        )
+        .await;
+
+        let api_root = format!("http://127.0.0.1:{}", *port);
+        let actual = retrieve_vulnerabilities_impl(
+            &reqwest::Client::new().into(),
+            &[
+                "com.google.guava:guava:31.1-jre".parse().unwrap(),
+                "androidx.core:core-ktx:1.12.0".parse().unwrap(),
+            ],
+            &api_root,
+        )
+        .await;
+
+        tx.send(()).unwrap();
+        handler.await.unwrap();
+
+        let expected = vec![
+            vec![Vulnerability {
+                id: "GHSA-7g45-4rm6-3mm3".into(),
+                severity: Some("MODERATE".into()),
+            }],
+            vec![],
+        ];
+        assert_eq!(expected, actual.unwrap());
+    }
+
+    #[tokio::test]
+    async fn list_versions_not_modified() {
+        use crate::function::http_cache::HttpCache;
+        use axum::http::{header, HeaderMap, StatusCode};
+        use axum::response::{IntoResponse, Response};
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        static FULL_RESPONSES: AtomicUsize = AtomicUsize::new(0);
+
+        async fn get_maven_metadata(headers: HeaderMap) -> Response {
+            if headers
+                .get(header::IF_NONE_MATCH)
+                .map_or(false, |data| data == "\"v1\"")
+            {
+                return StatusCode::NOT_MODIFIED.into_response();
+            }
+
+            FULL_RESPONSES.fetch_add(1, Ordering::SeqCst);
+            (
+                [(header::ETAG, "\"v1\"")],
+                Html(
+                    r#"<?xml version='1.0' encoding='UTF-8'?>
+<metadata>
+  <groupId>com.example</groupId>
+  <artifactId>cached</artifactId>
+  <versioning>
+    <release>1.0.0</release>
+    <versions>
+      <version>1.0.0</version>
+    </versions>
+  </versioning>
+</metadata>
+"#,
+                ),
+            )
+                .into_response()
+        }
+
+        let (handler, tx, port) = launch_web_server(
+            Router::new()
+                .route(
+                    "/com/example/cached/maven-metadata.xml",
+                    get(get_maven_metadata),
+                )
+                .into_make_service(),
+        )
+        .await;
+
+        let cache_dir = std::env::temp_dir().join(format!(
+            "oss-info-maven-list-versions-{}",
+            std::process::id()
+        ));
+        let client = HttpClient::new(reqwest::Client::new())
+            .with_cache(std::sync::Arc::new(HttpCache::new(&cache_dir)));
+        let repositories = RepositoryConfig::single(format!("http://127.0.0.1:{}", *port));
+        let coordinate = "com.example:cached".parse().unwrap();
+        let first = list_versions(client.clone(), &coordinate, &repositories).await;
+        let second = list_versions(client, &coordinate, &repositories).await;
+
+        tx.send(()).unwrap();
+        handler.await.unwrap();
+        std::fs::remove_dir_all(&cache_dir).unwrap();
+
+        assert_eq!(vec!["1.0.0".to_string()], first.unwrap());
+        assert_eq!(vec!["1.0.0".to_string()], second.unwrap());
+        assert_eq!(1, FULL_RESPONSES.load(Ordering::SeqCst));
+    }
+
+    #[tokio::test]
+    async fn retrieve_maven_lib_impl_fixed_version_round_trips() {
+        use axum::extract::Path;
+        use std::collections::BTreeSet;
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use std::sync::Mutex;
+
+        // the round trip of the POM that is requested after the maven-metadata.xml is responded.
+        static ROUND_TRIPS: AtomicUsize = AtomicUsize::new(0);
+        static RESPONDED: Mutex<BTreeSet<String>> = Mutex::new(BTreeSet::new());
+
+        async fn get_maven_metadata(Path(artifact_id): Path<String>) -> Html<String> {
+            ROUND_TRIPS.fetch_add(1, Ordering::SeqCst);
+            tokio::time::sleep(Duration::from_millis(300)).await;
+            RESPONDED.lock().unwrap().insert(artifact_id.clone());
+            Html(format!(
+                r#"<?xml version='1.0' encoding='UTF-8'?>
+<metadata>
+  <groupId>androidx.compose.ui</groupId>
+  <artifactId>{}</artifactId>
+  <versioning>
+    <release>1.5.0</release>
+    <versions>
+      <version>1.5.0</version>
+    </versions>
+  </versioning>
+</metadata>
+"#,
+                artifact_id
+            ))
+        }
+
+        async fn get_pom(
+            Path((artifact_id, _, _)): Path<(String, String, String)>,
+        ) -> Html<String> {
+            if RESPONDED.lock().unwrap().contains(&artifact_id) {
+                ROUND_TRIPS.fetch_add(1, Ordering::SeqCst);
+            }
+            Html(format!(
+                r#"<?xml version="1.0" encoding="UTF-8"?>
+<project>
+  <modelVersion>4.0.0</modelVersion>
+  <groupId>androidx.compose.ui</groupId>
+  <artifactId>{}</artifactId>
+  <version>1.5.0</version>
+</project>
+"#,
+                artifact_id
+            ))
+        }
+
+        let (handler, tx, port) = launch_web_server(
+            Router::new()
+                .route(
+                    "/androidx/compose/ui/:artifact_id/maven-metadata.xml",
+                    get(get_maven_metadata),
+                )
+                .route(
+                    "/androidx/compose/ui/:artifact_id/:version/:file_name",
+                    get(get_pom),
+                )
+                .into_make_service(),
+        )
+        .await;
+
+        let repo_root = format!("http://127.0.0.1:{}", *port);
+        let client = reqwest::Client::new().into();
+        let retrieve = |artifact_id: &str, selector: Box<dyn VersionSelector>| {
+            let coordinate = Coordinate::new("androidx.compose.ui", artifact_id);
+            let (client, repo_root) = (&client, &repo_root);
+            async move { retrieve_maven_lib_impl(client, &coordinate, repo_root, &*selector).await }
+        };
+        let fixed = || Box::new(FixedVersionSelector("1.5.0".into()));
+        let fixed_results = futures::future::join_all([
+            retrieve("ui", fixed()),
+            retrieve("ui-graphics", fixed()),
+            retrieve("ui-text", fixed()),
+        ])
+        .await;
+        let fixed_round_trips = ROUND_TRIPS.swap(0, Ordering::SeqCst);
+        let default_result = retrieve("ui-unit", Box::new(DefaultVersionSelector)).await;
+        let default_round_trips = ROUND_TRIPS.load(Ordering::SeqCst);
+
+        tx.send(()).unwrap();
+        handler.await.unwrap();
+
+        for result in fixed_results {
+            assert_eq!("1.5.0", result.unwrap().1.version);
+        }
+        assert_eq!(3, fixed_round_trips, "a round trip per artifact");
+        assert_eq!("1.5.0", default_result.unwrap().1.version);
+        assert_eq!(2, default_round_trips);
+    }
+
+    #[tokio::test]
+    async fn list_versions_shared_metadata() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        static REQUESTS: AtomicUsize = AtomicUsize::new(0);
+
+        async fn get_maven_metadata() -> Html<&'static str> {
+            REQUESTS.fetch_add(1, Ordering::SeqCst);
+            Html(
+                r#"<?xml version='1.0' encoding='UTF-8'?>
+<metadata>
+  <groupId>com.example</groupId>
+  <artifactId>shared</artifactId>
+  <versioning>
+    <release>1.0.0</release>
+    <versions>
+      <version>1.0.0</version>
+    </versions>
+  </versioning>
+</metadata>
+"#,
+            )
+        }
+
+        let (handler, tx, port) = launch_web_server(
+            Router::new()
+                .route(
+                    "/com/example/shared/maven-metadata.xml",
+                    get(get_maven_metadata),
+                )
+                .into_make_service(),
+        )
+        .await;
+
+        let client = HttpClient::new(reqwest::Client::new()).with_shared_metadata();
+        let repositories = RepositoryConfig::single(format!("http://127.0.0.1:{}", *port));
+        let first = "com.example:shared:1.0.0".parse().unwrap();
+        let second = "com.example:shared:0.9.0".parse().unwrap();
+        let (first, second) = futures::join!(
+            list_versions(client.clone(), &first, &repositories),
+            list_versions(client.clone(), &second, &repositories),
+        );
+
+        tx.send(()).unwrap();
+        handler.await.unwrap();
+
+        assert_eq!(vec!["1.0.0".to_string()], first.unwrap());
+        assert_eq!(vec!["1.0.0".to_string()], second.unwrap());
+        assert_eq!(1, REQUESTS.load(Ordering::SeqCst));
+        assert_eq!(1, client.metrics().requests());
+    }
+
+    #[tokio::test]
+    async fn list_versions_shared_metadata_retry() {
+        use axum::http::StatusCode;
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        static REQUESTS: AtomicUsize = AtomicUsize::new(0);
+
+        async fn get_maven_metadata() -> Result<Html<&'static str>, StatusCode> {
+            if REQUESTS.fetch_add(1, Ordering::SeqCst) == 0 {
+                return Err(StatusCode::INTERNAL_SERVER_ERROR);
+            }
+            Ok(Html(
+                r#"<?xml version='1.0' encoding='UTF-8'?>
+<metadata>
+  <groupId>com.example</groupId>
+  <artifactId>retry</artifactId>
+  <versioning>
+    <release>1.0.0</release>
+    <versions>
+      <version>1.0.0</version>
+    </versions>
+  </versioning>
+</metadata>
+"#,
+            ))
+        }
+
+        let (handler, tx, port) = launch_web_server(
+            Router::new()
+                .route(
+                    "/com/example/retry/maven-metadata.xml",
+                    get(get_maven_metadata),
+                )
+                .into_make_service(),
+        )
+        .await;
+
+        let client = HttpClient::new(reqwest::Client::new()).with_shared_metadata();
+        let repositories = RepositoryConfig::single(format!("http://127.0.0.1:{}", *port));
+        let coordinate = "com.example:retry:1.0.0".parse().unwrap();
+        let first = list_versions(client.clone(), &coordinate, &repositories).await;
+        let second = list_versions(client.clone(), &coordinate, &repositories).await;
+        let third = list_versions(client.clone(), &coordinate, &repositories).await;
+
+        tx.send(()).unwrap();
+        handler.await.unwrap();
+
+        assert!(first.is_err());
+        assert_eq!(vec!["1.0.0".to_string()], second.unwrap());
+        assert_eq!(vec!["1.0.0".to_string()], third.unwrap());
+        assert_eq!(2, REQUESTS.load(Ordering::SeqCst));
+        assert_eq!(1, client.metrics().retries());
+    }
+
+    #[tokio::test]
+    async fn retrieve_versions_ordered() {
+        async fn get_maven_metadata() -> Html<&'static str> {
+            Html(
+                r#"<?xml version='1.0' encoding='UTF-8'?>
+<metadata>
+  <groupId>com.example</groupId>
+  <artifactId>ordered</artifactId>
+  <versioning>
+    <versions>
+      <version>1.10.0</version>
+      <version>1.9.0</version>
+      <version>1.10.0-rc01</version>
+      <version>1.10.0-alpha01</version>
+    </versions>
+  </versioning>
+</metadata>
+"#,
+            )
+        }
+
+        let (handler, tx, port) = launch_web_server(
+            Router::new()
+                .route(
+                    "/com/example/ordered/maven-metadata.xml",
+                    get(get_maven_metadata),
+                )
+                .into_make_service(),
+        )
+        .await;
+
+        let repositories = RepositoryConfig::single(format!("http://127.0.0.1:{}", *port));
+        let actual = retrieve_versions(
+            reqwest::Client::new(),
+            &"com.example:ordered".parse().unwrap(),
+            &repositories,
+        )
+        .await;
+
+        tx.send(()).unwrap();
+        handler.await.unwrap();
+
+        let actual = actual
+            .unwrap()
+            .iter()
+            .map(Version::to_string)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            vec!["1.9.0", "1.10.0-alpha01", "1.10.0-rc01", "1.10.0"],
+            actual
+        );
+    }
+
+    #[tokio::test]
+    async fn retrieve_platform_maven_lib_kotlinx_coroutines() {
+        async fn get_jvm_maven_metadata() -> Html<&'static str> {
+            Html(
+                r#"<?xml version='1.0' encoding='UTF-8'?>
+<metadata>
+  <groupId>org.jetbrains.kotlinx</groupId>
+  <artifactId>kotlinx-coroutines-core-jvm</artifactId>
+  <versioning>
+    <release>1.7.3</release>
+    <versions>
+      <version>1.7.2</version>
+      <version>1.7.3</version>
+    </versions>
+  </versioning>
+</metadata>
+"#,
+            )
+        }
+
+        async fn get_jvm_pom() -> Html<&'static str> {
+            Html(
+                r#"<?xml version="1.0" encoding="UTF-8"?>
+<project>
+  <modelVersion>4.0.0</modelVersion>
+  <groupId>org.jetbrains.kotlinx</groupId>
+  <artifactId>kotlinx-coroutines-core-jvm</artifactId>
+  <version>1.7.2</version>
+  <name>kotlinx-coroutines-core</name>
+  <description>Coroutines support libraries for Kotlin</description>
+  <licenses>
+    <license>
+      <name>The Apache Software License, Version 2.0</name>
+      <url>https://www.apache.org/licenses/LICENSE-2.0.txt</url>
+    </license>
+  </licenses>
+</project>
+"#,
+            )
+        }
+
+        let (handler, tx, port) = launch_web_server(
+            Router::new()
+                .route(
+                    "/org/jetbrains/kotlinx/kotlinx-coroutines-core-jvm/maven-metadata.xml",
+                    get(get_jvm_maven_metadata),
+                )
+                .route(
+                    "/org/jetbrains/kotlinx/kotlinx-coroutines-core-jvm/1.7.2/kotlinx-coroutines-core-jvm-1.7.2.pom",
+                    get(get_jvm_pom),
+                )
+                .into_make_service(),
+        )
+        .await;
+
+        let repositories = RepositoryConfig::single(format!("http://127.0.0.1:{}", *port));
+        let coordinate = "org.jetbrains.kotlinx:kotlinx-coroutines-core:1.7.2"
+            .parse::<Coordinate>()
+            .unwrap();
+        let root = POM {
+            group_id: Some("org.jetbrains.kotlinx".into()),
+            artifact_id: "kotlinx-coroutines-core".into(),
+            version: Some("1.7.2".into()),
+            packaging: Some("pom".into()),
+            ..Default::default()
+        };
+        let client = reqwest::Client::new();
+        let actual =
+            retrieve_platform_maven_lib(client.clone(), &coordinate, &repositories, &root, "jvm")
+                .await;
+        let jar = POM {
+            packaging: None,
+            ..root.clone()
+        };
+        let not_root =
+            retrieve_platform_maven_lib(client, &coordinate, &repositories, &jar, "jvm").await;
+
+        tx.send(()).unwrap();
+        handler.await.unwrap();
+
+        let actual = actual.unwrap().unwrap();
+        assert_eq!("kotlinx-coroutines-core-jvm", actual.artifact_id);
+        assert_eq!(Some("1.7.2".into()), actual.version);
+        assert_eq!(vec![SPDX::Apache20], actual.licenses);
+        assert!(not_root.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn check_link_dead() {
+        async fn get_license() -> &'static str {
+            "license"
+        }
+
+        let (handler, tx, port) = launch_web_server(
+            Router::new()
+                .route("/LICENSE", get(get_license))
+                .into_make_service(),
+        )
+        .await;
+
+        let client = reqwest::Client::new();
+        let alive = check_link(
+            client.clone(),
+            &format!("http://127.0.0.1:{}/LICENSE", *port),
+        )
+        .await;
+        let dead = check_link(client, &format!("http://127.0.0.1:{}/404", *port)).await;
+
+        tx.send(()).unwrap();
+        handler.await.unwrap();
+
+        assert_eq!(LinkStatus::Alive, alive);
+        assert_eq!(LinkStatus::Dead(404), dead);
+    }
+
+    #[tokio::test]
+    async fn check_repository_status() {
+        use axum::http::StatusCode;
+
+        async fn get_maven_metadata() -> Html<&'static str> {
+            Html(
+                r#"<?xml version='1.0' encoding='UTF-8'?>
+<metadata>
+  <groupId>junit</groupId>
+  <artifactId>junit</artifactId>
+  <versioning>
+    <release>4.13.2</release>
+  </versioning>
+</metadata>
+"#,
+            )
+        }
+
+        async fn get_unauthorized() -> StatusCode {
+            StatusCode::UNAUTHORIZED
+        }
+
+        let (handler, tx, port) = launch_web_server(
+            Router::new()
+                .route(
+                    "/public/junit/junit/maven-metadata.xml",
+                    get(get_maven_metadata),
+                )
+                .route(
+                    "/private/junit/junit/maven-metadata.xml",
+                    get(get_unauthorized),
+                )
+                .into_make_service(),
+        )
+        .await;
+
+        let client = reqwest::Client::new();
+        let probe = "junit:junit".parse().unwrap();
+        let repo_root = format!("http://127.0.0.1:{}", *port);
+        let (healthy, _) =
+            check_repository(client.clone(), &format!("{}/public/", repo_root), &probe).await;
+        let (unauthorized, _) =
+            check_repository(client.clone(), &format!("{}/private", repo_root), &probe).await;
+        let (not_found, _) =
+            check_repository(client, &format!("{}/empty", repo_root), &probe).await;
+
+        tx.send(()).unwrap();
+        handler.await.unwrap();
+
+        assert_eq!(RepositoryStatus::Healthy, healthy);
+        assert_eq!(RepositoryStatus::Unauthorized(401), unauthorized);
+        assert_eq!(RepositoryStatus::ProbeNotFound, not_found);
+    }
+
+    #[tokio::test]
+    async fn http_client_timeout() {
+        async fn get_slow() -> &'static str {
+            tokio::time::sleep(std::time::Duration::from_secs(1)).await;
+            "slow"
+        }
+
+        let (handler, tx, port) = launch_web_server(
+            Router::new()
+                .route("/slow", get(get_slow))
+                .into_make_service(),
+        )
+        .await;
+
+        let client = HttpClient::new(reqwest::Client::new())
+            .with_timeout(std::time::Duration::from_millis(100));
+        let actual = client
+            .send(client.get(&format!("http://127.0.0.1:{}/slow", *port)))
+            .await;
+
+        tx.send(()).unwrap();
+        handler.await.unwrap();
+
+        assert!(actual
+            .unwrap_err()
+            .downcast_ref::<reqwest::Error>()
+            .unwrap()
+            .is_timeout());
+    }
+
+    #[test]
+    fn split_dependency_name_to_path_core_ktx() {
+        let source = "androidx.core:core-ktx";
+        let expected = "androidx/core/core-ktx";
 
-        // yield for launching server.
-        tokio::task::yield_now().await;
+        let actual = split_dependency_name_to_path(&source.parse().unwrap());
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn split_dependency_name_to_path_core_ktx_version() {
+        let source = "androidx.core:core-ktx:1.1.0";
+        let expected = "androidx/core/core-ktx";
+
+        let actual = split_dependency_name_to_path(&source.parse().unwrap());
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn split_dependency_name_to_path_javax_inject() {
+        let source = "javax.inject:javax.inject";
+        let expected = "javax/inject/javax.inject";
+
+        let actual = split_dependency_name_to_path(&source.parse().unwrap());
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn split_dependency_name_to_path_classifier_packaging() {
+        let source = "org.lwjgl:lwjgl:3.3.3:natives-linux@jar";
+        let expected = "org/lwjgl/lwjgl";
+
+        let actual = split_dependency_name_to_path(&source.parse().unwrap());
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn artifact_file_name_without_classifier() {
+        let source = "androidx.core:core-ktx:1.1.0";
+        let expected = "core-ktx-1.1.0.aar";
+
+        let actual = artifact_file_name(&source.parse().unwrap(), "1.1.0", "aar");
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn artifact_file_name_classifier() {
+        let source = "org.lwjgl:lwjgl:3.3.3:natives-linux@jar";
+        let expected = "lwjgl-3.3.3-natives-linux.jar";
 
-        (handler, tx, port_guard)
+        let actual = artifact_file_name(&source.parse().unwrap(), "3.3.3", "jar");
+        assert_eq!(expected, actual);
     }
 }
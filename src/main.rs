@@ -14,172 +14,3404 @@
  * limitations under the License.
  */
 
-use clap::{CommandFactory, Parser, ValueEnum};
-use futures::StreamExt;
+use clap::{ArgAction, CommandFactory, FromArgMatches, Parser, Subcommand, ValueEnum};
+use futures::{FutureExt, StreamExt, TryFutureExt};
 use indexmap::IndexMap;
+use oss_info_maven::function::atomic_file::AtomicFile;
+use oss_info_maven::function::bazel::parse_maven_install;
+use oss_info_maven::function::compatibility::{check_compatibility, Compatibility};
+use oss_info_maven::function::config::{parse_config, ConfigValue};
+use oss_info_maven::function::conflict::find_conflicts;
+use oss_info_maven::function::exclude::{read_exclude_patterns, ExcludePattern};
+use oss_info_maven::function::formula::{escape_formula, is_formula_like};
 use oss_info_maven::function::gradle::{
-    parse_dependencies_string, parse_prettied_dependencies_string,
+    parse_dependency_insight, parse_dependency_report, parse_dependency_tree, parse_lockfile,
+    parse_prettied_entries, parse_tree_entries, DependencyTree, LineError, TreeEntry,
+};
+use oss_info_maven::function::graph::{write_dot, write_mermaid, LicenseMap};
+use oss_info_maven::function::group_license::{infer_group_licenses, InferredLicense};
+use oss_info_maven::function::http_cache::HttpCache;
+use oss_info_maven::function::http_client::{ConnectionOptions, HttpClient};
+use oss_info_maven::function::jsonl::{read_jsonl, write_jsonl_record};
+use oss_info_maven::function::link::LinkStatus;
+use oss_info_maven::function::lock::{read_lock, write_lock, LockEntry};
+use oss_info_maven::function::maven::{
+    parse_maven_settings, parse_pom_dependencies, write_bom_pom, ArtifactFile,
+    DefaultVersionSelector, Dependency, FixedVersionSelector, LatestVersionSelector, MavenSettings,
+    ReleaseVersionSelector, RepositoryConfig, VersionSelector, POM,
+};
+use oss_info_maven::function::metrics::{
+    slowest_artifacts, write_metrics, write_metrics_json, RunMetrics, SLOWEST_ARTIFACTS,
+};
+use oss_info_maven::function::notice::{
+    render_notice_template, write_notice_with_template, year_of_unix_time, NoticeEntry,
+};
+use oss_info_maven::function::obligation::{write_obligations, ObligationEntry};
+use oss_info_maven::function::osv::Vulnerability;
+use oss_info_maven::function::outdated::classify_delta;
+use oss_info_maven::function::overrides::{
+    find_override, parse_overrides_csv, parse_overrides_toml, LicenseOverride,
+};
+use oss_info_maven::function::progress::Progress;
+use oss_info_maven::function::rate_limit::{Rate, RateLimiter};
+use oss_info_maven::function::release_notes::androidx_release_notes_url;
+use oss_info_maven::function::report::{column_key, CsvFormatter, QuoteStyle, ReportFormatter};
+use oss_info_maven::function::state::{read_state, write_state};
+use oss_info_maven::function::summary::{count_licenses, has_unknown_license, write_summary};
+use oss_info_maven::function::template::{check_template, TemplateFormatter};
+use oss_info_maven::function::text::{transform_pom_text, AsciiTransformer};
+use oss_info_maven::function::xlsx::write_xlsx;
+use oss_info_maven::model::{
+    Annotation, Coordinate, LicenseConjunction, LicenseOrigin, Version, SPDX,
 };
-use oss_info_maven::model::SPDX;
 use oss_info_maven::prelude::*;
-use oss_info_maven::retrieve_maven_lib;
-use std::io::BufReader;
+use oss_info_maven::{
+    check_link, check_repository, retrieve_artifact_file, retrieve_clearly_defined_licenses,
+    retrieve_ivy_lib_with_lock_entry, retrieve_latest_version, retrieve_maven_lib_with_selector,
+    retrieve_maven_lib_with_selector_and_lock_entry, retrieve_platform_maven_lib,
+    retrieve_versions, retrieve_vulnerabilities,
+};
+use regex::Regex;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::fmt::{Display, Formatter};
+use std::fs::File;
+use std::io::{BufRead, BufReader, BufWriter, IsTerminal, Write};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
 use std::sync::Arc;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 use tokio::sync::Semaphore;
 use tracing::{info_span, Instrument};
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::util::SubscriberInitExt;
 
 /// Collect OSS information from server.
 #[derive(Parser)]
+#[command(after_help = EXIT_STATUS_HELP, args_override_self = true)]
 struct Opt {
     /// Output format type.
+    ///
+    /// `notice` emits a plaintext NOTICE file that has a section per license.
+    /// `obligations` emits a first-pass compliance checklist per license.
+    /// `xlsx` writes a workbook of the dependencies and the license summary.
+    /// `template` renders the file of `--template`.
+    /// `dot` and `mermaid` render the dependency graph of the Gradle output that the nodes are
+    /// colored by the license.
     #[clap(long, default_value = "csv")]
     format: FormatType,
 
-    /// Parse stdin as manually formatted Gradle output.
+    /// Template of `--format template`.
+    ///
+    /// `{{#each rows}}...{{/each}}` repeats the block per dependency that has the columns of the
+    /// CSV in snake case such as `{{version_input}}`,
+    /// `{{#each licenses}}` has `{{license}}` and `{{count}}` of the summary, and
+    /// `{{#if name}}...{{/if}}` writes the block if the variable is not empty. The variables of
+    /// `--notice-var`, `{{year}}` and `{{count}}` of the rows are also available.
+    #[clap(long, required_if_eq("format", "template"))]
+    template: Option<PathBuf>,
+
+    /// Write the output to the file instead of stdout. The file is replaced only after the whole
+    /// output is written except for `--flush-interval`. Required for `--format xlsx`.
+    #[clap(long, required_if_eq("format", "xlsx"))]
+    output: Option<PathBuf>,
+
+    /// Overwrite the existing file of `--output`.
+    #[clap(long, requires = "output")]
+    force: bool,
+
+    /// Read the Gradle output from the files instead of stdin. The files are merged and the
+    /// "Source" column that has the files declaring each dependency is added to the CSV when
+    /// multiple files are specified.
+    #[clap(long)]
+    input: Vec<PathBuf>,
+
+    /// Format of the input.
+    #[clap(long, default_value = "text", conflicts_with = "skip_pretty")]
+    input_format: InputFormat,
+
+    /// Exclude the dependencies from the resolution and the report. The glob such as
+    /// `com.mycompany.*` or the regex with the `regex:` prefix that matches with
+    /// `group:artifact` or the whole coordinate.
+    #[clap(long)]
+    exclude: Vec<ExcludePattern>,
+
+    /// Read the patterns of `--exclude` from the file that has a pattern per line.
+    #[clap(long)]
+    exclude_file: Vec<PathBuf>,
+
+    /// Add the "Modules" column that has the Gradle modules declaring each dependency from the
+    /// `Project ':app'` sections of the Gradle output.
+    #[clap(long, conflicts_with = "skip_pretty")]
+    modules: bool,
+
+    /// Parse only the configurations that match the regex from the Gradle output that has
+    /// multiple configurations. e.g. `^releaseRuntimeClasspath$`
+    #[clap(long, conflicts_with = "skip_pretty")]
+    configuration_filter: Option<Regex>,
+
+    /// Exclude the dependencies that appear only as the dependency constraints `(c)` of the
+    /// Gradle output.
+    #[clap(long, conflicts_with = "skip_pretty")]
+    exclude_constraints: bool,
+
+    /// Add the project dependencies such as `project :lib` of the Gradle output to the CSV with
+    /// the "internal" tag.
+    #[clap(long, conflicts_with = "skip_pretty")]
+    internal_projects: bool,
+
+    /// Parse the input as manually formatted Gradle output.
     #[clap(long)]
     skip_pretty: bool,
 
-    /// Generate shell completions.
-    #[arg(long, exclusive = true)]
+    /// Fail on the first line of the input that cannot be parsed instead of reporting all of them
+    /// before the requests.
+    #[clap(long)]
+    strict: bool,
+
+    /// Skip the malformed lines of the input and continue with the valid ones instead of failing
+    /// before the requests.
+    #[clap(long, conflicts_with = "strict")]
+    skip_invalid: bool,
+
+    /// Operator of the "Licenses" column for the POM that has multiple licenses, `or` or `and`.
+    /// e.g. `BSD-2-Clause OR Apache-2.0`. The license that is not the SPDX identifier is
+    /// `LicenseRef-` such as `LicenseRef-Custom-License`.
+    #[clap(long, default_value = "or")]
+    license_conjunction: LicenseConjunction,
+
+    /// License of the project that uses the dependencies. e.g. `Apache-2.0`
+    ///
+    /// Add the "Compatibility" and the "Compatibility Notes" columns that flag the dependencies
+    /// whose licenses are incompatible with the project license or need the manual review such as
+    /// the GPL family and the unknown licenses. The POM that has multiple licenses is checked with
+    /// the best of them because any of them can be chosen. This is not legal advice.
+    #[clap(long)]
+    project_license: Option<SPDX>,
+
+    /// Replace the licenses of the resolved artifacts with the manually reviewed ones of the file
+    /// and add the "Overridden" and the "Override Note" columns. The other formats mark the
+    /// overridden licenses such as "License: overridden" of the notice. The file is the CSV that
+    /// has the `coordinate`, `license` and `note` columns if the extension is `.csv`, otherwise
+    /// the TOML that has a table per coordinate such as `["com.example:lib"]` with `license` and
+    /// `note`. The coordinate without the version matches all versions.
+    #[clap(long, value_name = "FILE")]
+    overrides: Option<PathBuf>,
+
+    /// Reuse the licenses of the sibling artifacts of the same group and version for the POMs that
+    /// have no license, and add the "Inferred From" column that has the sibling. The licenses are
+    /// inferred only if all siblings that have the recognized licenses agree on them. The other
+    /// formats mark the inferred licenses and `--fail-on-unknown-license` treats them as unknown.
+    #[clap(long)]
+    infer_group_licenses: bool,
+
+    /// Output only the artifacts of the packagings. e.g. `jar,aar`
+    ///
+    /// The packaging is the `@ext` of the input or the `<packaging>` of the POM that defaults to
+    /// `jar`.
+    #[clap(long, value_delimiter = ',')]
+    packaging_filter: Vec<String>,
+
+    /// Output only the artifacts that have no license or the license that is not recognized, and
+    /// add the "License URLs" column that has the raw URLs of the licenses to review them.
+    #[clap(long, conflicts_with = "internal_projects")]
+    only_unknown: bool,
+
+    /// Order of the artifacts of the output instead of the order of the input. The order is
+    /// stable.
+    #[clap(long)]
+    sort: Option<SortKey>,
+
+    /// Keep the artifacts of the same group id or the same licenses together in the output. The
+    /// groups are in alphabetical order and the artifacts of each group are in the order of
+    /// `--sort`.
+    #[clap(long)]
+    group_by: Option<GroupKey>,
+
+    /// Add the "Purl" column that has the package URL such as
+    /// `pkg:maven/androidx.core/core-ktx@1.12.0`.
+    #[clap(long)]
+    purl: bool,
+
+    /// Add the "Copyright" column that is synthesized from `<inceptionYear>` and
+    /// `<organization>` of the POM such as `Copyright 2018 The Android Open Source Project`.
+    #[clap(long)]
+    copyright: bool,
+
+    /// Retrieve the size and the published SHA-256 of the main artifact (jar/aar).
+    #[clap(long)]
+    artifact_digest: bool,
+
+    /// Fill in the licenses of the POMs that have no `<licenses>` from the external service.
+    #[clap(long, value_delimiter = ',')]
+    enrich: Vec<EnrichType>,
+
+    /// Follow the platform artifact such as `jvm` of `kotlinx-coroutines-core-jvm` when the POM is
+    /// the root artifact of the Kotlin Multiplatform library that has `<packaging>pom</packaging>`
+    /// and no licenses.
+    #[clap(long)]
+    kmp_platform: Option<String>,
+
+    /// Replace the BOM such as `androidx.compose:compose-bom` that has `<packaging>pom</packaging>`
+    /// and `<dependencyManagement>` with the managed artifacts and resolve them in the managed
+    /// version regardless of `--version-policy`. The artifact that is in the input is kept in the
+    /// version of the input.
+    #[clap(long)]
+    expand_bom: bool,
+
+    /// Version of the POM to retrieve from the maven-metadata.xml instead of `<release>`, then
+    /// `<latest>` and then `<version>`.
+    #[clap(long)]
+    version_policy: Option<VersionPolicy>,
+
+    /// Fold the name and the description to ASCII. The artifact id is used for the name and the
+    /// description is dropped if they cannot be folded.
+    #[clap(long)]
+    prefer_ascii: bool,
+
+    /// Hide the progress that is shown by default when stderr is a terminal.
+    #[clap(long)]
+    no_progress: bool,
+
+    /// Write the CSV rows during the run and flush them at most once per the seconds instead of
+    /// writing all rows at the end. Add the "Status" column that is "timed out" or "interrupted"
+    /// for the dependencies that are cancelled after the header is written. The rows are written
+    /// to the file of `--output` directly so that the file has the rows of the run that is killed.
+    #[clap(
+        long,
+        value_name = "SECS",
+        conflicts_with_all = [
+            "check_license_urls",
+            "check_vulnerabilities",
+            "packaging_filter",
+            "only_unknown",
+            "overrides",
+            "infer_group_licenses",
+            "expand_bom",
+            "sort",
+            "group_by",
+        ]
+    )]
+    flush_interval: Option<u64>,
+
+    /// Limit the requests per repository host. e.g. `10/s`, `600/m`.
+    #[clap(long)]
+    rate_limit: Option<Rate>,
+
+    /// Store maven-metadata.xml to the directory and revalidate it with `ETag` and
+    /// `Last-Modified` on the next run.
+    #[clap(long)]
+    cache_dir: Option<PathBuf>,
+
+    /// Timeout in seconds of the connection and the whole of each request.
+    #[clap(long, value_name = "SECS")]
+    timeout: Option<u64>,
+
+    /// Timeout in seconds of the resolution of each dependency including all of its requests.
+    /// The dependency that exceeds it fails.
+    #[clap(long, value_name = "SECS")]
+    coordinate_timeout: Option<u64>,
+
+    /// Deadline in seconds of the whole run. The dependencies that are not resolved by then are
+    /// cancelled, and the output is written with the dependencies that were resolved so far. Add
+    /// the "Status" column that is "timed out" for the cancelled dependencies, or "interrupted"
+    /// for the ones cancelled by Ctrl-C.
+    #[clap(long, value_name = "SECS")]
+    deadline: Option<u64>,
+
+    /// Apply the `<mirrors>`, the `<servers>` and the `<proxies>` of `~/.m2/settings.xml` to the
+    /// repositories, the authentication and the proxy. `${env.NAME}` of the file is substituted
+    /// with the environment variable.
+    #[clap(long)]
+    use_maven_settings: bool,
+
+    /// Root URL of the Ivy repository of the `gradle` layout that has
+    /// `[organisation]/[module]/[revision]/ivy-[revision].xml`. The Ivy repositories are tried in
+    /// order when the artifact is not found in the Maven repositories. The `<info>` and the
+    /// `<license>` of the Ivy file are mapped into the POM.
+    #[clap(long, value_name = "URL")]
+    ivy_repository: Vec<String>,
+
+    /// Replace the title of the NOTICE with the file. `{{name}}` is substituted with the
+    /// `--notice-var` and `{{year}}` is the current year.
+    #[clap(long)]
+    notice_header: Option<PathBuf>,
+
+    /// Append the file to the NOTICE. The variables are same as `--notice-header`.
+    #[clap(long)]
+    notice_footer: Option<PathBuf>,
+
+    /// Variable for `--notice-header` and `--notice-footer`. e.g. `app_name=Example`
+    #[clap(long, value_parser = parse_key_value)]
+    notice_var: Vec<(String, String)>,
+
+    /// Prefix the CSV fields that start with `=`, `+`, `-` or `@` with `'` to prevent the
+    /// spreadsheets from interpreting them as formulas.
+    #[clap(long)]
+    escape_formulas: bool,
+
+    /// Separator of the CSV fields such as `;` for the spreadsheets of the locales that use the
+    /// comma as the decimal separator. `tab` or `\t` for the TSV.
+    #[clap(long, default_value = ",", value_name = "CHAR", value_parser = parse_delimiter)]
+    csv_delimiter: u8,
+
+    /// Quoting of the CSV fields.
+    #[clap(long, default_value = "necessary")]
+    csv_quote: QuoteStyle,
+
+    /// Output only the columns in the order. The column is the header of the CSV or its snake
+    /// case such as `version_input`. e.g. `dependency,version_latest,licenses`
+    #[clap(long, value_delimiter = ',')]
+    columns: Vec<String>,
+
+    /// Write the Maven BOM `pom.xml` that pins the version of every resolved artifact to the
+    /// file.
+    #[clap(long)]
+    emit_bom_pom: Option<PathBuf>,
+
+    /// `group:artifact:version` of the BOM of `--emit-bom-pom`.
+    #[clap(long, default_value = "com.example:third-party-bom:1.0.0")]
+    bom_coordinate: Coordinate,
+
+    /// Add the "Release Notes" column that has the release notes URL of the androidx artifacts.
+    #[clap(long)]
+    release_notes: bool,
+
+    /// Check the liveness of the license URLs and add the "Dead License URLs" column.
+    #[clap(long)]
+    check_license_urls: bool,
+
+    /// Record the resolved results to the file and resolve only the dependencies that are not in
+    /// the file on the re-run. The results in the file are reused regardless of the other options.
+    #[clap(long)]
+    state_file: Option<PathBuf>,
+
+    /// Record the repository, the resolved version, the POM URL and the SHA-256 of the POM of
+    /// each dependency to the file.
+    #[clap(long, conflicts_with = "state_file")]
+    lock_output: Option<PathBuf>,
+
+    /// Verify that the resolutions are the same as the file of `--lock-output` and exit with 3 if
+    /// any of them has drifted.
+    #[clap(long, conflicts_with = "state_file")]
+    locked: Option<PathBuf>,
+
+    /// Query the vulnerabilities of the resolved versions to OSV and add the "Vulnerabilities"
+    /// column.
+    #[clap(long)]
+    check_vulnerabilities: bool,
+
+    /// Validate the fetched maven-metadata.xml and POMs against the XSD rules and warn the
+    /// violations.
+    #[clap(long)]
+    validate_xml: bool,
+
+    /// Fail the dependency whose maven-metadata.xml or POM violates the XSD rules with the URL
+    /// and the violations instead of warning them. This implies `--validate-xml`.
+    #[clap(long)]
+    strict_xml: bool,
+
+    /// Write the CSV of the dependencies that are requested with more than one version and the
+    /// version that Gradle picks instead of retrieving the information.
+    #[clap(long)]
+    conflicts: bool,
+
+    /// How to report the dependencies of the same `group:artifact` that are requested with
+    /// different versions such as the inputs of multiple projects.
+    ///
+    /// `keep` writes a row per version and `collapse` writes a row of the highest version whose
+    /// "Version (Input)" has all the input versions.
+    #[clap(long, default_value = "keep")]
+    duplicate_versions: DuplicateVersions,
+
+    /// Write the CSV of the dependencies that are behind the highest version of the
+    /// maven-metadata.xml with the delta (major, minor, patch or other) instead of retrieving the
+    /// information. The pre-releases are compared only with the pre-release.
+    #[clap(
+        long,
+        conflicts_with_all = ["conflicts", "state_file", "lock_output", "locked"]
+    )]
+    outdated: bool,
+
+    /// Write the number of the artifacts per license and the artifacts that have the unknown or
+    /// other licenses to stderr after the output.
+    #[clap(long)]
+    summary: bool,
+
+    /// Exit with 4 if any artifact has no license, the license that is not recognized or the
+    /// license of `--infer-group-licenses`, after logging the artifacts and the license names.
+    #[clap(long)]
+    fail_on_unknown_license: bool,
+
+    /// Write the metrics of the run such as the number of the HTTP requests, the retries, the cache
+    /// hits, the failures, the wall time and the slowest artifacts to stderr after the output.
+    #[clap(long)]
+    metrics: bool,
+
+    /// Write the metrics of `--metrics` to the file as JSON to track them in CI.
+    #[clap(long)]
+    metrics_json: Option<PathBuf>,
+
+    /// Format of the log that is written to stderr.
+    #[clap(long, default_value = "text")]
+    log_format: LogFormat,
+
+    /// Export the spans to the collector of `OTEL_EXPORTER_OTLP_ENDPOINT` with OTLP/gRPC. The
+    /// spans of the level of `RUST_LOG` such as `info` are exported.
+    #[cfg(feature = "otel")]
+    #[clap(long)]
+    otel: bool,
+
+    /// Number of the artifacts that are resolved concurrently.
+    #[clap(long, default_value = "8", value_parser = clap::value_parser!(u16).range(1..))]
+    concurrency: u16,
+
+    /// Read the defaults of the options from the file instead of
+    /// `~/.config/oss-info-maven/config.toml`.
+    ///
+    /// The file is the TOML of `option_name = value` such as `format = "notice"`,
+    /// `exclude = ["com.mycompany.*"]` and `modules = true`, and the keys of `[notice]` are
+    /// prefixed with `notice-`. The `OSS_INFO_MAVEN_<OPTION_NAME>` environment variables such as
+    /// `OSS_INFO_MAVEN_FORMAT=notice` override the file and the command line options override
+    /// both. The command line replaces the lists and `--flag=false` turns off the flag of the file.
+    #[clap(long)]
+    config: Option<PathBuf>,
+
+    /// Generate shell completions. Use the `completions` subcommand instead.
+    #[arg(long, exclusive = true, hide = true)]
     completion: Option<clap_complete::Shell>,
+
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Print the POM and the versions of an artifact. The latest version is used if the
+    /// coordinate has no version. e.g. `info androidx.core:core-ktx`
+    Info { coordinate: Coordinate },
+
+    /// Print every path of the dependency trees of the Gradle output that pulls in the artifact
+    /// with the requested and the selected versions like the `dependencyInsight` task. The Gradle
+    /// output is read from `--input` or stdin. e.g. `insight androidx.annotation:annotation`
+    Insight { module_id: String },
+
+    /// Check that Google Maven, Maven Central and Gradle Plugin Portal including the mirrors and
+    /// the credentials of `--use-maven-settings` are reachable and resolve a probe artifact, and
+    /// print the latency per repository.
+    CheckRepos {
+        /// Probe artifact of all repositories instead of `androidx.annotation:annotation` for
+        /// Google Maven, `junit:junit` for Maven Central and the plugin marker of
+        /// `org.jetbrains.kotlin.android` for Gradle Plugin Portal.
+        #[arg(long)]
+        probe: Option<Coordinate>,
+    },
+
+    /// Generate the shell completions.
+    Completions {
+        shell: clap_complete::Shell,
+
+        /// Write the file to the directory instead of stdout.
+        #[arg(long)]
+        out_dir: Option<PathBuf>,
+    },
+
+    /// Generate the man pages of the command and the subcommands.
+    Man {
+        /// Write the `.1` files to the directory instead of writing the page of the command to
+        /// stdout.
+        #[arg(long)]
+        out_dir: Option<PathBuf>,
+    },
+}
+
+/// Number of the versions of the "Recent" of the `info` subcommand.
+const INFO_RECENT_VERSIONS: usize = 5;
+
+#[derive(Clone, Copy, ValueEnum)]
+enum InputFormat {
+    /// Output of the `dependencies` task.
+    Text,
+
+    /// `build/reports/project/dependencies/js/*.js` of the `htmlDependencyReport` task.
+    HtmlReport,
+
+    /// `gradle.lockfile` that is written by `--write-locks`.
+    Lockfile,
+
+    /// `maven_install.json` of Bazel that is written by `rules_jvm_external`.
+    MavenInstall,
+
+    /// `<dependencies>` of the `pom.xml` of a Maven project.
+    Pom,
+
+    /// `--format jsonl` of the previous run that is re-processed without the requests except for
+    /// the artifacts that failed to resolve.
+    Jsonl,
 }
 
 #[derive(Clone, ValueEnum)]
 enum FormatType {
     Csv,
+    Notice,
+    Obligations,
+    Xlsx,
+    Template,
+    Dot,
+    Mermaid,
+    Jsonl,
+}
+
+#[derive(Clone, Copy, Eq, PartialEq, ValueEnum)]
+enum DuplicateVersions {
+    /// A row per input version.
+    Keep,
+
+    /// A row per `group:artifact` that is resolved with the highest version.
+    Collapse,
+}
+
+#[derive(Clone, Copy, Eq, PartialEq, ValueEnum)]
+enum SortKey {
+    /// `<name>` of the POM that falls back to the artifact id. Case-insensitive.
+    Name,
+
+    /// The "Licenses" column.
+    License,
+
+    /// The group id and then the artifact id.
+    Group,
+}
+
+#[derive(Clone, Copy, Eq, PartialEq, ValueEnum)]
+enum GroupKey {
+    Group,
+    License,
+}
+
+#[derive(Clone, Copy, Eq, PartialEq, ValueEnum)]
+enum VersionPolicy {
+    /// The highest version of the `<versions>` that is not a pre-release such as `alpha`.
+    Release,
+
+    /// `<latest>` that may be a pre-release.
+    Latest,
+
+    /// The version of the input. The dependency that has no version falls back to the default.
+    Input,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum LogFormat {
+    Text,
+
+    /// JSON per line.
+    Json,
+}
+
+#[derive(Clone, Eq, PartialEq, ValueEnum)]
+enum EnrichType {
+    /// https://clearlydefined.io/
+    #[value(name = "clearlydefined")]
+    ClearlyDefined,
+}
+
+const EXIT_STATUS_HELP: &str = "\
+Exit status:
+  0    Success
+  1    Invalid arguments, invalid input or an unexpected error
+  2    Some malformed lines of the input were skipped with `--skip-invalid` or some artifacts failed to resolve or timed out
+  3    The resolutions have drifted from `--locked`
+  4    Some artifacts have the unknown license with `--fail-on-unknown-license`
+  130  Interrupted by Ctrl-C after writing the dependencies that were resolved so far";
+
+const INTERACTIVE_HELP: &str = "\
+Reading the dependencies from the terminal.
+Type a coordinate such as `androidx.core:core-ktx:1.12.0` per line and press Ctrl-D
+(Ctrl-Z and Enter on Windows) to finish.
+Pipe the output of `./gradlew dependencies` or use `--input` to read the Gradle output.
+See `--help` for the details.";
+
+/// Stable exit statuses for CI. Keep in sync with [EXIT_STATUS_HELP].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum ExitStatus {
+    Success = 0,
+    Error = 1,
+    ResolveFailure = 2,
+    LockMismatch = 3,
+    UnknownLicense = 4,
+    Interrupted = 130,
+}
+
+impl From<ExitStatus> for ExitCode {
+    fn from(value: ExitStatus) -> Self {
+        ExitCode::from(value as u8)
+    }
 }
 
 #[tokio::main]
-async fn main() -> Fallible<()> {
-    tracing_subscriber::fmt()
-        .with_writer(std::io::stderr)
-        .with_env_filter(tracing_subscriber::EnvFilter::from_default_env())
-        .init();
+async fn main() -> Fallible<ExitCode> {
+    let started = Instant::now();
+    let command = match layered_command() {
+        Ok(data) => data,
+        Err(e) => {
+            eprintln!("error: {:#}", e);
+            return Ok(ExitStatus::Error.into());
+        }
+    };
+    let opt = match command
+        .try_get_matches()
+        .and_then(|data| Opt::from_arg_matches(&data))
+    {
+        Ok(data) => data,
+        // clap exits with 2 for the usage error that conflicts with ExitStatus::ResolveFailure.
+        Err(e) if e.use_stderr() => {
+            e.print()?;
+            return Ok(ExitStatus::Error.into());
+        }
+        Err(e) => e.exit(),
+    };
+
+    #[cfg(feature = "otel")]
+    let (otel_layer, _otel_guard) = if opt.otel {
+        let tracer = match create_otel_tracer() {
+            Ok(data) => data,
+            Err(e) => {
+                eprintln!("error: {:#}", e);
+                return Ok(ExitStatus::Error.into());
+            }
+        };
+        (
+            Some(tracing_opentelemetry::layer().with_tracer(tracer)),
+            Some(OtelGuard),
+        )
+    } else {
+        (None, None)
+    };
+    #[cfg(not(feature = "otel"))]
+    let otel_layer = None::<tracing_subscriber::layer::Identity>;
+
+    let subscriber = tracing_subscriber::registry()
+        .with(otel_layer)
+        .with(tracing_subscriber::EnvFilter::from_default_env());
+    let fmt_layer = tracing_subscriber::fmt::layer().with_writer(std::io::stderr);
+    match opt.log_format {
+        LogFormat::Text => subscriber.with(fmt_layer).init(),
+        // the span list has the `dep_name` of the `retrieve_task` span.
+        LogFormat::Json => subscriber
+            .with(
+                fmt_layer
+                    .json()
+                    .with_current_span(true)
+                    .with_span_list(true),
+            )
+            .init(),
+    }
+
+    let maven_settings = if opt.use_maven_settings {
+        Some(read_maven_settings()?)
+    } else {
+        None
+    };
 
-    let opt = Opt::parse();
+    match &opt.command {
+        Some(Command::Info { coordinate }) => {
+            print_info(
+                std::io::stdout().lock(),
+                create_client(&opt, maven_settings.as_ref())?,
+                &repository_config(maven_settings.as_ref()),
+                coordinate,
+            )
+            .await?;
+            return Ok(ExitStatus::Success.into());
+        }
+        Some(Command::Insight { module_id }) => {
+            let found = print_insight(std::io::stdout().lock(), &opt.input, module_id)?;
+            if !found {
+                error!(%module_id, "no path pulls in the artifact");
+                return Ok(ExitStatus::Error.into());
+            }
+            return Ok(ExitStatus::Success.into());
+        }
+        Some(Command::CheckRepos { probe }) => {
+            let healthy = print_repository_health(
+                std::io::stdout().lock(),
+                create_client(&opt, maven_settings.as_ref())?,
+                &repository_config(maven_settings.as_ref()),
+                probe.as_ref(),
+            )
+            .await?;
+            if !healthy {
+                error!("some repositories are unhealthy");
+                return Ok(ExitStatus::ResolveFailure.into());
+            }
+            return Ok(ExitStatus::Success.into());
+        }
+        Some(Command::Completions { shell, out_dir }) => {
+            write_completions(*shell, out_dir.as_deref())?;
+            return Ok(ExitStatus::Success.into());
+        }
+        Some(Command::Man { out_dir }) => {
+            write_man_pages(out_dir.as_deref())?;
+            return Ok(ExitStatus::Success.into());
+        }
+        None => {}
+    }
 
     if let Some(shell) = opt.completion {
-        clap_complete::generate(
-            shell,
-            &mut Opt::command(),
-            env!("CARGO_PKG_NAME"),
-            &mut std::io::stdout(),
-        );
-        return Ok(());
+        write_completions(shell, None)?;
+        return Ok(ExitStatus::Success.into());
     }
 
     info!("hello");
 
-    let lines = if opt.skip_pretty {
-        parse_prettied_dependencies_string(BufReader::new(std::io::stdin()))?
+    let mut notice_vars = HashMap::from([(
+        "year".to_string(),
+        year_of_unix_time(
+            SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .context("unexpected system time")?
+                .as_secs(),
+        )
+        .to_string(),
+    )]);
+    notice_vars.extend(opt.notice_var.iter().cloned());
+    let read_notice_template = |path: &Option<PathBuf>| -> Fallible<Option<String>> {
+        let path = match path {
+            Some(data) => data,
+            None => return Ok(None),
+        };
+        let template = std::fs::read_to_string(path)
+            .with_context(|| format!("failed to read the template: {}", path.display()))?;
+        render_notice_template(&template, &notice_vars)
+            .with_context(|| format!("failed to render the template: {}", path.display()))
+            .map(Some)
+    };
+    let notice_header = read_notice_template(&opt.notice_header)?;
+    let notice_footer = read_notice_template(&opt.notice_footer)?;
+    let template = match &opt.template {
+        Some(path) => {
+            let template = std::fs::read_to_string(path)
+                .with_context(|| format!("failed to read the template: {}", path.display()))?;
+            check_template(&template)
+                .with_context(|| format!("failed to parse the template: {}", path.display()))?;
+            Some(template)
+        }
+        None => None,
+    };
+    let overrides = match &opt.overrides {
+        Some(path) => read_overrides(path)?,
+        None => vec![],
+    };
+
+    ensure!(
+        !matches!(opt.format, FormatType::Dot | FormatType::Mermaid)
+            || (matches!(opt.input_format, InputFormat::Text) && !opt.skip_pretty),
+        "--format dot and --format mermaid require the dependency trees of --input-format text",
+    );
+    ensure!(
+        !matches!(opt.format, FormatType::Jsonl)
+            || !(opt.check_license_urls
+                || opt.check_vulnerabilities
+                || !opt.packaging_filter.is_empty()
+                || opt.only_unknown
+                || opt.overrides.is_some()
+                || opt.infer_group_licenses
+                || opt.sort.is_some()
+                || opt.group_by.is_some()),
+        "--format jsonl writes the artifacts as soon as they are resolved and cannot be combined with the options that need all of them",
+    );
+
+    let mut lines = BTreeMap::<Coordinate, InputEntry>::new();
+    let mut projects = BTreeMap::<String, InputEntry>::new();
+    let mut line_errors = vec![];
+    let mut trees = vec![];
+    let mut resolved_inputs = HashMap::new();
+    if opt.input.is_empty() {
+        let stdin = std::io::stdin();
+        let input = if stdin.is_terminal() {
+            // nothing is piped. accept the coordinates that are typed instead of blocking silently.
+            eprintln!("{}", INTERACTIVE_HELP);
+            read_prettied_input(BufReader::new(stdin), opt.strict)?
+        } else {
+            read_input(BufReader::new(stdin), &opt)?
+        };
+        line_errors.extend(input.errors.into_iter().map(|e| ("stdin".to_string(), e)));
+        trees.extend(input.trees);
+        resolved_inputs.extend(input.resolved);
+        for (coordinate, annotation, modules) in input.dependencies {
+            lines
+                .entry(coordinate)
+                .or_default()
+                .merge(annotation, modules, None);
+        }
+        for (project, modules) in input.projects {
+            projects
+                .entry(project)
+                .or_default()
+                .merge(Annotation::default(), modules, None);
+        }
+    } else {
+        for path in &opt.input {
+            let file = File::open(path)
+                .with_context(|| format!("failed to open the input: {}", path.display()))?;
+            let source = path.display().to_string();
+            let input = read_input(BufReader::new(file), &opt)
+                .with_context(|| format!("failed to parse the input: {}", source))?;
+            line_errors.extend(input.errors.into_iter().map(|e| (source.clone(), e)));
+            trees.extend(input.trees);
+            resolved_inputs.extend(input.resolved);
+            for (coordinate, annotation, modules) in input.dependencies {
+                lines
+                    .entry(coordinate)
+                    .or_default()
+                    .merge(annotation, modules, Some(&source));
+            }
+            for (project, modules) in input.projects {
+                projects.entry(project).or_default().merge(
+                    Annotation::default(),
+                    modules,
+                    Some(&source),
+                );
+            }
+        }
+    }
+
+    for (source, e) in &line_errors {
+        error!(%source, "malformed {}", e);
+    }
+    if !line_errors.is_empty() && !opt.skip_invalid {
+        error!(
+            count = line_errors.len(),
+            "some lines of the input are malformed. fix them or use `--skip-invalid` to continue with the valid lines"
+        );
+        return Ok(ExitStatus::Error.into());
+    }
+
+    let mut excludes = opt.exclude.clone();
+    for path in &opt.exclude_file {
+        let file = File::open(path)
+            .with_context(|| format!("failed to open the exclude file: {}", path.display()))?;
+        excludes.extend(
+            read_exclude_patterns(BufReader::new(file))
+                .with_context(|| format!("failed to parse the exclude file: {}", path.display()))?,
+        );
+    }
+    lines.retain(|coordinate, _| {
+        let excluded = excludes.iter().any(|pattern| pattern.is_match(coordinate));
+        if excluded {
+            info!(dep_name = %coordinate, "exclude");
+        }
+        !excluded
+    });
+
+    let mut output = match opt.flush_interval {
+        Some(_) => Output::create_direct(opt.output.as_deref(), opt.force)?,
+        None => Output::create(opt.output.as_deref(), opt.force)?,
+    };
+
+    if opt.conflicts {
+        write_conflicts(&mut output, &lines, 1 < opt.input.len())?;
+        output.commit()?;
+        info!("bye");
+        return Ok(ExitStatus::Success.into());
+    }
+
+    let input_versions = match opt.duplicate_versions {
+        DuplicateVersions::Keep => {
+            for conflict in find_conflicts(lines.keys()) {
+                warn!(
+                    dep_name = conflict.module_id,
+                    versions = conflict.versions.join(", "),
+                    "multiple versions are written as the separate rows. use `--duplicate-versions collapse` to write a row"
+                );
+            }
+            HashMap::new()
+        }
+        DuplicateVersions::Collapse => collapse_versions(&mut lines),
+    };
+
+    let annotations = lines
+        .iter()
+        .filter(|(_, entry)| !entry.annotation.is_empty())
+        .map(|(coordinate, entry)| (coordinate.clone(), entry.annotation.clone()))
+        .collect::<HashMap<_, _>>();
+
+    let sources = if 1 < opt.input.len() {
+        lines
+            .iter()
+            .map(|(coordinate, entry)| (coordinate.clone(), entry.sources.clone()))
+            .collect::<HashMap<_, _>>()
+    } else {
+        HashMap::new()
+    };
+
+    let modules = if opt.modules {
+        lines
+            .iter()
+            .map(|(coordinate, entry)| (coordinate.clone(), entry.modules.clone()))
+            .collect::<HashMap<_, _>>()
     } else {
-        let mut reader = BufReader::new(std::io::stdin());
-        parse_dependencies_string(&mut reader)?
+        HashMap::new()
     };
 
-    let mut dep_map = lines.into_iter().fold(IndexMap::new(), |mut acc, data| {
+    let mut dep_map = lines.into_keys().fold(IndexMap::new(), |mut acc, data| {
         acc.insert(data, None);
         acc
     });
 
-    let client = reqwest::Client::builder().build().expect("Client::new()");
-    let semaphore = Arc::new(Semaphore::new(8));
-    let mut futs = futures::stream::FuturesUnordered::new();
-    for dep_name in dep_map.keys() {
+    let mut restored = vec![];
+    if !resolved_inputs.is_empty() {
+        for (coordinate, resolved) in dep_map.iter_mut() {
+            if let Some(data) = resolved_inputs.remove(coordinate) {
+                debug!(dep_name = %coordinate, "restore");
+                *resolved = Some(data);
+                restored.push(coordinate.clone());
+            }
+        }
+        info!(restored = restored.len(), "restored the input");
+    }
+    if let Some(path) = opt.state_file.as_ref().filter(|data| data.exists()) {
+        let file = File::open(path)
+            .with_context(|| format!("failed to open the state file: {}", path.display()))?;
+        let mut previous = read_state(BufReader::new(file))
+            .with_context(|| format!("failed to parse the state file: {}", path.display()))?;
+        for (coordinate, resolved) in dep_map
+            .iter_mut()
+            .filter(|(_, resolved)| resolved.is_none())
+        {
+            if let Some(data) = previous.remove(coordinate) {
+                debug!(dep_name = %coordinate, "restore");
+                *resolved = Some(data);
+                restored.push(coordinate.clone());
+            }
+        }
+        info!(restored = restored.len(), "restored the state");
+    }
+
+    let locked =
+        match &opt.locked {
+            Some(path) => {
+                let file = File::open(path)
+                    .with_context(|| format!("failed to open the lock file: {}", path.display()))?;
+                Some(read_lock(BufReader::new(file)).with_context(|| {
+                    format!("failed to parse the lock file: {}", path.display())
+                })?)
+            }
+            None => None,
+        };
+
+    let client = create_client(&opt, maven_settings.as_ref())?;
+    let repositories = Arc::new(repository_config(maven_settings.as_ref()));
+    let semaphore = Arc::new(Semaphore::new(opt.concurrency.into()));
+
+    if opt.outdated {
+        let has_error = write_outdated(&mut output, &client, &repositories, dep_map.keys()).await?;
+        output.commit()?;
+        if has_error {
+            error!("finished but an error occurred in some requests");
+            return Ok(ExitStatus::ResolveFailure.into());
+        }
+        info!("bye");
+        return Ok(ExitStatus::Success.into());
+    }
+
+    let progress = (!opt.no_progress && std::io::stderr().is_terminal())
+        .then(|| Arc::new(Progress::new(dep_map.len() - restored.len())));
+    // the POM doesn't depend on the classifier and the packaging so the rows of the same
+    // version share the request.
+    let mut pom_futs = HashMap::new();
+    let mut spawn_task = |coordinate: &Coordinate, version_policy: Option<VersionPolicy>| {
+        let pom_fut = pom_futs
+            .entry(pom_coordinate(coordinate))
+            .or_insert_with_key(|pom_coordinate| {
+                let client = client.clone();
+                let repositories = repositories.clone();
+                let pom_coordinate = pom_coordinate.clone();
+                let prefer_ascii = opt.prefer_ascii;
+                let enrich_clearly_defined = opt.enrich.contains(&EnrichType::ClearlyDefined);
+                let kmp_platform = opt.kmp_platform.clone();
+                let ivy_repositories = opt.ivy_repository.clone();
+                async move {
+                    let (mut pom, lock_entry) = retrieve_maven_or_ivy_lib(
+                        &client,
+                        &pom_coordinate,
+                        &repositories,
+                        &ivy_repositories,
+                        version_policy,
+                    )
+                    .await?;
+                    if let Some(platform) = &kmp_platform {
+                        match retrieve_platform_maven_lib(
+                            client.clone(),
+                            &pom_coordinate,
+                            &repositories,
+                            &pom,
+                            platform,
+                        )
+                        .await
+                        {
+                            Ok(Some(data)) => {
+                                info!(target = %data.artifact_id, "follow the platform artifact");
+                                pom = data;
+                            }
+                            Ok(None) => {}
+                            Err(e) => warn!(?e, "failed to retrieve the platform artifact"),
+                        }
+                    }
+                    if enrich_clearly_defined && pom.licenses.is_empty() {
+                        match retrieve_clearly_defined_licenses(client, &pom_coordinate, &pom).await
+                        {
+                            Ok(licenses) => pom.licenses = licenses,
+                            Err(e) => warn!(?e, "failed to retrieve licenses from ClearlyDefined"),
+                        }
+                    }
+                    if prefer_ascii {
+                        transform_pom_text(&mut pom, &AsciiTransformer);
+                    }
+                    Ok::<_, anyhow::Error>((pom, lock_entry))
+                }
+                .map_err(Arc::new)
+                .boxed()
+                .shared()
+            })
+            .clone();
         let client = client.clone();
+        let repositories = repositories.clone();
         let semaphore = semaphore.clone();
-        let dep_name = dep_name.to_string();
-        let span = info_span!("retrieve_task", %dep_name);
-        futs.push(tokio::task::spawn(
+        let coordinate = coordinate.clone();
+        let artifact_digest = opt.artifact_digest;
+        let kmp_platform = opt.kmp_platform.clone();
+        let coordinate_timeout = opt.coordinate_timeout.map(Duration::from_secs);
+        let progress = progress.clone();
+        let span = info_span!("retrieve_task", dep_name = %coordinate);
+        tokio::task::spawn(
             async move {
                 let _permit = semaphore.acquire().await.unwrap();
-                let ret = retrieve_maven_lib(client, &dep_name).await;
-                (dep_name, ret)
+                let started = Instant::now();
+                let name = coordinate.to_string();
+                if let Some(progress) = &progress {
+                    progress.start(&name);
+                }
+                let resolve = async {
+                    match pom_fut.await {
+                        Ok((pom, lock_entry)) if artifact_digest => {
+                            let artifact_coordinate =
+                                artifact_coordinate(&coordinate, &pom, kmp_platform.as_deref());
+                            retrieve_artifact_file(
+                                client,
+                                &artifact_coordinate,
+                                &repositories,
+                                &pom,
+                            )
+                            .await
+                            .map(|artifact_file| (pom, artifact_file, lock_entry))
+                        }
+                        Ok((pom, lock_entry)) => Ok((pom, None, lock_entry)),
+                        Err(e) => Err(anyhow!("{:#}", e)),
+                    }
+                };
+                let ret = match coordinate_timeout {
+                    Some(timeout) => match tokio::time::timeout(timeout, resolve).await {
+                        Ok(data) => data,
+                        Err(_) => Err(anyhow!("timed out after {} secs", timeout.as_secs())),
+                    },
+                    None => resolve.await,
+                };
+                if let Some(progress) = &progress {
+                    progress.finish(&name, ret.is_ok());
+                }
+                (coordinate, ret, started.elapsed())
             }
             .instrument(span),
-        ));
+        )
+    };
+    let mut futs = dep_map
+        .iter()
+        .filter(|(_, resolved)| resolved.is_none())
+        .map(|(coordinate, _)| spawn_task(coordinate, opt.version_policy))
+        .collect::<futures::stream::FuturesUnordered<_>>();
+    // the dependencies of the `futs` that are cancelled at the deadline.
+    let mut unfinished = dep_map
+        .iter()
+        .filter(|(_, resolved)| resolved.is_none())
+        .map(|(coordinate, _)| coordinate.clone())
+        .collect::<HashSet<_>>();
+
+    // the license URLs and the vulnerabilities are checked after all requests so
+    // `--flush-interval` conflicts with them.
+    let no_link_statuses = HashMap::new();
+    let no_vulnerabilities = HashMap::new();
+    let (mut incremental_csv, mut output) = match (opt.flush_interval, &opt.format) {
+        (Some(interval), FormatType::Csv) => (
+            Some(IncrementalCsv::new(
+                TableReport::new(
+                    &opt,
+                    &annotations,
+                    &modules,
+                    &sources,
+                    &no_link_statuses,
+                    &no_vulnerabilities,
+                    &projects,
+                )
+                .with_input_versions(&input_versions)
+                // the run can be cancelled after the header is written.
+                .with_status()
+                .with_columns(&opt.columns)?,
+                output,
+                Duration::from_secs(interval),
+                dep_map.len(),
+            )?),
+            None,
+        ),
+        _ => (None, Some(output)),
+    };
+    if let Some(incremental_csv) = &mut incremental_csv {
+        for coordinate in &restored {
+            incremental_csv.write_finished(&dep_map, coordinate)?;
+        }
+    }
+    let mut jsonl_output = output
+        .as_mut()
+        .filter(|_| matches!(opt.format, FormatType::Jsonl));
+    if let Some(output) = &mut jsonl_output {
+        for coordinate in &restored {
+            let resolved = dep_map[coordinate].as_ref();
+            write_jsonl_record(
+                &mut **output,
+                coordinate,
+                resolved.map(|(pom, artifact_file)| (pom, artifact_file.as_ref())),
+            )?;
+        }
     }
 
+    let mut expanded_boms = vec![];
+    if opt.expand_bom {
+        for coordinate in &restored {
+            if let Some((pom, _)) = &dep_map[coordinate] {
+                let artifacts = bom_artifacts(&dep_map, pom);
+                if !artifacts.is_empty() {
+                    info!(dep_name = %coordinate, count = artifacts.len(), "expand the BOM");
+                    expanded_boms.push(coordinate.clone());
+                }
+                for artifact in artifacts {
+                    futs.push(spawn_task(&artifact, Some(VersionPolicy::Input)));
+                    unfinished.insert(artifact.clone());
+                    dep_map.insert(artifact, None);
+                    if let Some(progress) = &progress {
+                        progress.add_total(1);
+                    }
+                }
+            }
+        }
+    }
+
+    let mut lock_entries = HashMap::new();
     let mut has_error = false;
-    while let Some(data) = futs.next().await {
-        let (name, pom) = match data {
-            Ok((name, Ok(pom))) => (name, pom),
-            Ok((name, Err(e))) => {
-                warn!(%name, ?e, "failed to request artifact info.");
+    let mut durations = vec![];
+    let mut failures = 0;
+    let mut pending = 0;
+    let mut cancelled = HashMap::new();
+    let ctrl_c = tokio::signal::ctrl_c();
+    tokio::pin!(ctrl_c);
+    let deadline = async {
+        match opt.deadline {
+            Some(secs) => {
+                let deadline = started + Duration::from_secs(secs);
+                tokio::time::sleep_until(tokio::time::Instant::from_std(deadline)).await
+            }
+            None => futures::future::pending().await,
+        }
+    };
+    tokio::pin!(deadline);
+    loop {
+        let data = tokio::select! {
+            data = futs.next() => match data {
+                Some(data) => data,
+                None => break,
+            },
+            Ok(()) = &mut ctrl_c => {
+                pending = futs.len();
+                warn!(pending, "interrupted. write the dependencies that were resolved so far");
+                for fut in futs.iter() {
+                    fut.abort();
+                }
+                cancelled.extend(
+                    std::mem::take(&mut unfinished)
+                        .into_iter()
+                        .map(|data| (data, Cancellation::Interrupted)),
+                );
+                break;
+            }
+            () = &mut deadline => {
+                for fut in futs.iter() {
+                    fut.abort();
+                }
+                warn!(
+                    pending = unfinished.len(),
+                    "the deadline has passed. cancel the dependencies that are not resolved"
+                );
+                cancelled.extend(
+                    std::mem::take(&mut unfinished)
+                        .into_iter()
+                        .map(|data| (data, Cancellation::TimedOut)),
+                );
+                break;
+            }
+        };
+        let (coordinate, resolved) = match data {
+            Ok((coordinate, Ok((pom, artifact_file, lock_entry)), elapsed)) => {
+                lock_entries.insert(coordinate.clone(), lock_entry);
+                durations.push((coordinate.to_string(), elapsed));
+                (coordinate, Some((pom, artifact_file)))
+            }
+            Ok((coordinate, Err(e), elapsed)) => {
+                warn!(name = %coordinate, ?e, "failed to request artifact info.");
                 has_error = true;
-                continue;
+                failures += 1;
+                durations.push((coordinate.to_string(), elapsed));
+                (coordinate, None)
             }
             Err(e) => {
                 error!(?e, "a request was aborted");
                 bail!("a request was aborted");
             }
         };
-        dep_map[&name] = Some(pom);
-    }
-
-    match opt.format {
-        FormatType::Csv => {
-            let mut writer = csv::WriterBuilder::new().from_writer(std::io::stdout());
-            writer.write_record([
-                "Dependency",
-                "Version (Input)",
-                "Version (Latest)",
-                "Packaging",
-                "Name",
-                "Description",
-                "Licenses",
-            ])?;
-            for (dep_name, pom) in dep_map {
-                let dep_name_segments = dep_name.split(':').collect::<Vec<_>>();
-                let input_version = match dep_name_segments.get(2) {
-                    Some(data) => data.to_string(),
-                    None => "".into(),
-                };
-                let pom = match pom {
-                    Some(pom) => pom,
+        unfinished.remove(&coordinate);
+        // the licenses of the overrides are reviewed.
+        if let Some((pom, _)) = resolved
+            .as_ref()
+            .filter(|_| find_override(&overrides, &coordinate).is_none())
+        {
+            for license in pom.licenses.iter().filter(|data| data.is_proprietary()) {
+                warn!(dep_name = %coordinate, %license, "the license is not open source");
+            }
+            if let Some(project_license) = &opt.project_license {
+                let compatibility = check_compatibility(project_license, &pom.licenses);
+                if let Compatibility::Incompatible(reason) = &compatibility {
+                    warn!(dep_name = %coordinate, %reason, "the license is incompatible");
+                }
+            }
+        }
+        dep_map[&coordinate] = resolved;
+        if let Some((pom, _)) = dep_map[&coordinate].as_ref().filter(|_| opt.expand_bom) {
+            let artifacts = bom_artifacts(&dep_map, pom);
+            if !artifacts.is_empty() {
+                info!(dep_name = %coordinate, count = artifacts.len(), "expand the BOM");
+                for artifact in artifacts {
+                    futs.push(spawn_task(&artifact, Some(VersionPolicy::Input)));
+                    unfinished.insert(artifact.clone());
+                    dep_map.insert(artifact, None);
+                    if let Some(progress) = &progress {
+                        progress.add_total(1);
+                    }
+                }
+                expanded_boms.push(coordinate);
+                continue;
+            }
+        }
+        if let Some(incremental_csv) = &mut incremental_csv {
+            incremental_csv.write_finished(&dep_map, &coordinate)?;
+        }
+        if let Some(output) = &mut jsonl_output {
+            let resolved = dep_map[&coordinate].as_ref();
+            write_jsonl_record(
+                &mut **output,
+                &coordinate,
+                resolved.map(|(pom, artifact_file)| (pom, artifact_file.as_ref())),
+            )?;
+        }
+    }
+
+    for (coordinate, cancellation) in dep_map
+        .keys()
+        .filter_map(|data| Some((data, *cancelled.get(data)?)))
+    {
+        match cancellation {
+            Cancellation::TimedOut => {
+                warn!(dep_name = %coordinate, "timed out by the deadline");
+                has_error = true;
+                failures += 1;
+            }
+            Cancellation::Interrupted => info!(dep_name = %coordinate, "interrupted"),
+        }
+        if let Some(incremental_csv) = &mut incremental_csv {
+            incremental_csv.write_cancelled(&dep_map, coordinate, cancellation)?;
+        }
+        if let Some(output) = &mut jsonl_output {
+            write_jsonl_record(&mut **output, coordinate, None)?;
+        }
+    }
+
+    if let Some(progress) = &progress {
+        progress.clear();
+    }
+
+    // the handler of `tokio::signal` replaces the default one so Ctrl-C after the requests skips
+    // the rest of the checks and the output is written as the interrupted one.
+    let ctrl_c = tokio::signal::ctrl_c();
+    tokio::pin!(ctrl_c);
+    let mut interrupted = 0 < pending;
+
+    if let Some(path) = &opt.state_file {
+        let mut file = AtomicFile::create(path, true)?;
+        write_state(
+            &mut file,
+            dep_map.iter().filter_map(|(coordinate, resolved)| {
+                let (pom, artifact_file) = resolved.as_ref()?;
+                Some((coordinate, pom, artifact_file.as_ref()))
+            }),
+        )
+        .with_context(|| format!("failed to write the state file: {}", path.display()))?;
+        file.commit()?;
+    }
+
+    if let Some(path) = &opt.lock_output {
+        let mut file = AtomicFile::create(path, true)?;
+        write_lock(
+            &mut file,
+            dep_map
+                .keys()
+                .filter_map(|coordinate| Some((coordinate, lock_entries.get(coordinate)?))),
+        )
+        .with_context(|| format!("failed to write the lock file: {}", path.display()))?;
+        file.commit()?;
+    }
+
+    let has_drift = match &locked {
+        Some(locked) => {
+            let mut has_drift = false;
+            for (coordinate, lock_entry) in &lock_entries {
+                match locked.get(coordinate) {
+                    Some(data) => {
+                        for drift in data.drifts(lock_entry) {
+                            warn!(dep_name = %coordinate, %drift, "the resolution has drifted");
+                            has_drift = true;
+                        }
+                    }
                     None => {
-                        info!(%dep_name, "skip");
-                        continue;
+                        warn!(dep_name = %coordinate, "the dependency is not locked");
+                        has_drift = true;
                     }
-                };
+                }
+            }
+            for coordinate in locked.keys().filter(|data| !dep_map.contains_key(*data)) {
+                warn!(dep_name = %coordinate, "the locked dependency is not in the input");
+                has_drift = true;
+            }
+            has_drift
+        }
+        None => false,
+    };
 
-                writer.write_record(&[
-                    format!(
-                        "{}:{}",
-                        dep_name_segments
-                            .first()
-                            .expect("unexpected format: group id"),
-                        dep_name_segments
-                            .get(1)
-                            .expect("unexpected format: artifact name"),
-                    ),
-                    input_version,
-                    pom.version.unwrap_or_else(|| "".into()),
-                    pom.packaging.unwrap_or_else(|| "".into()),
-                    pom.name.unwrap_or_else(|| "".into()),
-                    pom.description.unwrap_or_else(|| "".into()),
-                    pom.licenses
-                        .iter()
-                        .map(SPDX::to_string)
-                        .collect::<Vec<_>>()
-                        .join("/"),
-                ])?;
-            }
-
-            writer.flush()?;
+    // the BOM carries no license of the managed artifacts.
+    for coordinate in &expanded_boms {
+        dep_map.shift_remove(coordinate);
+    }
+
+    let mut overridden = OverriddenMap::new();
+    let mut license_origins = HashMap::new();
+    for (coordinate, resolved) in dep_map.iter_mut() {
+        let (pom, _) = match resolved {
+            Some(data) => data,
+            None => continue,
+        };
+        if let Some(data) = find_override(&overrides, coordinate) {
+            info!(dep_name = %coordinate, "override the licenses");
+            let original = std::mem::replace(&mut pom.licenses, data.licenses.clone());
+            overridden.insert(coordinate.clone(), (original, data.note.clone()));
+            license_origins.insert(
+                coordinate.clone(),
+                LicenseOrigin::Overridden {
+                    note: data.note.clone(),
+                },
+            );
+        }
+    }
+
+    let inferred = if opt.infer_group_licenses {
+        let inferred = infer_group_licenses(dep_map.iter().filter_map(|(coordinate, resolved)| {
+            let (pom, _) = resolved.as_ref()?;
+            Some((
+                coordinate,
+                pom.version.as_deref().or(coordinate.version.as_deref()),
+                pom.licenses.as_slice(),
+            ))
+        }));
+        for (coordinate, data) in &inferred {
+            info!(
+                dep_name = %coordinate,
+                source = %data.source,
+                "infer the licenses from the sibling"
+            );
+            if let Some((pom, _)) = &mut dep_map[coordinate] {
+                pom.licenses = data.licenses.clone();
+            }
+            license_origins.insert(
+                coordinate.clone(),
+                LicenseOrigin::Inferred {
+                    source: data.source.clone(),
+                },
+            );
         }
+        inferred
+    } else {
+        HashMap::new()
+    };
+
+    if !opt.packaging_filter.is_empty() {
+        dep_map.retain(|coordinate, resolved| match resolved {
+            Some((pom, _)) => {
+                let packaging = packaging(coordinate, pom);
+                let retain = opt.packaging_filter.iter().any(|data| data == packaging);
+                if !retain {
+                    debug!(dep_name = %coordinate, %packaging, "filter out the packaging");
+                }
+                retain
+            }
+            None => true,
+        });
     }
 
+    if opt.only_unknown {
+        dep_map.retain(|coordinate, resolved| match resolved {
+            Some((pom, _)) => {
+                // the inferred licenses need the review.
+                let retain =
+                    has_unknown_license(&pom.licenses) || inferred.contains_key(coordinate);
+                if !retain {
+                    debug!(dep_name = %coordinate, "filter out the known license");
+                }
+                retain
+            }
+            None => true,
+        });
+    }
+
+    if opt.sort.is_some() || opt.group_by.is_some() {
+        sort_dep_map(
+            &mut dep_map,
+            opt.sort,
+            opt.group_by,
+            opt.license_conjunction,
+        );
+    }
+
+    // the deadline has passed so no more request.
+    let link_statuses = if opt.check_license_urls && cancelled.is_empty() {
+        let urls = dep_map
+            .values()
+            .flatten()
+            .flat_map(|(pom, _)| &pom.license_urls)
+            .cloned()
+            .collect::<BTreeSet<_>>();
+        let link_statuses = futures::stream::iter(urls)
+            .map(|url| {
+                let client = client.clone();
+                async move {
+                    let status = check_link(client, &url).await;
+                    (url, status)
+                }
+            })
+            .buffer_unordered(8)
+            .collect::<HashMap<_, _>>();
+        let link_statuses = tokio::select! {
+            data = link_statuses => data,
+            Ok(()) = &mut ctrl_c => {
+                warn!("interrupted. skip the check of the license urls");
+                interrupted = true;
+                HashMap::new()
+            }
+        };
+        for (url, status) in &link_statuses {
+            if status.is_dead() {
+                warn!(%url, %status, "dead license url");
+            }
+        }
+        link_statuses
+    } else {
+        HashMap::new()
+    };
+
+    let vulnerabilities = if opt.check_vulnerabilities && cancelled.is_empty() && !interrupted {
+        let targets = dep_map
+            .iter()
+            .filter_map(|(coordinate, resolved)| {
+                let (pom, _) = resolved.as_ref()?;
+                let version = coordinate.version.as_ref().or(pom.version.as_ref())?;
+                Some((
+                    coordinate.clone(),
+                    Coordinate::new(&coordinate.group_id, &coordinate.artifact_id)
+                        .with_version(version),
+                ))
+            })
+            .collect::<Vec<_>>();
+        let queries = targets
+            .iter()
+            .map(|(_, query)| query.clone())
+            .collect::<Vec<_>>();
+        let retrieved = tokio::select! {
+            data = retrieve_vulnerabilities(client.clone(), &queries) => Some(data),
+            Ok(()) = &mut ctrl_c => {
+                warn!("interrupted. skip the check of the vulnerabilities");
+                interrupted = true;
+                None
+            }
+        };
+        match retrieved {
+            None => HashMap::new(),
+            Some(Ok(data)) => {
+                let vulnerabilities = targets
+                    .into_iter()
+                    .map(|(coordinate, _)| coordinate)
+                    .zip(data)
+                    .filter(|(_, data)| !data.is_empty())
+                    .collect::<HashMap<_, _>>();
+                for (coordinate, data) in &vulnerabilities {
+                    warn!(dep_name = %coordinate, count = data.len(), "vulnerable");
+                }
+                vulnerabilities
+            }
+            Some(Err(e)) => {
+                warn!(?e, "failed to retrieve vulnerabilities from OSV");
+                has_error = true;
+                HashMap::new()
+            }
+        }
+    } else {
+        HashMap::new()
+    };
+
+    if let Some(path) = &opt.emit_bom_pom {
+        let mut file = AtomicFile::create(path, true)?;
+        write_bom_pom(
+            &mut file,
+            &opt.bom_coordinate,
+            dep_map
+                .iter()
+                .filter_map(|(coordinate, resolved)| Some((coordinate, &resolved.as_ref()?.0))),
+        )
+        .with_context(|| format!("failed to write the BOM: {}", path.display()))?;
+        file.commit()?;
+    }
+
+    let (mut output, incremental) = match incremental_csv {
+        Some(incremental_csv) => (incremental_csv.finish()?, true),
+        None => (output.context("the output is missing")?, false),
+    };
+    match opt.format {
+        FormatType::Csv if incremental => {}
+        FormatType::Jsonl => {}
+        FormatType::Csv | FormatType::Template => {
+            let report = TableReport::new(
+                &opt,
+                &annotations,
+                &modules,
+                &sources,
+                &link_statuses,
+                &vulnerabilities,
+                &projects,
+            )
+            .with_input_versions(&input_versions)
+            .with_overrides(&overridden)
+            .with_inferred_licenses(&inferred)
+            .with_cancelled(&cancelled)
+            .with_columns(&opt.columns)?;
+            let mut formatter: Box<dyn ReportFormatter + '_> = match &template {
+                Some(template) => Box::new(
+                    TemplateFormatter::new(&mut output, template.clone(), notice_vars.clone())
+                        .context("failed to parse the template")?
+                        .with_list(
+                            "licenses",
+                            count_licenses(
+                                dep_map
+                                    .values()
+                                    .flatten()
+                                    .map(|(pom, _)| pom.licenses.as_slice()),
+                            )
+                            .into_iter()
+                            .map(|(license, count)| {
+                                HashMap::from([
+                                    ("license".to_string(), license),
+                                    ("count".to_string(), count.to_string()),
+                                ])
+                            })
+                            .collect(),
+                        ),
+                ),
+                None => Box::new(CsvFormatter::with_dialect(
+                    &mut output,
+                    opt.csv_delimiter,
+                    opt.csv_quote,
+                )),
+            };
+            report.write_header(formatter.as_mut())?;
+            for (coordinate, resolved) in &dep_map {
+                match resolved {
+                    Some((pom, artifact_file)) => report.write_record(
+                        formatter.as_mut(),
+                        coordinate,
+                        pom,
+                        artifact_file.as_ref(),
+                    )?,
+                    None => match report.cancellation(coordinate) {
+                        Some(cancellation) => report.write_cancelled_record(
+                            formatter.as_mut(),
+                            coordinate,
+                            cancellation,
+                        )?,
+                        None => info!(dep_name = %coordinate, "skip"),
+                    },
+                }
+            }
+            report.write_project_records(formatter.as_mut())?;
+
+            formatter.finish()?;
+        }
+        FormatType::Xlsx => {
+            let report = TableReport::new(
+                &opt,
+                &annotations,
+                &modules,
+                &sources,
+                &link_statuses,
+                &vulnerabilities,
+                &projects,
+            )
+            .with_input_versions(&input_versions)
+            .with_overrides(&overridden)
+            .with_inferred_licenses(&inferred)
+            .with_cancelled(&cancelled)
+            .with_columns(&opt.columns)?;
+            let records = dep_map
+                .iter()
+                .filter_map(|(coordinate, resolved)| match resolved {
+                    Some((pom, artifact_file)) => {
+                        Some(report.record(coordinate, pom, artifact_file.as_ref()))
+                    }
+                    None => report
+                        .cancellation(coordinate)
+                        .map(|data| report.cancelled_record(coordinate, data)),
+                })
+                .chain(
+                    report
+                        .project_records()
+                        .into_iter()
+                        .map(|(_, record)| record),
+                );
+            let license_counts = count_licenses(
+                dep_map
+                    .values()
+                    .flatten()
+                    .map(|(pom, _)| pom.licenses.as_slice()),
+            );
+            write_xlsx(
+                &mut output,
+                &report.selected_header(),
+                records.map(|record| report.select(record)),
+                &license_counts,
+            )
+            .context("failed to write the workbook")?;
+        }
+        FormatType::Notice => {
+            write_notice_with_template(
+                &mut output,
+                dep_map
+                    .iter()
+                    .filter_map(|(coordinate, resolved)| match resolved {
+                        Some((pom, _)) => Some(NoticeEntry {
+                            coordinate,
+                            pom,
+                            annotation: annotations.get(coordinate),
+                            license_origin: license_origins.get(coordinate),
+                        }),
+                        None => {
+                            info!(dep_name = %coordinate, "skip");
+                            None
+                        }
+                    }),
+                notice_header.as_deref(),
+                notice_footer.as_deref(),
+            )?;
+        }
+        FormatType::Dot | FormatType::Mermaid => {
+            let licenses = dep_map
+                .iter()
+                .filter_map(|(coordinate, resolved)| {
+                    let (pom, _) = resolved.as_ref()?;
+                    let version = pom.version.as_ref().or(coordinate.version.as_ref())?;
+                    Some((
+                        format!("{}:{}", coordinate.module_id(), version),
+                        (pom.licenses.as_slice(), license_origins.get(coordinate)),
+                    ))
+                })
+                .collect::<LicenseMap>();
+            match opt.format {
+                FormatType::Dot => {
+                    write_dot(&mut output, &trees, &licenses, opt.license_conjunction)?
+                }
+                _ => write_mermaid(&mut output, &trees, &licenses, opt.license_conjunction)?,
+            }
+        }
+        FormatType::Obligations => {
+            write_obligations(
+                &mut output,
+                dep_map.iter().filter_map(|(coordinate, resolved)| {
+                    let (pom, _) = resolved.as_ref()?;
+                    Some(ObligationEntry {
+                        coordinate,
+                        licenses: &pom.licenses,
+                        license_origin: license_origins.get(coordinate),
+                    })
+                }),
+            )?;
+        }
+    }
+
+    output.commit()?;
+
+    if opt.summary {
+        write_summary(
+            std::io::stderr().lock(),
+            dep_map.iter().filter_map(|(coordinate, resolved)| {
+                Some((coordinate, resolved.as_ref()?.0.licenses.as_slice()))
+            }),
+        )?;
+    }
+
+    if opt.metrics || opt.metrics_json.is_some() {
+        let metrics = RunMetrics {
+            coordinates: dep_map.len(),
+            restored: restored.len(),
+            cache_hits: client.metrics().cache_hits(),
+            requests: client.metrics().requests(),
+            retries: client.metrics().retries(),
+            failures,
+            wall_time_secs: started.elapsed().as_secs_f64(),
+            slowest: slowest_artifacts(durations, SLOWEST_ARTIFACTS),
+        };
+        if opt.metrics {
+            write_metrics(std::io::stderr().lock(), &metrics)?;
+        }
+        if let Some(path) = &opt.metrics_json {
+            let file = File::create(path).with_context(|| {
+                format!("failed to create the metrics file: {}", path.display())
+            })?;
+            write_metrics_json(BufWriter::new(file), &metrics)
+                .with_context(|| format!("failed to write the metrics file: {}", path.display()))?;
+        }
+    }
+
+    if interrupted {
+        error!(
+            pending,
+            "interrupted. the output is incomplete. use `--state-file` to resume the run"
+        );
+        return Ok(ExitStatus::Interrupted.into());
+    }
+
+    if !line_errors.is_empty() {
+        error!(
+            count = line_errors.len(),
+            "finished but some malformed lines of the input were skipped"
+        );
+        return Ok(ExitStatus::ResolveFailure.into());
+    }
     if has_error {
-        bail!("finished but an error occurred in some requests");
+        error!("finished but an error occurred in some requests");
+        return Ok(ExitStatus::ResolveFailure.into());
+    }
+    if has_drift {
+        error!("finished but the resolutions have drifted from the lock file");
+        return Ok(ExitStatus::LockMismatch.into());
+    }
+    if opt.fail_on_unknown_license {
+        let mut has_unknown = false;
+        for (coordinate, (pom, _)) in dep_map
+            .iter()
+            .filter_map(|(coordinate, resolved)| Some((coordinate, resolved.as_ref()?)))
+            // the inferred licenses are not reviewed.
+            .filter(|(coordinate, (pom, _))| {
+                has_unknown_license(&pom.licenses) || inferred.contains_key(*coordinate)
+            })
+        {
+            let licenses = if pom.licenses.is_empty() {
+                "none".to_string()
+            } else {
+                pom.licenses
+                    .iter()
+                    .map(SPDX::to_string)
+                    .collect::<Vec<_>>()
+                    .join("/")
+            };
+            match inferred.get(coordinate) {
+                Some(data) => {
+                    error!(dep_name = %coordinate, %licenses, source = %data.source, "inferred license")
+                }
+                None => error!(dep_name = %coordinate, %licenses, "unknown license"),
+            }
+            has_unknown = true;
+        }
+        if has_unknown {
+            error!("finished but some artifacts have the unknown license");
+            return Ok(ExitStatus::UnknownLicense.into());
+        }
     }
     info!("bye");
+    Ok(ExitStatus::Success.into())
+}
+
+type ResolvedMap = IndexMap<Coordinate, Option<(POM, Option<ArtifactFile>)>>;
+
+/// Reason of the dependency that is not resolved because the run is cut short.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Cancellation {
+    /// `--deadline` has passed.
+    TimedOut,
+
+    /// Ctrl-C is pressed.
+    Interrupted,
+}
+
+impl Display for Cancellation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::TimedOut => f.write_str("timed out"),
+            Self::Interrupted => f.write_str("interrupted"),
+        }
+    }
+}
+
+/// Original licenses of the POMs and the notes of `--overrides`.
+type OverriddenMap = HashMap<Coordinate, (Vec<SPDX>, Option<String>)>;
+
+/// Columns of the CSV and the XLSX that depend on the options.
+struct TableReport<'a> {
+    opt: &'a Opt,
+    header: Vec<&'static str>,
+    has_annotation: bool,
+    annotations: &'a HashMap<Coordinate, Annotation>,
+    modules: &'a HashMap<Coordinate, Vec<String>>,
+    sources: &'a HashMap<Coordinate, Vec<String>>,
+    link_statuses: &'a HashMap<String, LinkStatus>,
+    vulnerabilities: &'a HashMap<Coordinate, Vec<Vulnerability>>,
+    projects: &'a BTreeMap<String, InputEntry>,
+    input_versions: Option<&'a HashMap<Coordinate, Vec<String>>>,
+
+    /// Original licenses of the POMs and the notes of `--overrides`.
+    overridden: Option<&'a OverriddenMap>,
+
+    inferred: Option<&'a HashMap<Coordinate, InferredLicense>>,
+
+    /// Whether the header has the "Status" of the cancelled dependencies.
+    has_status: bool,
+
+    /// Dependencies that are cancelled at `--deadline` or by Ctrl-C.
+    cancelled: Option<&'a HashMap<Coordinate, Cancellation>>,
+
+    /// Indices of the `header` of `--columns`.
+    columns: Option<Vec<usize>>,
+}
+
+impl<'a> TableReport<'a> {
+    fn new(
+        opt: &'a Opt,
+        annotations: &'a HashMap<Coordinate, Annotation>,
+        modules: &'a HashMap<Coordinate, Vec<String>>,
+        sources: &'a HashMap<Coordinate, Vec<String>>,
+        link_statuses: &'a HashMap<String, LinkStatus>,
+        vulnerabilities: &'a HashMap<Coordinate, Vec<Vulnerability>>,
+        projects: &'a BTreeMap<String, InputEntry>,
+    ) -> Self {
+        let mut header = vec![
+            "Dependency",
+            "Version (Input)",
+            "Version (Latest)",
+            "Packaging",
+            "Name",
+            "Description",
+            "Licenses",
+            "Repository",
+        ];
+        if opt.only_unknown {
+            header.push("License URLs");
+        }
+        if opt.purl {
+            header.push("Purl");
+        }
+        if opt.copyright {
+            header.push("Copyright");
+        }
+        if opt.artifact_digest {
+            header.extend(["Artifact", "Size", "SHA-256"]);
+        }
+        if opt.release_notes {
+            header.push("Release Notes");
+        }
+        if opt.check_license_urls {
+            header.push("Dead License URLs");
+        }
+        if opt.check_vulnerabilities {
+            header.push("Vulnerabilities");
+        }
+        if opt.project_license.is_some() {
+            header.extend(["Compatibility", "Compatibility Notes"]);
+        }
+        if opt.overrides.is_some() {
+            header.extend(["Overridden", "Override Note"]);
+        }
+        if opt.infer_group_licenses {
+            header.push("Inferred From");
+        }
+        if opt.deadline.is_some() {
+            header.push("Status");
+        }
+        // the projects have the "internal" tag.
+        let has_annotation = !annotations.is_empty() || !projects.is_empty();
+        if has_annotation {
+            header.extend(["Tags", "Notes"]);
+        }
+        if opt.modules {
+            header.push("Modules");
+        }
+        if !sources.is_empty() {
+            header.push("Source");
+        }
+
+        Self {
+            opt,
+            header,
+            has_annotation,
+            annotations,
+            modules,
+            sources,
+            link_statuses,
+            vulnerabilities,
+            projects,
+            input_versions: None,
+            overridden: None,
+            inferred: None,
+            has_status: opt.deadline.is_some(),
+            cancelled: None,
+            columns: None,
+        }
+    }
+
+    /// Write the collapsed input versions of `--duplicate-versions collapse` to the
+    /// "Version (Input)".
+    fn with_input_versions(mut self, input_versions: &'a HashMap<Coordinate, Vec<String>>) -> Self {
+        self.input_versions = Some(input_versions);
+        self
+    }
+
+    /// Write the overrides of `--overrides` to the "Overridden" and the "Override Note".
+    fn with_overrides(mut self, overridden: &'a OverriddenMap) -> Self {
+        self.overridden = Some(overridden);
+        self
+    }
+
+    /// Write the siblings of `--infer-group-licenses` to the "Inferred From".
+    fn with_inferred_licenses(
+        mut self,
+        inferred: &'a HashMap<Coordinate, InferredLicense>,
+    ) -> Self {
+        self.inferred = Some(inferred);
+        self
+    }
+
+    /// Add the "Status" column even if `--deadline` is not specified.
+    fn with_status(mut self) -> Self {
+        if !self.has_status {
+            let index = self
+                .header
+                .iter()
+                .position(|data| matches!(*data, "Tags" | "Modules" | "Source"))
+                .unwrap_or(self.header.len());
+            self.header.insert(index, "Status");
+            self.has_status = true;
+        }
+        self
+    }
+
+    /// Write the rows of the cancelled dependencies with "timed out" or "interrupted" of the
+    /// "Status".
+    fn with_cancelled(mut self, cancelled: &'a HashMap<Coordinate, Cancellation>) -> Self {
+        self.cancelled = Some(cancelled);
+        if cancelled.is_empty() {
+            self
+        } else {
+            self.with_status()
+        }
+    }
+
+    fn cancellation(&self, coordinate: &Coordinate) -> Option<Cancellation> {
+        self.cancelled?.get(coordinate).copied()
+    }
+
+    /// Pick and order the columns of `--columns` by the header or its snake case such as
+    /// `version_input`.
+    fn with_columns(mut self, columns: &[String]) -> Fallible<Self> {
+        if columns.is_empty() {
+            return Ok(self);
+        }
+
+        let keys = self
+            .header
+            .iter()
+            .map(|data| column_key(data))
+            .collect::<Vec<_>>();
+        let mut indices = vec![];
+        for column in columns {
+            let index = keys
+                .iter()
+                .position(|data| *data == column_key(column))
+                .with_context(|| {
+                    format!(
+                        "unknown column: {}. available columns: {}",
+                        column,
+                        keys.join(", ")
+                    )
+                })?;
+            indices.push(index);
+        }
+        self.columns = Some(indices);
+        Ok(self)
+    }
+
+    /// The `header` of `--columns`.
+    fn selected_header(&self) -> Vec<&'static str> {
+        match &self.columns {
+            Some(columns) => columns.iter().map(|index| self.header[*index]).collect(),
+            None => self.header.clone(),
+        }
+    }
+
+    /// Pick the fields of `--columns` from the `record` of the `header`.
+    fn select(&self, record: Vec<String>) -> Vec<String> {
+        match &self.columns {
+            Some(columns) => columns.iter().map(|index| record[*index].clone()).collect(),
+            None => record,
+        }
+    }
+
+    fn write_header<F: ReportFormatter + ?Sized>(&self, formatter: &mut F) -> Fallible<()> {
+        formatter.write_header(&self.selected_header())
+    }
+
+    fn write_record<F: ReportFormatter + ?Sized>(
+        &self,
+        formatter: &mut F,
+        coordinate: &Coordinate,
+        pom: &POM,
+        artifact_file: Option<&ArtifactFile>,
+    ) -> Fallible<()> {
+        let record = self.record(coordinate, pom, artifact_file);
+        self.write_escaped_record(formatter, &coordinate.to_string(), record)
+    }
+
+    /// Write the row of the dependency that has no POM because it is cancelled at `--deadline`
+    /// or by Ctrl-C.
+    fn write_cancelled_record<F: ReportFormatter + ?Sized>(
+        &self,
+        formatter: &mut F,
+        coordinate: &Coordinate,
+        cancellation: Cancellation,
+    ) -> Fallible<()> {
+        let record = self.cancelled_record(coordinate, cancellation);
+        self.write_escaped_record(formatter, &coordinate.to_string(), record)
+    }
+
+    /// Returns the fields of the `header` that have only the dependency and the "Status".
+    fn cancelled_record(&self, coordinate: &Coordinate, cancellation: Cancellation) -> Vec<String> {
+        self.header
+            .iter()
+            .map(|column| match *column {
+                "Dependency" => coordinate.module_id(),
+                "Version (Input)" => coordinate.version.clone().unwrap_or_default(),
+                "Status" => cancellation.to_string(),
+                _ => String::new(),
+            })
+            .collect()
+    }
+
+    /// Returns the fields of the `header` without the formula handling.
+    fn record(
+        &self,
+        coordinate: &Coordinate,
+        pom: &POM,
+        artifact_file: Option<&ArtifactFile>,
+    ) -> Vec<String> {
+        let opt = self.opt;
+        let mut record = vec![
+            coordinate.module_id(),
+            self.input_versions
+                .and_then(|data| data.get(coordinate))
+                .map(|data| data.join(", "))
+                .or_else(|| coordinate.version.clone())
+                .unwrap_or_default(),
+            pom.version.clone().unwrap_or_default(),
+            packaging(coordinate, pom).to_string(),
+            pom.name.clone().unwrap_or_default(),
+            pom.description.clone().unwrap_or_default(),
+            opt.license_conjunction.join(&pom.licenses),
+            pom.repository.clone().unwrap_or_default(),
+        ];
+        if opt.only_unknown {
+            record.push(pom.license_urls.join(", "));
+        }
+        if opt.purl {
+            record.push(coordinate.to_purl());
+        }
+        if opt.copyright {
+            record.push(pom.copyright().unwrap_or_default());
+        }
+        if opt.artifact_digest {
+            match artifact_file {
+                Some(data) => record.extend([
+                    data.file_name.clone(),
+                    data.size.map(|data| data.to_string()).unwrap_or_default(),
+                    data.sha256.clone().unwrap_or_default(),
+                ]),
+                None => record.extend(["".into(), "".into(), "".into()]),
+            }
+        }
+        if opt.release_notes {
+            record.push(androidx_release_notes_url(coordinate, pom).unwrap_or_default());
+        }
+        if opt.check_license_urls {
+            record.push(
+                pom.license_urls
+                    .iter()
+                    .filter_map(|url| {
+                        let status = self.link_statuses.get(url)?;
+                        status.is_dead().then(|| format!("{} ({})", url, status))
+                    })
+                    .collect::<Vec<_>>()
+                    .join(", "),
+            );
+        }
+        if opt.check_vulnerabilities {
+            record.push(
+                self.vulnerabilities
+                    .get(coordinate)
+                    .map(|data| {
+                        data.iter()
+                            .map(Vulnerability::to_string)
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    })
+                    .unwrap_or_default(),
+            );
+        }
+        if let Some(project_license) = &opt.project_license {
+            let compatibility = check_compatibility(project_license, &pom.licenses);
+            record.extend([
+                compatibility.to_string(),
+                compatibility.explanation().to_string(),
+            ]);
+        }
+        if opt.overrides.is_some() {
+            match self.overridden.and_then(|data| data.get(coordinate)) {
+                Some((original, note)) => {
+                    let original = if original.is_empty() {
+                        "POM: no license".to_string()
+                    } else {
+                        format!("POM: {}", opt.license_conjunction.join(original))
+                    };
+                    record.extend([
+                        "yes".into(),
+                        note.iter()
+                            .cloned()
+                            .chain([original])
+                            .collect::<Vec<_>>()
+                            .join("; "),
+                    ]);
+                }
+                None => record.extend(["".into(), "".into()]),
+            }
+        }
+        if opt.infer_group_licenses {
+            record.push(
+                self.inferred
+                    .and_then(|data| data.get(coordinate))
+                    .map(|data| data.source.to_string())
+                    .unwrap_or_default(),
+            );
+        }
+        if self.has_status {
+            record.push("".into());
+        }
+        if self.has_annotation {
+            let annotation = self.annotations.get(coordinate);
+            let notes = annotation
+                .and_then(|data| data.note.clone())
+                .into_iter()
+                .chain(
+                    pom.relocation
+                        .as_ref()
+                        .map(|data| format!("relocated to {}", data)),
+                )
+                .collect::<Vec<_>>();
+            record.extend([
+                annotation
+                    .map(|data| data.tags.join(", "))
+                    .unwrap_or_default(),
+                notes.join("; "),
+            ]);
+        }
+        if opt.modules {
+            record.push(
+                self.modules
+                    .get(coordinate)
+                    .map(|data| data.join(", "))
+                    .unwrap_or_default(),
+            );
+        }
+        if !self.sources.is_empty() {
+            record.push(
+                self.sources
+                    .get(coordinate)
+                    .map(|data| data.join(", "))
+                    .unwrap_or_default(),
+            );
+        }
+        record
+    }
+
+    /// Write the project dependencies of `--internal-projects`.
+    fn write_project_records<F: ReportFormatter + ?Sized>(
+        &self,
+        formatter: &mut F,
+    ) -> Fallible<()> {
+        for (name, record) in self.project_records() {
+            self.write_escaped_record(formatter, &name, record)?;
+        }
+        Ok(())
+    }
+
+    /// Returns the name and the fields of the project dependencies of `--internal-projects`.
+    fn project_records(&self) -> Vec<(String, Vec<String>)> {
+        let mut records = vec![];
+        for (project, entry) in self.projects {
+            let name = format!("project {}", project);
+            let mut record = vec![name.clone()];
+            // Version (Input) to Repository.
+            record.extend(std::iter::repeat(String::new()).take(7));
+            if self.opt.only_unknown {
+                record.push("".into());
+            }
+            if self.opt.purl {
+                record.push("".into());
+            }
+            if self.opt.copyright {
+                record.push("".into());
+            }
+            if self.opt.artifact_digest {
+                record.extend(["".into(), "".into(), "".into()]);
+            }
+            if self.opt.release_notes {
+                record.push("".into());
+            }
+            if self.opt.check_license_urls {
+                record.push("".into());
+            }
+            if self.opt.check_vulnerabilities {
+                record.push("".into());
+            }
+            if self.opt.project_license.is_some() {
+                record.extend(["".into(), "".into()]);
+            }
+            if self.opt.overrides.is_some() {
+                record.extend(["".into(), "".into()]);
+            }
+            if self.opt.infer_group_licenses {
+                record.push("".into());
+            }
+            if self.has_status {
+                record.push("".into());
+            }
+            record.extend(["internal".into(), "".into()]);
+            if self.opt.modules {
+                record.push(entry.modules.join(", "));
+            }
+            if !self.sources.is_empty() {
+                record.push(entry.sources.join(", "));
+            }
+            records.push((name, record));
+        }
+        records
+    }
+
+    fn write_escaped_record<F: ReportFormatter + ?Sized>(
+        &self,
+        formatter: &mut F,
+        dep_name: &str,
+        record: Vec<String>,
+    ) -> Fallible<()> {
+        let mut record = self.select(record);
+        for (column, field) in self.selected_header().iter().zip(record.iter_mut()) {
+            if !is_formula_like(field) {
+                continue;
+            }
+            if self.opt.escape_formulas {
+                *field = escape_formula(field).into_owned();
+            } else {
+                warn!(
+                    %dep_name,
+                    %column,
+                    "the field may be interpreted as a formula by spreadsheets"
+                );
+            }
+        }
+        formatter.write_row(&record)
+    }
+}
+
+/// Write the CSV records in the input order as soon as the preceding dependencies are finished
+/// and flush them at most once per `interval`.
+struct IncrementalCsv<'a, W: Write> {
+    report: TableReport<'a>,
+    formatter: CsvFormatter<W>,
+    interval: Duration,
+    last_flush: Instant,
+    finished: Vec<bool>,
+    written: usize,
+    cancelled: HashMap<Coordinate, Cancellation>,
+}
+
+impl<'a, W: Write> IncrementalCsv<'a, W> {
+    fn new(report: TableReport<'a>, writer: W, interval: Duration, len: usize) -> Fallible<Self> {
+        let opt = report.opt;
+        let mut formatter = CsvFormatter::with_dialect(writer, opt.csv_delimiter, opt.csv_quote);
+        report.write_header(&mut formatter)?;
+        formatter.flush()?;
+
+        Ok(Self {
+            report,
+            formatter,
+            interval,
+            last_flush: Instant::now(),
+            finished: vec![false; len],
+            written: 0,
+            cancelled: HashMap::new(),
+        })
+    }
+
+    fn write_finished(&mut self, dep_map: &ResolvedMap, coordinate: &Coordinate) -> Fallible<()> {
+        if let Some(index) = dep_map.get_index_of(coordinate) {
+            self.finished[index] = true;
+        }
+
+        while self.finished.get(self.written) == Some(&true) {
+            let (coordinate, resolved) = dep_map
+                .get_index(self.written)
+                .context("dep_map is shorter than finished")?;
+            match resolved {
+                Some((pom, artifact_file)) => self.report.write_record(
+                    &mut self.formatter,
+                    coordinate,
+                    pom,
+                    artifact_file.as_ref(),
+                )?,
+                None => match self.cancelled.get(coordinate) {
+                    Some(cancellation) => self.report.write_cancelled_record(
+                        &mut self.formatter,
+                        coordinate,
+                        *cancellation,
+                    )?,
+                    None => info!(dep_name = %coordinate, "skip"),
+                },
+            }
+            self.written += 1;
+        }
+
+        if self.interval <= self.last_flush.elapsed() {
+            self.formatter.flush()?;
+            self.last_flush = Instant::now();
+        }
+
+        Ok(())
+    }
+
+    /// Same as [IncrementalCsv::write_finished] but write the row of
+    /// [TableReport::write_cancelled_record].
+    fn write_cancelled(
+        &mut self,
+        dep_map: &ResolvedMap,
+        coordinate: &Coordinate,
+        cancellation: Cancellation,
+    ) -> Fallible<()> {
+        self.cancelled.insert(coordinate.clone(), cancellation);
+        self.write_finished(dep_map, coordinate)
+    }
+
+    fn finish(mut self) -> Fallible<W> {
+        self.report.write_project_records(&mut self.formatter)?;
+        self.formatter.finish()?;
+        self.formatter.into_inner()
+    }
+}
+
+/// Packaging of the row that prefers the `@ext` of the input to the `<packaging>` because some
+/// libraries publish both of the jar and the aar with a POM.
+fn packaging<'a>(coordinate: &'a Coordinate, pom: &'a POM) -> &'a str {
+    coordinate
+        .packaging
+        .as_deref()
+        .unwrap_or_else(|| pom.packaging_or_default())
+}
+
+/// Coordinate of the POM that is shared by the classifiers and the packagings.
+fn pom_coordinate(coordinate: &Coordinate) -> Coordinate {
+    Coordinate {
+        classifier: None,
+        packaging: None,
+        ..coordinate.clone()
+    }
+}
+
+/// Reorder the `dep_map` by the `group_by` and then the `sort` for `--group-by` and `--sort`.
+/// The artifacts that have the same key keep the current order.
+fn sort_dep_map(
+    dep_map: &mut ResolvedMap,
+    sort: Option<SortKey>,
+    group_by: Option<GroupKey>,
+    license_conjunction: LicenseConjunction,
+) {
+    let licenses = |resolved: &Option<(POM, Option<ArtifactFile>)>| match resolved {
+        Some((pom, _)) => license_conjunction.join(&pom.licenses),
+        None => String::new(),
+    };
+    let group_key =
+        |coordinate: &Coordinate, resolved: &Option<(POM, Option<ArtifactFile>)>| match group_by {
+            Some(GroupKey::Group) => coordinate.group_id.clone(),
+            Some(GroupKey::License) => licenses(resolved),
+            None => String::new(),
+        };
+    let sort_key =
+        |coordinate: &Coordinate, resolved: &Option<(POM, Option<ArtifactFile>)>| match sort {
+            Some(SortKey::Name) => resolved
+                .as_ref()
+                .and_then(|(pom, _)| pom.name.clone())
+                .unwrap_or_else(|| coordinate.artifact_id.clone())
+                .to_lowercase(),
+            Some(SortKey::License) => licenses(resolved),
+            Some(SortKey::Group) => coordinate.module_id(),
+            None => String::new(),
+        };
+    dep_map.sort_by(|a_coordinate, a_resolved, b_coordinate, b_resolved| {
+        group_key(a_coordinate, a_resolved)
+            .cmp(&group_key(b_coordinate, b_resolved))
+            .then_with(|| {
+                sort_key(a_coordinate, a_resolved).cmp(&sort_key(b_coordinate, b_resolved))
+            })
+    });
+}
+
+/// Managed artifacts of the BOM of `--expand-bom` that have the version and are not in the
+/// `dep_map` in any version. Returns an empty list if the `pom` is not the BOM.
+fn bom_artifacts(dep_map: &ResolvedMap, pom: &POM) -> Vec<Coordinate> {
+    if pom.packaging_or_default() != "pom" {
+        return vec![];
+    }
+
+    let mut artifacts = Vec::<Coordinate>::new();
+    for dependency in pom.dependency_management.iter().filter(|data| {
+        data.version
+            .as_deref()
+            .map_or(false, |data| !data.contains("${"))
+    }) {
+        let coordinate = dependency.coordinate();
+        let module_id = coordinate.module_id();
+        if dep_map
+            .keys()
+            .chain(&artifacts)
+            .any(|data| data.module_id() == module_id)
+        {
+            continue;
+        }
+        artifacts.push(coordinate);
+    }
+    artifacts
+}
+
+/// Coordinate of the artifact that is published at the relocated coordinate instead of the
+/// relocation stub, or at the platform artifact of `--kmp-platform`.
+fn artifact_coordinate(
+    coordinate: &Coordinate,
+    pom: &POM,
+    kmp_platform: Option<&str>,
+) -> Coordinate {
+    let is_platform_artifact = kmp_platform.map_or(false, |platform| {
+        pom.artifact_id == format!("{}-{}", coordinate.artifact_id, platform)
+    });
+    match &pom.relocation {
+        Some(relocation) => Coordinate {
+            group_id: relocation.group_id.clone().unwrap_or_default(),
+            artifact_id: relocation.artifact_id.clone().unwrap_or_default(),
+            version: relocation.version.clone(),
+            ..coordinate.clone()
+        },
+        None if is_platform_artifact => Coordinate {
+            artifact_id: pom.artifact_id.clone(),
+            version: pom.version.clone(),
+            ..coordinate.clone()
+        },
+        None => coordinate.clone(),
+    }
+}
+
+/// Destination of the report that is stdout or the file of `--output`.
+enum Output {
+    Stdout(std::io::Stdout),
+    File(AtomicFile),
+
+    /// File of `--flush-interval` that has the rows as soon as they are flushed.
+    DirectFile(BufWriter<File>),
+}
+
+impl Output {
+    fn create(path: Option<&Path>, force: bool) -> Fallible<Self> {
+        match path {
+            Some(path) => Ok(Self::File(AtomicFile::create(path, force)?)),
+            None => Ok(Self::Stdout(std::io::stdout())),
+        }
+    }
+
+    /// Same as [Output::create] but write to the file of `path` instead of the temporary file.
+    fn create_direct(path: Option<&Path>, force: bool) -> Fallible<Self> {
+        let path = match path {
+            Some(data) => data,
+            None => return Ok(Self::Stdout(std::io::stdout())),
+        };
+        if !force && path.exists() {
+            bail!(
+                "the output file already exists, use --force to overwrite it: {}",
+                path.display()
+            );
+        }
+        let file = File::create(path)
+            .with_context(|| format!("failed to create the file: {}", path.display()))?;
+        Ok(Self::DirectFile(BufWriter::new(file)))
+    }
+
+    fn commit(self) -> Fallible<()> {
+        match self {
+            Self::Stdout(mut stdout) => stdout.flush()?,
+            Self::File(file) => file.commit()?,
+            Self::DirectFile(mut file) => file.flush()?,
+        }
+        Ok(())
+    }
+}
+
+impl Write for Output {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        match self {
+            Self::Stdout(stdout) => stdout.write(buf),
+            Self::File(file) => file.write(buf),
+            Self::DirectFile(file) => file.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        match self {
+            Self::Stdout(stdout) => stdout.flush(),
+            Self::File(file) => file.flush(),
+            Self::DirectFile(file) => file.flush(),
+        }
+    }
+}
+
+/// Returns `true` if the latest version of some dependencies failed to retrieve.
+async fn write_outdated<'a, W, I>(
+    writer: W,
+    client: &HttpClient,
+    repositories: &RepositoryConfig,
+    coordinates: I,
+) -> Fallible<bool>
+where
+    W: Write,
+    I: IntoIterator<Item = &'a Coordinate>,
+{
+    let coordinates = coordinates
+        .into_iter()
+        .filter(|coordinate| coordinate.version.is_some())
+        .map(pom_coordinate)
+        .collect::<BTreeSet<_>>();
+    let mut results = futures::stream::iter(coordinates)
+        .map(|coordinate| async move {
+            let latest = retrieve_latest_version(client.clone(), &coordinate, repositories).await;
+            (coordinate, latest)
+        })
+        .buffered(8);
+
+    let mut writer = csv::WriterBuilder::new().from_writer(writer);
+    writer.write_record(["Dependency", "Current", "Latest", "Delta"])?;
+    let mut has_error = false;
+    while let Some((coordinate, latest)) = results.next().await {
+        let latest = match latest {
+            Ok(Some(data)) => data,
+            Ok(None) => {
+                warn!(name = %coordinate, "maven-metadata.xml has no version");
+                continue;
+            }
+            Err(e) => {
+                warn!(name = %coordinate, ?e, "failed to request the latest version.");
+                has_error = true;
+                continue;
+            }
+        };
+        let current = coordinate.version.as_deref().unwrap_or_default();
+        if let Some(delta) = classify_delta(current, &latest) {
+            writer.write_record([
+                coordinate.module_id().as_str(),
+                current,
+                latest.as_str(),
+                delta.to_string().as_str(),
+            ])?;
+        }
+    }
+
+    writer.flush()?;
+    Ok(has_error)
+}
+
+fn write_conflicts<W: Write>(
+    writer: W,
+    lines: &BTreeMap<Coordinate, InputEntry>,
+    has_source: bool,
+) -> Fallible<()> {
+    let mut writer = csv::WriterBuilder::new().from_writer(writer);
+    let mut header = vec!["Dependency", "Versions", "Selected"];
+    if has_source {
+        header.push("Source");
+    }
+    writer.write_record(&header)?;
+
+    for conflict in find_conflicts(lines.keys()) {
+        let mut record = vec![
+            conflict.module_id.clone(),
+            conflict.versions.join(", "),
+            conflict.selected,
+        ];
+        if has_source {
+            record.push(
+                conflict
+                    .versions
+                    .iter()
+                    .map(|version| {
+                        let sources = lines
+                            .iter()
+                            .filter(|(coordinate, _)| {
+                                coordinate.module_id() == conflict.module_id
+                                    && coordinate.version.as_ref() == Some(version)
+                            })
+                            .flat_map(|(_, entry)| &entry.sources)
+                            .map(String::as_str)
+                            .collect::<Vec<_>>();
+                        format!("{}: {}", version, sources.join(", "))
+                    })
+                    .collect::<Vec<_>>()
+                    .join("; "),
+            );
+        }
+        writer.write_record(&record)?;
+    }
+
+    writer.flush()?;
+
     Ok(())
 }
 
+/// Read the file of `--overrides` that is the CSV or the TOML by the extension.
+fn read_overrides(path: &Path) -> Fallible<Vec<LicenseOverride>> {
+    let source = std::fs::read_to_string(path)
+        .with_context(|| format!("failed to read the overrides: {}", path.display()))?;
+    let is_csv = path
+        .extension()
+        .map_or(false, |data| data.eq_ignore_ascii_case("csv"));
+    let overrides = if is_csv {
+        parse_overrides_csv(&source)
+    } else {
+        parse_overrides_toml(&source)
+    };
+    overrides.with_context(|| format!("failed to parse the overrides: {}", path.display()))
+}
+
+/// Single ASCII character, `tab` or `\t` of `--csv-delimiter`.
+fn parse_delimiter(s: &str) -> Fallible<u8> {
+    match s {
+        "tab" | "\\t" => Ok(b'\t'),
+        _ => match s.as_bytes() {
+            [data] if data.is_ascii() && *data != b'"' && *data != b'\n' => Ok(*data),
+            _ => bail!("expected a single ASCII character, `tab` or `\\t`: {}", s),
+        },
+    }
+}
+
+/// `KEY=VALUE`
+fn parse_key_value(s: &str) -> Fallible<(String, String)> {
+    let (key, value) = s
+        .split_once('=')
+        .with_context(|| format!("expected `KEY=VALUE`: {}", s))?;
+    Ok((key.trim().to_owned(), value.to_owned()))
+}
+
+/// Dependency of the inputs that has the merged information.
+#[derive(Default)]
+struct InputEntry {
+    annotation: Annotation,
+    modules: Vec<String>,
+    sources: Vec<String>,
+}
+
+impl InputEntry {
+    fn merge(&mut self, annotation: Annotation, modules: Vec<String>, source: Option<&str>) {
+        self.annotation.merge(annotation);
+        for module in modules {
+            if !self.modules.contains(&module) {
+                self.modules.push(module);
+            }
+        }
+        if let Some(source) = source {
+            if !self.sources.iter().any(|data| data == source) {
+                self.sources.push(source.into());
+            }
+        }
+    }
+}
+
+/// Merge the entries of the same `group:artifact`, classifier and packaging into the entry of the
+/// highest version, and returns the input versions of the merged entries in ascending order.
+fn collapse_versions(
+    lines: &mut BTreeMap<Coordinate, InputEntry>,
+) -> HashMap<Coordinate, Vec<String>> {
+    let mut groups = BTreeMap::<_, Vec<Coordinate>>::new();
+    for coordinate in lines.keys().filter(|data| data.version.is_some()) {
+        groups
+            .entry((
+                coordinate.module_id(),
+                coordinate.classifier.clone(),
+                coordinate.packaging.clone(),
+            ))
+            .or_default()
+            .push(coordinate.clone());
+    }
+
+    let mut input_versions = HashMap::new();
+    for mut coordinates in groups.into_values().filter(|data| 1 < data.len()) {
+        coordinates
+            .sort_by_cached_key(|data| Version::from(data.version.as_deref().unwrap_or_default()));
+        let versions = coordinates
+            .iter()
+            .filter_map(|data| data.version.clone())
+            .collect::<Vec<_>>();
+        let selected = coordinates.pop().expect("multiple coordinates");
+        for coordinate in coordinates {
+            let Some(entry) = lines.remove(&coordinate) else {
+                continue;
+            };
+            let selected_entry = lines.get_mut(&selected).expect("selected entry");
+            selected_entry.merge(entry.annotation, entry.modules, None);
+            for source in &entry.sources {
+                selected_entry.merge(Annotation::default(), vec![], Some(source));
+            }
+        }
+        info!(dep_name = %selected, versions = versions.join(", "), "collapse");
+        input_versions.insert(selected, versions);
+    }
+    input_versions
+}
+
+/// Entries of an input.
+struct ParsedInput {
+    /// Dependencies with the annotation and the Gradle modules that declare it.
+    dependencies: Vec<(Coordinate, Annotation, Vec<String>)>,
+
+    /// Project dependencies of `--internal-projects` with the Gradle modules that declare it.
+    projects: Vec<(String, Vec<String>)>,
+
+    /// Lines of the Gradle output that were skipped because they could not be parsed.
+    errors: Vec<LineError>,
+
+    /// Dependency trees of the Gradle output for `--format dot` and `--format mermaid`.
+    trees: Vec<DependencyTree>,
+
+    /// Resolved artifacts of `--input-format jsonl` that are restored instead of the requests.
+    resolved: Vec<(Coordinate, (POM, Option<ArtifactFile>))>,
+}
+
+const ENV_PREFIX: &str = "OSS_INFO_MAVEN_";
+
+/// [Opt] that has the defaults of the config file and the `OSS_INFO_MAVEN_*` environment
+/// variables so that the command line options override them.
+fn layered_command() -> Fallible<clap::Command> {
+    let args = std::env::args_os().collect::<Vec<_>>();
+    let mut command = Opt::command();
+
+    let explicit_config = args
+        .iter()
+        .skip(1)
+        .zip(args.iter().skip(2).map(Some).chain([None]))
+        .find_map(|(arg, next)| match arg.to_str()? {
+            "--config" => next.map(PathBuf::from),
+            data => data.strip_prefix("--config=").map(PathBuf::from),
+        })
+        .or_else(|| std::env::var_os(format!("{}CONFIG", ENV_PREFIX)).map(PathBuf::from));
+    let config_path = explicit_config.clone().or_else(|| {
+        std::env::var_os("XDG_CONFIG_HOME")
+            .map(PathBuf::from)
+            .or_else(|| std::env::var_os("HOME").map(|data| PathBuf::from(data).join(".config")))
+            .map(|data| data.join(env!("CARGO_PKG_NAME")).join("config.toml"))
+    });
+    if let Some(path) = config_path {
+        match std::fs::read_to_string(&path) {
+            Ok(config) => {
+                let entries = parse_config(&config)
+                    .with_context(|| format!("failed to parse the config: {}", path.display()))?;
+                for (name, value) in entries {
+                    command = with_default(command, &name, value).with_context(|| {
+                        format!("unexpected option of the config: {}", path.display())
+                    })?;
+                }
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound && explicit_config.is_none() => {}
+            Err(e) => {
+                return Err(e)
+                    .with_context(|| format!("failed to read the config: {}", path.display()))
+            }
+        }
+    }
+
+    let names = command
+        .get_arguments()
+        .filter_map(|data| {
+            Some((
+                data.get_long()?.to_owned(),
+                data.get_action().takes_values(),
+            ))
+        })
+        .filter(|(name, _)| name != "config")
+        .collect::<Vec<_>>();
+    for (name, takes_values) in names {
+        let env_name = format!(
+            "{}{}",
+            ENV_PREFIX,
+            name.to_ascii_uppercase().replace('-', "_")
+        );
+        let value = match std::env::var(&env_name) {
+            Ok(data) => data,
+            Err(_) => continue,
+        };
+        let value = match value.as_str() {
+            "true" | "1" if !takes_values => ConfigValue::Bool(true),
+            "false" | "0" | "" if !takes_values => ConfigValue::Bool(false),
+            _ => ConfigValue::Str(value),
+        };
+        command = with_default(command, &name, value)
+            .with_context(|| format!("unexpected environment variable: {}", env_name))?;
+    }
+
+    Ok(command)
+}
+
+/// Set the default value of the option of the config file or the environment variable.
+///
+/// The flag that has the default accepts `--flag=false` to turn off the default.
+fn with_default(command: clap::Command, name: &str, value: ConfigValue) -> Fallible<clap::Command> {
+    let arg = command
+        .get_arguments()
+        .find(|data| data.get_long() == Some(name))
+        .with_context(|| format!("unknown option: {}", name))?;
+    let id = arg.get_id().clone();
+    let is_flag = matches!(arg.get_action(), ArgAction::SetTrue);
+    let takes_values = arg.get_action().takes_values();
+    Ok(match value {
+        ConfigValue::Bool(data) if is_flag => command.mut_arg(id, |arg| {
+            arg.action(ArgAction::Set)
+                .num_args(0..=1)
+                .require_equals(true)
+                .default_missing_value("true")
+                .value_parser(clap::value_parser!(bool))
+                .default_value(data.to_string())
+        }),
+        _ if !takes_values => bail!("{} expects true or false", name),
+        ConfigValue::Bool(data) => command.mut_arg(id, |arg| arg.default_value(data.to_string())),
+        ConfigValue::Str(data) => command.mut_arg(id, |arg| arg.default_value(data)),
+        ConfigValue::List(data) => command.mut_arg(id, |arg| arg.default_values(data)),
+    })
+}
+
+fn write_completions(shell: clap_complete::Shell, out_dir: Option<&Path>) -> Fallible<()> {
+    let mut command = Opt::command();
+    match out_dir {
+        Some(out_dir) => {
+            std::fs::create_dir_all(out_dir).with_context(|| {
+                format!("failed to create the directory: {}", out_dir.display())
+            })?;
+            let path =
+                clap_complete::generate_to(shell, &mut command, env!("CARGO_PKG_NAME"), out_dir)
+                    .with_context(|| {
+                        format!("failed to write the completions: {}", out_dir.display())
+                    })?;
+            info!(path = %path.display(), "wrote the completions");
+        }
+        None => clap_complete::generate(
+            shell,
+            &mut command,
+            env!("CARGO_PKG_NAME"),
+            &mut std::io::stdout(),
+        ),
+    }
+    Ok(())
+}
+
+/// Write `oss-info-maven.1` and `oss-info-maven-<subcommand>.1` to the `out_dir` or the page of
+/// the command to stdout.
+fn write_man_pages(out_dir: Option<&Path>) -> Fallible<()> {
+    let command = Opt::command().name(env!("CARGO_PKG_NAME"));
+    let out_dir = match out_dir {
+        Some(data) => data,
+        None => {
+            let mut stdout = std::io::stdout().lock();
+            clap_mangen::Man::new(command).render(&mut stdout)?;
+            stdout.flush()?;
+            return Ok(());
+        }
+    };
+
+    std::fs::create_dir_all(out_dir)
+        .with_context(|| format!("failed to create the directory: {}", out_dir.display()))?;
+    let mut pages = vec![(
+        command.get_name().to_string(),
+        clap_mangen::Man::new(command.clone()),
+    )];
+    for subcommand in command
+        .get_subcommands()
+        .filter(|data| data.get_name() != "help")
+    {
+        let name = format!("{}-{}", command.get_name(), subcommand.get_name());
+        pages.push((
+            name.clone(),
+            clap_mangen::Man::new(subcommand.clone()).title(name),
+        ));
+    }
+    for (name, man) in pages {
+        let path = out_dir.join(format!("{}.1", name));
+        let mut file = BufWriter::new(
+            File::create(&path)
+                .with_context(|| format!("failed to create the man page: {}", path.display()))?,
+        );
+        man.render(&mut file)?;
+        file.flush()?;
+        info!(path = %path.display(), "wrote the man page");
+    }
+    Ok(())
+}
+
+/// Tracer of `--otel` that exports the spans in the batches.
+#[cfg(feature = "otel")]
+fn create_otel_tracer() -> Fallible<opentelemetry_sdk::trace::Tracer> {
+    use opentelemetry::KeyValue;
+    use opentelemetry_otlp::WithExportConfig;
+
+    opentelemetry_otlp::new_pipeline()
+        .tracing()
+        .with_exporter(opentelemetry_otlp::new_exporter().tonic().with_env())
+        .with_trace_config(opentelemetry_sdk::trace::config().with_resource(
+            opentelemetry_sdk::Resource::new([KeyValue::new(
+                "service.name",
+                env!("CARGO_PKG_NAME"),
+            )]),
+        ))
+        .install_batch(opentelemetry_sdk::runtime::Tokio)
+        .context("failed to install the OpenTelemetry exporter")
+}
+
+/// Flush the spans of `--otel` at the end of `main`.
+#[cfg(feature = "otel")]
+struct OtelGuard;
+
+#[cfg(feature = "otel")]
+impl Drop for OtelGuard {
+    fn drop(&mut self) {
+        opentelemetry::global::shutdown_tracer_provider();
+    }
+}
+
+/// Read `~/.m2/settings.xml` of `--use-maven-settings`.
+fn read_maven_settings() -> Fallible<MavenSettings> {
+    let path = std::env::var_os("HOME")
+        .map(|data| PathBuf::from(data).join(".m2").join("settings.xml"))
+        .context("failed to find the home directory")?;
+    let xml = std::fs::read_to_string(&path)
+        .with_context(|| format!("failed to read the Maven settings: {}", path.display()))?;
+    parse_maven_settings(&xml)
+        .with_context(|| format!("failed to parse the Maven settings: {}", path.display()))
+}
+
+/// Retrieve the POM from the Maven repositories and fall back to the Ivy repositories of
+/// `--ivy-repository` in order.
+async fn retrieve_maven_or_ivy_lib(
+    client: &HttpClient,
+    coordinate: &Coordinate,
+    repositories: &RepositoryConfig,
+    ivy_repositories: &[String],
+    version_policy: Option<VersionPolicy>,
+) -> Fallible<(POM, LockEntry)> {
+    let selector: Box<dyn VersionSelector> = match (version_policy, coordinate.version.clone()) {
+        (Some(VersionPolicy::Release), _) => Box::new(ReleaseVersionSelector),
+        (Some(VersionPolicy::Latest), _) => Box::new(LatestVersionSelector),
+        (Some(VersionPolicy::Input), Some(version)) => Box::new(FixedVersionSelector(version)),
+        (Some(VersionPolicy::Input), None) | (None, _) => Box::new(DefaultVersionSelector),
+    };
+    let maven_err = match retrieve_maven_lib_with_selector_and_lock_entry(
+        client.clone(),
+        coordinate,
+        repositories,
+        selector.as_ref(),
+    )
+    .await
+    {
+        Ok(data) => return Ok(data),
+        Err(e) => e,
+    };
+    for ivy_repository in ivy_repositories {
+        match retrieve_ivy_lib_with_lock_entry(client.clone(), coordinate, ivy_repository).await {
+            Ok(data) => {
+                info!(repo = %ivy_repository, "resolved from the Ivy repository");
+                return Ok(data);
+            }
+            Err(e) => debug!(repo = %ivy_repository, ?e, "not found in the Ivy repository"),
+        }
+    }
+    Err(maven_err)
+}
+
+/// Repositories that are replaced with the mirrors of `--use-maven-settings`.
+fn repository_config(maven_settings: Option<&MavenSettings>) -> RepositoryConfig {
+    match maven_settings {
+        Some(settings) => settings.apply_mirrors(&RepositoryConfig::default()),
+        None => RepositoryConfig::default(),
+    }
+}
+
+/// Create the client of the `--timeout`, the `--rate-limit`, the `--cache-dir`, the
+/// `--validate-xml`, the `--strict-xml` and the credentials and the proxy of `--use-maven-settings`.
+fn create_client(opt: &Opt, maven_settings: Option<&MavenSettings>) -> Fallible<HttpClient> {
+    let timeout = opt.timeout.map(Duration::from_secs);
+    let proxy = match maven_settings.and_then(MavenSettings::active_proxy) {
+        Some(proxy) => Some(proxy.to_reqwest_proxy()?),
+        None => None,
+    };
+    let client = ConnectionOptions {
+        connect_timeout: timeout,
+        proxy,
+        ..Default::default()
+    }
+    .build()
+    .expect("Client::new()");
+    let client = match opt.rate_limit {
+        Some(rate) => HttpClient::new(client).with_rate_limiter(Arc::new(RateLimiter::new(rate))),
+        None => HttpClient::new(client),
+    }
+    .with_xml_validation(opt.validate_xml)
+    .with_strict_xml(opt.strict_xml)
+    .with_shared_metadata();
+    let client = match timeout {
+        Some(timeout) => client.with_timeout(timeout),
+        None => client,
+    };
+    let client = match maven_settings {
+        Some(settings) => {
+            client.with_credentials(settings.credentials(&RepositoryConfig::default()))
+        }
+        None => client,
+    };
+    Ok(match &opt.cache_dir {
+        Some(dir) => client.with_cache(Arc::new(HttpCache::new(dir))),
+        None => client,
+    })
+}
+
+/// Print the paths of the `module_id` of the Gradle outputs of `inputs` or stdin for the `insight`
+/// subcommand. Returns false if no path is found.
+fn print_insight<W: Write>(mut writer: W, inputs: &[PathBuf], module_id: &str) -> Fallible<bool> {
+    let mut paths = vec![];
+    if inputs.is_empty() {
+        paths.extend(
+            parse_dependency_insight(&mut std::io::stdin().lock(), module_id)
+                .context("failed to parse stdin")?
+                .into_iter()
+                .map(|data| (None, data)),
+        );
+    } else {
+        for path in inputs {
+            let file = File::open(path)
+                .with_context(|| format!("failed to open the input: {}", path.display()))?;
+            let source = path.display().to_string();
+            paths.extend(
+                parse_dependency_insight(&mut BufReader::new(file), module_id)
+                    .with_context(|| format!("failed to parse the input: {}", source))?
+                    .into_iter()
+                    .map(|data| (Some(source.clone()), data)),
+            );
+        }
+    }
+
+    for (index, (source, path)) in paths.iter().enumerate() {
+        if index != 0 {
+            writeln!(writer)?;
+        }
+        if let Some(source) = source.as_ref().filter(|_| 1 < inputs.len()) {
+            writeln!(writer, "# {}", source)?;
+        }
+        write!(writer, "{}", path)?;
+    }
+    writer.flush()?;
+
+    Ok(!paths.is_empty())
+}
+
+/// Print the status and the latency of the repositories for the `check-repos` subcommand.
+/// Returns false if some repositories are unhealthy.
+async fn print_repository_health<W: Write>(
+    mut writer: W,
+    client: HttpClient,
+    repositories: &RepositoryConfig,
+    probe: Option<&Coordinate>,
+) -> Fallible<bool> {
+    let google_probe = Coordinate::new("androidx.annotation", "annotation");
+    let central_probe = Coordinate::new("junit", "junit");
+    let plugin_portal_probe = Coordinate::new(
+        "org.jetbrains.kotlin.android",
+        "org.jetbrains.kotlin.android.gradle.plugin",
+    );
+    let mut healthy = true;
+    for (name, repo_url, default_probe) in [
+        ("Google Maven", &repositories.google_maven, &google_probe),
+        ("Maven Central", &repositories.maven_central, &central_probe),
+        (
+            "Plugin Portal",
+            &repositories.gradle_plugin_portal,
+            &plugin_portal_probe,
+        ),
+    ] {
+        let probe = probe.unwrap_or(default_probe);
+        let (status, latency) = check_repository(client.clone(), repo_url, probe).await;
+        if !status.is_healthy() {
+            warn!(repository = name, %repo_url, %status, "the repository is unhealthy");
+            healthy = false;
+        }
+        // the width of the format is applied to the string but not to Display of the enum.
+        let status = status.to_string();
+        writeln!(
+            writer,
+            "{:<14}{:>8.3}s  {:<16}{} ({})",
+            name,
+            latency.as_secs_f64(),
+            status,
+            repo_url,
+            probe.module_id(),
+        )?;
+    }
+    writer.flush()?;
+
+    Ok(healthy)
+}
+
+/// Print the POM and the versions of the `coordinate` for the `info` subcommand.
+async fn print_info<W: Write>(
+    mut writer: W,
+    client: HttpClient,
+    repositories: &RepositoryConfig,
+    coordinate: &Coordinate,
+) -> Fallible<()> {
+    let requested_version = coordinate.version.clone();
+    let selector = move |metadata: &Dependency| {
+        requested_version
+            .clone()
+            .or_else(|| DefaultVersionSelector.select(metadata))
+    };
+    let pom = retrieve_maven_lib_with_selector(client.clone(), coordinate, repositories, &selector)
+        .await
+        .with_context(|| format!("failed to retrieve the POM: {}", coordinate))?;
+    let versions = retrieve_versions(client, coordinate, repositories)
+        .await
+        .with_context(|| format!("failed to retrieve the versions: {}", coordinate))?;
+
+    let version = pom.version.clone().unwrap_or_default();
+    writeln!(writer, "{}:{}", coordinate.module_id(), version)?;
+    let mut field = |name: &str, value: Option<&str>| -> Fallible<()> {
+        if let Some(value) = value.filter(|data| !data.is_empty()) {
+            writeln!(writer, "  {:<13}{}", format!("{}:", name), value)?;
+        }
+        Ok(())
+    };
+    field("Name", pom.name.as_deref())?;
+    field("Description", pom.description.as_deref())?;
+    field("Packaging", Some(pom.packaging_or_default()))?;
+    field(
+        "Licenses",
+        Some(LicenseConjunction::default().join(&pom.licenses).as_str()),
+    )?;
+    field("License URLs", Some(pom.license_urls.join(", ").as_str()))?;
+    field("URL", pom.url.as_deref())?;
+    field("SCM", pom.scm_url.as_deref())?;
+    field("Repository", pom.repository.as_deref())?;
+    field("Copyright", pom.copyright().as_deref())?;
+    field(
+        "Relocated to",
+        pom.relocation.as_ref().map(ToString::to_string).as_deref(),
+    )?;
+    field(
+        "Latest",
+        versions
+            .iter()
+            .rev()
+            .find(|data| !data.is_pre_release())
+            .map(Version::as_str),
+    )?;
+    field(
+        "Pre-release",
+        versions
+            .last()
+            .filter(|data| data.is_pre_release())
+            .map(Version::as_str),
+    )?;
+    field(
+        "Recent",
+        Some(
+            versions
+                .iter()
+                .rev()
+                .take(INFO_RECENT_VERSIONS)
+                .map(Version::as_str)
+                .collect::<Vec<_>>()
+                .join(", ")
+                .as_str(),
+        ),
+    )?;
+
+    writer.flush()?;
+    Ok(())
+}
+
+/// Read the lines of `--skip-pretty`.
+fn read_prettied_input<R: BufRead>(reader: R, strict: bool) -> Fallible<ParsedInput> {
+    let mut errors = vec![];
+    let dependencies = parse_prettied_entries(reader, (!strict).then_some(&mut errors))?
+        .into_iter()
+        .map(|(coordinate, annotation)| (coordinate, annotation, vec![]))
+        .collect();
+    Ok(ParsedInput {
+        dependencies,
+        projects: vec![],
+        errors,
+        trees: vec![],
+        resolved: vec![],
+    })
+}
+
+fn read_input<R: BufRead>(mut reader: R, opt: &Opt) -> Fallible<ParsedInput> {
+    if opt.skip_pretty {
+        return read_prettied_input(reader, opt.strict);
+    }
+
+    let configuration_filter = opt.configuration_filter.as_ref();
+    let mut projects = vec![];
+    let mut errors = vec![];
+    let mut trees = vec![];
+    let mut resolved = vec![];
+    let list = match opt.input_format {
+        InputFormat::Text => {
+            let mut text = String::new();
+            reader.read_to_string(&mut text)?;
+            if matches!(opt.format, FormatType::Dot | FormatType::Mermaid) {
+                trees = parse_dependency_tree(&mut text.as_bytes())?;
+                if let Some(filter) = configuration_filter {
+                    trees.retain(|tree| {
+                        tree.configuration
+                            .as_ref()
+                            .map_or(true, |data| filter.is_match(data))
+                    });
+                }
+            }
+
+            let mut list = vec![];
+            for (entry, modules) in parse_tree_entries(
+                &mut text.as_bytes(),
+                configuration_filter,
+                (!opt.strict).then_some(&mut errors),
+            )? {
+                match entry {
+                    TreeEntry::Dependency {
+                        coordinate,
+                        constraint,
+                    } => {
+                        if constraint && opt.exclude_constraints {
+                            info!(dep_name = %coordinate, "exclude the dependency constraint");
+                            continue;
+                        }
+                        list.push((coordinate, modules));
+                    }
+                    TreeEntry::Project(project) if opt.internal_projects => {
+                        projects.push((project, modules))
+                    }
+                    TreeEntry::Project(_) => {}
+                }
+            }
+            list
+        }
+        InputFormat::HtmlReport => {
+            let mut report = String::new();
+            reader.read_to_string(&mut report)?;
+            parse_dependency_report(&report, configuration_filter)?
+        }
+        InputFormat::Lockfile => parse_lockfile(reader, configuration_filter)?
+            .into_iter()
+            .map(|coordinate| (coordinate, vec![]))
+            .collect(),
+        InputFormat::MavenInstall => parse_maven_install(reader)?
+            .into_iter()
+            .map(|coordinate| (coordinate, vec![]))
+            .collect(),
+        InputFormat::Pom => {
+            let mut xml = String::new();
+            reader.read_to_string(&mut xml)?;
+            parse_pom_dependencies(&xml)?
+                .into_iter()
+                .map(|coordinate| (coordinate, vec![]))
+                .collect()
+        }
+        InputFormat::Jsonl => {
+            let mut list = vec![];
+            for (coordinate, data) in read_jsonl(reader)? {
+                if let Some(data) = data {
+                    resolved.push((coordinate.clone(), data));
+                }
+                list.push((coordinate, vec![]));
+            }
+            list
+        }
+    };
+
+    Ok(ParsedInput {
+        dependencies: list
+            .into_iter()
+            .map(|(coordinate, modules)| (coordinate, Annotation::default(), modules))
+            .collect(),
+        projects,
+        errors,
+        trees,
+        resolved,
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use oss_info_maven::function::maven::PomDependency;
 
     #[test]
     fn struct_opt() {
@@ -191,4 +3423,248 @@ mod tests {
     fn struct_opt_help() {
         Opt::command().print_help().unwrap();
     }
+
+    #[test]
+    fn with_default_config() {
+        let config = r#"
+strict = true
+modules = true
+exclude = ["com.mycompany.*"]
+"#;
+        let mut command = Opt::command();
+        for (name, value) in parse_config(config).unwrap() {
+            command = with_default(command, &name, value).unwrap();
+        }
+        let parse = |args: &[&str]| {
+            let args = ["oss-info-maven"].iter().chain(args);
+            Opt::from_arg_matches(&command.clone().try_get_matches_from(args).unwrap()).unwrap()
+        };
+
+        let opt = parse(&[]);
+        assert!(opt.strict);
+        assert!(opt.modules);
+        assert_eq!(1, opt.exclude.len());
+
+        // the defaults don't conflict with the command line options.
+        assert!(parse(&["--skip-invalid"]).skip_invalid);
+        assert!(!parse(&["--strict=false"]).strict);
+
+        let opt = parse(&["--modules", "--exclude", "a.*", "--exclude", "b.*"]);
+        assert!(opt.modules);
+        assert_eq!(2, opt.exclude.len());
+
+        assert!(with_default(Opt::command(), "unknown", ConfigValue::Bool(true)).is_err());
+        assert!(with_default(Opt::command(), "strict", ConfigValue::Str("yes".into())).is_err());
+    }
+
+    #[test]
+    fn table_report_cancelled() {
+        let opt = Opt::parse_from(["oss-info-maven", "--modules"]);
+        let (annotations, modules, sources) = (HashMap::new(), HashMap::new(), HashMap::new());
+        let (link_statuses, vulnerabilities) = (HashMap::new(), HashMap::new());
+        let projects = BTreeMap::new();
+        let coordinate = "androidx.core:core:1.12.0".parse::<Coordinate>().unwrap();
+        let cancelled = HashMap::from([(coordinate.clone(), Cancellation::Interrupted)]);
+        let report = TableReport::new(
+            &opt,
+            &annotations,
+            &modules,
+            &sources,
+            &link_statuses,
+            &vulnerabilities,
+            &projects,
+        )
+        .with_cancelled(&cancelled);
+
+        assert_eq!(
+            Some(&"Status"),
+            report.header.get(report.header.len() - 2),
+            "the status is before the modules",
+        );
+        let record =
+            report.cancelled_record(&coordinate, report.cancellation(&coordinate).unwrap());
+        assert_eq!("androidx.core:core", record[0]);
+        assert_eq!("1.12.0", record[1]);
+        assert_eq!("interrupted", record[record.len() - 2]);
+    }
+
+    #[test]
+    fn bom_artifacts_managed() {
+        let pom =
+            |artifact_id: &str, packaging: Option<&str>, managed: &[(&str, Option<&str>)]| POM {
+                packaging: packaging.map(Into::into),
+                dependency_management: managed
+                    .iter()
+                    .map(|(artifact_id, version)| PomDependency {
+                        group_id: "com.example".into(),
+                        artifact_id: artifact_id.to_string(),
+                        version: version.map(Into::into),
+                        scope: None,
+                        optional: false,
+                    })
+                    .collect(),
+                ..POM::new(Some("com.example"), artifact_id, "1.0.0")
+            };
+        let bom = pom(
+            "example-bom",
+            Some("pom"),
+            &[
+                ("core", Some("1.2.0")),
+                ("ui", Some("1.3.0")),
+                ("nested-bom", Some("2.0.0")),
+                ("tooling", Some("${tooling.version}")),
+                ("unmanaged", None),
+                ("core", Some("1.2.1")),
+            ],
+        );
+        let mut dep_map = ResolvedMap::new();
+        dep_map.insert("com.example:example-bom:1.0.0".parse().unwrap(), None);
+        dep_map.insert("com.example:ui:1.1.0".parse().unwrap(), None);
+
+        let actual = bom_artifacts(&dep_map, &bom);
+
+        let expected = ["com.example:core:1.2.0", "com.example:nested-bom:2.0.0"]
+            .iter()
+            .map(|data| data.parse::<Coordinate>().unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            expected, actual,
+            "the artifact in the input keeps its version"
+        );
+
+        // the nested BOM is expanded when it is resolved.
+        for coordinate in actual {
+            dep_map.insert(coordinate, None);
+        }
+        let nested = pom(
+            "nested-bom",
+            Some("pom"),
+            &[("core", Some("1.2.0")), ("test", Some("2.0.0"))],
+        );
+        assert_eq!(
+            vec!["com.example:test:2.0.0".parse::<Coordinate>().unwrap()],
+            bom_artifacts(&dep_map, &nested),
+        );
+
+        let jar = pom("jar", None, &[("extra", Some("1.0.0"))]);
+        assert!(bom_artifacts(&dep_map, &jar).is_empty());
+    }
+
+    #[test]
+    fn artifact_coordinate_kmp_platform() {
+        let coordinate = "org.jetbrains.kotlinx:kotlinx-coroutines-core:1.7.3"
+            .parse::<Coordinate>()
+            .unwrap();
+        let platform_pom = POM::new(
+            Some("org.jetbrains.kotlinx"),
+            "kotlinx-coroutines-core-jvm",
+            "1.7.3",
+        );
+        assert_eq!(
+            "org.jetbrains.kotlinx:kotlinx-coroutines-core-jvm:1.7.3",
+            artifact_coordinate(&coordinate, &platform_pom, Some("jvm")).to_string(),
+        );
+
+        // the artifactId of the POM may differ in case from the coordinate.
+        let coordinate = "com.example:legacylib:1.0.0".parse::<Coordinate>().unwrap();
+        let pom = POM::new(Some("com.example"), "LegacyLib", "1.0.0");
+        assert_eq!(coordinate, artifact_coordinate(&coordinate, &pom, None));
+        assert_eq!(
+            coordinate,
+            artifact_coordinate(&coordinate, &pom, Some("jvm"))
+        );
+    }
+
+    #[test]
+    fn sort_dep_map_stable() {
+        let mut dep_map = ResolvedMap::new();
+        for (coordinate, licenses) in [
+            ("com.squareup.okhttp3:okhttp:4.11.0", vec![SPDX::Apache20]),
+            ("org.slf4j:slf4j-api:2.0.9", vec![SPDX::MIT]),
+            ("androidx.core:core:1.12.0", vec![SPDX::Apache20]),
+            ("com.squareup.okio:okio:3.6.0", vec![SPDX::Apache20]),
+        ] {
+            let coordinate = coordinate.parse::<Coordinate>().unwrap();
+            let pom = POM {
+                licenses,
+                ..POM::new(Some(&coordinate.group_id), &coordinate.artifact_id, "1.0.0")
+            };
+            dep_map.insert(coordinate, Some((pom, None)));
+        }
+
+        sort_dep_map(
+            &mut dep_map,
+            Some(SortKey::License),
+            None,
+            LicenseConjunction::And,
+        );
+
+        let actual = dep_map.keys().map(|data| data.artifact_id.as_str());
+        assert_eq!(
+            vec!["okhttp", "core", "okio", "slf4j-api"],
+            actual.collect::<Vec<_>>(),
+            "the artifacts of the same license keep the input order",
+        );
+    }
+
+    #[test]
+    fn sort_dep_map_group_then_sort() {
+        let mut dep_map = ResolvedMap::new();
+        for (coordinate, name) in [
+            ("com.squareup.okio:okio:3.6.0", Some("Okio")),
+            ("com.squareup.okhttp3:okhttp:4.11.0", Some("OkHttp")),
+            ("com.squareup.okhttp3:logging-interceptor:4.11.0", None),
+            ("androidx.core:core:1.12.0", Some("Core")),
+        ] {
+            let coordinate = coordinate.parse::<Coordinate>().unwrap();
+            let pom = POM {
+                name: name.map(Into::into),
+                ..POM::new(Some(&coordinate.group_id), &coordinate.artifact_id, "1.0.0")
+            };
+            dep_map.insert(coordinate, Some((pom, None)));
+        }
+        dep_map.insert("com.example:missing:1.0.0".parse().unwrap(), None);
+
+        sort_dep_map(
+            &mut dep_map,
+            Some(SortKey::Name),
+            Some(GroupKey::Group),
+            LicenseConjunction::And,
+        );
+
+        let actual = dep_map.keys().map(|data| data.artifact_id.as_str());
+        assert_eq!(
+            vec!["core", "missing", "logging-interceptor", "okhttp", "okio"],
+            actual.collect::<Vec<_>>(),
+        );
+    }
+
+    #[test]
+    fn collapse_versions_okhttp() {
+        let mut lines = BTreeMap::<Coordinate, InputEntry>::new();
+        for (coordinate, source) in [
+            ("com.squareup.okhttp3:okhttp:4.11.0", "app.txt"),
+            ("com.squareup.okhttp3:okhttp:4.9.3", "lib.txt"),
+            ("androidx.core:core-ktx:1.9.0", "app.txt"),
+        ] {
+            lines.entry(coordinate.parse().unwrap()).or_default().merge(
+                Annotation::default(),
+                vec![],
+                Some(source),
+            );
+        }
+
+        let actual = collapse_versions(&mut lines);
+
+        let selected = "com.squareup.okhttp3:okhttp:4.11.0"
+            .parse::<Coordinate>()
+            .unwrap();
+        let expected = HashMap::from([(
+            selected.clone(),
+            vec!["4.9.3".to_string(), "4.11.0".to_string()],
+        )]);
+        assert_eq!(expected, actual);
+        assert_eq!(2, lines.len());
+        assert_eq!(vec!["app.txt", "lib.txt"], lines[&selected].sources);
+    }
 }
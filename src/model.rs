@@ -14,6 +14,16 @@
  * limitations under the License.
  */
 
+pub use annotation::*;
+pub use coordinate::*;
+pub use license_origin::*;
+pub use rich_version::*;
 pub use spdx::*;
+pub use version::*;
 
+mod annotation;
+mod coordinate;
+mod license_origin;
+mod rich_version;
 mod spdx;
+mod version;
@@ -0,0 +1,113 @@
+/*
+ * Copyright 2023 sukawasatoru
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::convert::Infallible;
+use std::str::FromStr;
+
+/// Freeform tags and note attached to a dependency by the input.
+///
+/// The text form is `[tag1, tag2] note`, both parts are optional.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Annotation {
+    pub tags: Vec<String>,
+    pub note: Option<String>,
+}
+
+impl Annotation {
+    pub fn is_empty(&self) -> bool {
+        self.tags.is_empty() && self.note.is_none()
+    }
+
+    /// Merge the `other` that was attached to the same dependency.
+    pub fn merge(&mut self, other: Annotation) {
+        for tag in other.tags {
+            if !self.tags.contains(&tag) {
+                self.tags.push(tag);
+            }
+        }
+
+        self.note = match (self.note.take(), other.note) {
+            (Some(a), Some(b)) if a != b => Some(format!("{}; {}", a, b)),
+            (a, b) => a.or(b),
+        };
+    }
+}
+
+impl FromStr for Annotation {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let (tags, note) = match s.strip_prefix('[').and_then(|data| data.split_once(']')) {
+            Some((tags, note)) => (
+                tags.split(',')
+                    .map(str::trim)
+                    .filter(|data| !data.is_empty())
+                    .map(str::to_owned)
+                    .collect(),
+                note.trim(),
+            ),
+            None => (vec![], s),
+        };
+
+        Ok(Self {
+            tags,
+            note: if note.is_empty() {
+                None
+            } else {
+                Some(note.into())
+            },
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_str_tags_and_note() {
+        let actual = "[flavor-x, ui] bundled only in flavor X"
+            .parse::<Annotation>()
+            .unwrap();
+        let expected = Annotation {
+            tags: vec!["flavor-x".into(), "ui".into()],
+            note: Some("bundled only in flavor X".into()),
+        };
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn from_str_note_only() {
+        let actual = " bundled only in flavor X ".parse::<Annotation>().unwrap();
+        let expected = Annotation {
+            tags: vec![],
+            note: Some("bundled only in flavor X".into()),
+        };
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn merge() {
+        let mut actual = "[a] note 1".parse::<Annotation>().unwrap();
+        actual.merge("[a, b] note 2".parse().unwrap());
+        let expected = Annotation {
+            tags: vec!["a".into(), "b".into()],
+            note: Some("note 1; note 2".into()),
+        };
+        assert_eq!(expected, actual);
+    }
+}
@@ -0,0 +1,364 @@
+/*
+ * Copyright 2023 sukawasatoru
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::prelude::*;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+/// `group:artifact[:version[:classifier]][@packaging]`
+///
+/// https://docs.gradle.org/current/userguide/dependency_management_terminology.html#sub:terminology_module
+#[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Coordinate {
+    pub group_id: String,
+    pub artifact_id: String,
+    pub version: Option<String>,
+    pub classifier: Option<String>,
+    pub packaging: Option<String>,
+}
+
+impl Coordinate {
+    pub fn new(group_id: impl Into<String>, artifact_id: impl Into<String>) -> Self {
+        Self {
+            group_id: group_id.into(),
+            artifact_id: artifact_id.into(),
+            version: None,
+            classifier: None,
+            packaging: None,
+        }
+    }
+
+    pub fn with_version(mut self, version: impl Into<String>) -> Self {
+        self.version = Some(version.into());
+        self
+    }
+
+    /// Check the characters of the parts to find the malformed input before the requests.
+    ///
+    /// The group id and the artifact id are `[A-Za-z0-9_.-]+` that Maven accepts. The other parts
+    /// must not have the whitespaces, `/` and `\` that break the URL of the repository.
+    pub fn validate(&self) -> Fallible<()> {
+        for (name, value) in [
+            ("group id", &self.group_id),
+            ("artifact id", &self.artifact_id),
+        ] {
+            if let Some(data) = value
+                .chars()
+                .find(|data| !data.is_ascii_alphanumeric() && !"_.-".contains(*data))
+            {
+                bail!("unexpected character {:?} in the {}: {}", data, name, self);
+            }
+        }
+        for (name, value) in [
+            ("version", &self.version),
+            ("classifier", &self.classifier),
+            ("packaging", &self.packaging),
+        ] {
+            let value = match value {
+                Some(data) => data,
+                None => continue,
+            };
+            if let Some(data) = value
+                .chars()
+                .find(|data| data.is_whitespace() || data.is_control() || "/\\".contains(*data))
+            {
+                bail!("unexpected character {:?} in the {}: {}", data, name, self);
+            }
+        }
+        Ok(())
+    }
+
+    /// `group:artifact`, the module identifier in Gradle.
+    pub fn module_id(&self) -> String {
+        format!("{}:{}", self.group_id, self.artifact_id)
+    }
+
+    /// `pkg:maven/group/artifact@version?classifier=classifier&type=packaging`
+    ///
+    /// https://github.com/package-url/purl-spec/blob/master/PURL-TYPES.rst#maven
+    pub fn to_purl(&self) -> String {
+        let mut purl = format!(
+            "pkg:maven/{}/{}",
+            encode_purl_component(&self.group_id),
+            encode_purl_component(&self.artifact_id),
+        );
+        if let Some(version) = &self.version {
+            purl.push('@');
+            purl.push_str(&encode_purl_component(version));
+        }
+
+        let qualifiers = [("classifier", &self.classifier), ("type", &self.packaging)]
+            .into_iter()
+            .filter_map(|(key, value)| {
+                Some(format!(
+                    "{}={}",
+                    key,
+                    encode_purl_component(value.as_ref()?)
+                ))
+            })
+            .collect::<Vec<_>>();
+        if !qualifiers.is_empty() {
+            purl.push('?');
+            purl.push_str(&qualifiers.join("&"));
+        }
+
+        purl
+    }
+
+    fn from_purl(s: &str) -> Fallible<Self> {
+        let source = s
+            .strip_prefix("pkg:maven/")
+            .with_context(|| format!("unsupported purl type: {}", s))?;
+        // the subpath is not used by maven.
+        let source = source.split_once('#').map_or(source, |(data, _)| data);
+        let (source, qualifiers) = match source.split_once('?') {
+            Some((source, qualifiers)) => (source, Some(qualifiers)),
+            None => (source, None),
+        };
+        let (source, version) = match source.rsplit_once('@') {
+            Some((source, version)) => (source, Some(decode_purl_component(version)?)),
+            None => (source, None),
+        };
+        let (group_id, artifact_id) = source
+            .split_once('/')
+            .with_context(|| format!("missing namespace: {}", s))?;
+        ensure!(!group_id.is_empty(), "missing group id: {}", s);
+        ensure!(!artifact_id.is_empty(), "missing artifact id: {}", s);
+
+        let mut coordinate = Self::new(
+            decode_purl_component(group_id)?,
+            decode_purl_component(artifact_id)?,
+        );
+        coordinate.version = version.filter(|data| !data.is_empty());
+        for qualifier in qualifiers.into_iter().flat_map(|data| data.split('&')) {
+            match qualifier.split_once('=') {
+                Some(("classifier", value)) => {
+                    coordinate.classifier = Some(decode_purl_component(value)?)
+                }
+                Some(("type", value)) => coordinate.packaging = Some(decode_purl_component(value)?),
+                _ => debug!(%qualifier, "ignore the qualifier"),
+            }
+        }
+
+        Ok(coordinate)
+    }
+}
+
+fn encode_purl_component(value: &str) -> String {
+    let mut encoded = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'.' | b'-' | b'_' | b'~' => {
+                encoded.push(byte as char)
+            }
+            _ => encoded.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    encoded
+}
+
+fn decode_purl_component(value: &str) -> Fallible<String> {
+    let mut decoded = Vec::with_capacity(value.len());
+    let mut bytes = value.bytes();
+    while let Some(byte) = bytes.next() {
+        if byte != b'%' {
+            decoded.push(byte);
+            continue;
+        }
+        let hex = [bytes.next(), bytes.next()]
+            .into_iter()
+            .collect::<Option<Vec<_>>>()
+            .context("unexpected percent-encoding")?;
+        let hex = std::str::from_utf8(&hex)?;
+        decoded.push(u8::from_str_radix(hex, 16).context("unexpected percent-encoding")?);
+    }
+    Ok(String::from_utf8(decoded)?)
+}
+
+impl Display for Coordinate {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}", self.group_id, self.artifact_id)?;
+
+        match (&self.version, &self.classifier) {
+            (Some(version), Some(classifier)) => write!(f, ":{}:{}", version, classifier)?,
+            (Some(version), None) => write!(f, ":{}", version)?,
+            (None, Some(classifier)) => write!(f, "::{}", classifier)?,
+            (None, None) => {}
+        }
+
+        if let Some(packaging) = &self.packaging {
+            write!(f, "@{}", packaging)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl FromStr for Coordinate {
+    type Err = anyhow::Error;
+
+    /// The purl such as `pkg:maven/androidx.core/core-ktx@1.12.0` is also accepted.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let source = s.trim();
+        if source.starts_with("pkg:") {
+            return Self::from_purl(source);
+        }
+        let (source, packaging) = match source.split_once('@') {
+            Some((source, packaging)) => (source, Some(packaging.trim())),
+            None => (source, None),
+        };
+
+        let segments = source.split(':').map(str::trim).collect::<Vec<_>>();
+        ensure!(
+            (2..=4).contains(&segments.len()),
+            "unexpected format: {}",
+            s,
+        );
+
+        let group_id = segments[0];
+        ensure!(!group_id.is_empty(), "missing group id: {}", s);
+
+        let artifact_id = segments[1];
+        ensure!(!artifact_id.is_empty(), "missing artifact id: {}", s);
+
+        let non_empty = |data: Option<&&str>| {
+            data.filter(|data| !data.is_empty())
+                .map(|data| data.to_string())
+        };
+
+        Ok(Self {
+            group_id: group_id.into(),
+            artifact_id: artifact_id.into(),
+            version: non_empty(segments.get(2)),
+            classifier: non_empty(segments.get(3)),
+            packaging: non_empty(packaging.as_ref()),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_str_without_version() {
+        let actual = "androidx.core:core-ktx".parse::<Coordinate>().unwrap();
+        assert_eq!(Coordinate::new("androidx.core", "core-ktx"), actual);
+    }
+
+    #[test]
+    fn from_str_full() {
+        let actual = "org.lwjgl:lwjgl:3.3.3:natives-linux@jar"
+            .parse::<Coordinate>()
+            .unwrap();
+        let expected = Coordinate {
+            group_id: "org.lwjgl".into(),
+            artifact_id: "lwjgl".into(),
+            version: Some("3.3.3".into()),
+            classifier: Some("natives-linux".into()),
+            packaging: Some("jar".into()),
+        };
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn from_str_unexpected_format() {
+        assert!("aaa".parse::<Coordinate>().is_err());
+        assert!(":core-ktx".parse::<Coordinate>().is_err());
+        assert!("androidx.core:".parse::<Coordinate>().is_err());
+        assert!("a:b:c:d:e".parse::<Coordinate>().is_err());
+    }
+
+    #[test]
+    fn validate_characters() {
+        assert!("org.lwjgl:lwjgl:3.3.3:natives-linux@jar"
+            .parse::<Coordinate>()
+            .unwrap()
+            .validate()
+            .is_ok());
+        assert!("com.example:lib$:1.0.0"
+            .parse::<Coordinate>()
+            .unwrap()
+            .validate()
+            .is_err());
+        assert!("com.example:lib:1.0/0"
+            .parse::<Coordinate>()
+            .unwrap()
+            .validate()
+            .is_err());
+        assert!(Coordinate::new("com.example", "lib")
+            .with_version("1.0 0")
+            .validate()
+            .is_err());
+    }
+
+    #[test]
+    fn display_round_trip() {
+        for source in [
+            "androidx.core:core-ktx",
+            "androidx.core:core-ktx:1.12.0",
+            "org.lwjgl:lwjgl:3.3.3:natives-linux",
+            "org.lwjgl:lwjgl::natives-linux@jar",
+            "com.google.guava:guava:32.1.2-android@jar",
+        ] {
+            assert_eq!(source, source.parse::<Coordinate>().unwrap().to_string());
+        }
+    }
+
+    #[test]
+    fn to_purl() {
+        assert_eq!(
+            "pkg:maven/androidx.core/core-ktx@1.12.0",
+            "androidx.core:core-ktx:1.12.0"
+                .parse::<Coordinate>()
+                .unwrap()
+                .to_purl(),
+        );
+        assert_eq!(
+            "pkg:maven/org.lwjgl/lwjgl@3.3.3?classifier=natives-linux&type=jar",
+            "org.lwjgl:lwjgl:3.3.3:natives-linux@jar"
+                .parse::<Coordinate>()
+                .unwrap()
+                .to_purl(),
+        );
+        assert_eq!(
+            "pkg:maven/com.example/example@1.0.0%2Bbuild",
+            "com.example:example:1.0.0+build"
+                .parse::<Coordinate>()
+                .unwrap()
+                .to_purl(),
+        );
+    }
+
+    #[test]
+    fn from_str_purl() {
+        for source in [
+            "androidx.core:core-ktx",
+            "androidx.core:core-ktx:1.12.0",
+            "org.lwjgl:lwjgl:3.3.3:natives-linux@jar",
+            "com.example:example:1.0.0+build",
+        ] {
+            let coordinate = source.parse::<Coordinate>().unwrap();
+            assert_eq!(coordinate, coordinate.to_purl().parse().unwrap());
+        }
+
+        assert!("pkg:npm/left-pad@1.3.0".parse::<Coordinate>().is_err());
+        assert!("pkg:maven/core-ktx@1.12.0".parse::<Coordinate>().is_err());
+        assert!("pkg:maven/androidx.core/core-ktx@1.12.0%2"
+            .parse::<Coordinate>()
+            .is_err());
+    }
+}
@@ -0,0 +1,74 @@
+/*
+ * Copyright 2023 sukawasatoru
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::model::Coordinate;
+use std::fmt::{Display, Formatter};
+
+/// Where the licenses of the artifact came from if not from its POM.
+///
+/// The reports mark the licenses that have the origin so that they are not published as the
+/// licenses of the POM.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum LicenseOrigin {
+    /// Replaced by `--overrides` with the note of the override.
+    Overridden { note: Option<String> },
+
+    /// Reused from the `source` sibling by `--infer-group-licenses` that needs the review.
+    Inferred { source: Coordinate },
+}
+
+impl LicenseOrigin {
+    /// Short label such as `overridden` for the places that have no room for the details.
+    pub fn label(&self) -> &'static str {
+        match self {
+            LicenseOrigin::Overridden { .. } => "overridden",
+            LicenseOrigin::Inferred { .. } => "inferred",
+        }
+    }
+}
+
+impl Display for LicenseOrigin {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LicenseOrigin::Overridden { note: Some(note) } => write!(f, "overridden ({})", note),
+            LicenseOrigin::Overridden { note: None } => f.write_str("overridden"),
+            LicenseOrigin::Inferred { source } => write!(f, "inferred from {}", source),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn display_license_origin() {
+        assert_eq!(
+            "overridden (relicensed)",
+            LicenseOrigin::Overridden {
+                note: Some("relicensed".into()),
+            }
+            .to_string(),
+        );
+        assert_eq!(
+            "inferred from androidx.compose.ui:ui:1.5.0",
+            LicenseOrigin::Inferred {
+                source: "androidx.compose.ui:ui:1.5.0".parse().unwrap(),
+            }
+            .to_string(),
+        );
+    }
+}
@@ -0,0 +1,273 @@
+/*
+ * Copyright 2023 sukawasatoru
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::model::Version;
+use crate::prelude::*;
+use std::str::FromStr;
+
+/// Gradle's rich version such as `{strictly [1.0, 2.0); prefer 1.5}` or
+/// `{require 1.0; reject 1.1 & 1.2}`. The plain version such as `1.0` is the `require`.
+///
+/// https://docs.gradle.org/current/userguide/rich_versions.html
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct RichVersion {
+    pub strictly: Option<VersionConstraint>,
+    pub require: Option<VersionConstraint>,
+    pub prefer: Option<String>,
+    pub reject: Vec<VersionConstraint>,
+}
+
+impl RichVersion {
+    /// Resolve the effective version from the `versions` of the maven-metadata.xml.
+    ///
+    /// The versions that match the `reject` are removed, and the `strictly` or the `require`
+    /// narrows the rest. The `prefer` is chosen if it remains, otherwise the highest one.
+    pub fn resolve(&self, versions: &[String]) -> Option<String> {
+        let bound = self.strictly.as_ref().or(self.require.as_ref());
+        let candidates = versions
+            .iter()
+            .filter(|version| !self.reject.iter().any(|data| data.matches(version)))
+            .filter(|version| bound.map_or(true, |data| data.matches(version)))
+            .collect::<Vec<_>>();
+
+        if let Some(prefer) = &self.prefer {
+            let prefer = Version::from(prefer.as_str());
+            if let Some(found) = candidates
+                .iter()
+                .find(|data| Version::from(data.as_str()) == prefer)
+            {
+                return Some(found.to_string());
+            }
+        }
+
+        candidates
+            .into_iter()
+            .max_by_key(|data| Version::from(data.as_str()))
+            .cloned()
+    }
+}
+
+impl FromStr for RichVersion {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let source = s.trim();
+        let source = match source
+            .strip_prefix('{')
+            .and_then(|data| data.strip_suffix('}'))
+        {
+            Some(data) => data,
+            None => {
+                return Ok(Self {
+                    require: Some(source.parse()?),
+                    ..Default::default()
+                })
+            }
+        };
+
+        let mut rich_version = Self::default();
+        for part in source
+            .split(';')
+            .map(str::trim)
+            .filter(|data| !data.is_empty())
+        {
+            let (keyword, value) = part
+                .split_once(' ')
+                .with_context(|| format!("missing version: {}", s))?;
+            let value = value.trim();
+            match keyword {
+                "strictly" => rich_version.strictly = Some(value.parse()?),
+                "require" => rich_version.require = Some(value.parse()?),
+                "prefer" => rich_version.prefer = Some(value.into()),
+                "reject" => {
+                    for data in value.split('&') {
+                        rich_version.reject.push(data.parse()?);
+                    }
+                }
+                _ => bail!("unexpected keyword `{}`: {}", keyword, s),
+            }
+        }
+
+        Ok(rich_version)
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum VersionConstraint {
+    /// `1.0`
+    Exact(String),
+
+    /// `1.+`, or `+` for any version.
+    Prefix(String),
+
+    /// `[1.0, 2.0)`, `[1.0,)` or `(,2.0]`. The `bool` is `true` if the bound is inclusive.
+    Range {
+        lower: Option<(String, bool)>,
+        upper: Option<(String, bool)>,
+    },
+}
+
+impl VersionConstraint {
+    pub fn matches(&self, version: &str) -> bool {
+        let version = Version::from(version);
+        match self {
+            Self::Exact(data) => version == Version::from(data.as_str()),
+            Self::Prefix(data) => version.as_str().starts_with(data.as_str()),
+            Self::Range { lower, upper } => {
+                let lower_matches = lower.as_ref().map_or(true, |(data, inclusive)| {
+                    let data = Version::from(data.as_str());
+                    if *inclusive {
+                        data <= version
+                    } else {
+                        data < version
+                    }
+                });
+                let upper_matches = upper.as_ref().map_or(true, |(data, inclusive)| {
+                    let data = Version::from(data.as_str());
+                    if *inclusive {
+                        version <= data
+                    } else {
+                        version < data
+                    }
+                });
+                lower_matches && upper_matches
+            }
+        }
+    }
+}
+
+impl FromStr for VersionConstraint {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let source = s.trim();
+        ensure!(!source.is_empty(), "empty version");
+
+        if let Some(prefix) = source.strip_suffix('+') {
+            return Ok(Self::Prefix(prefix.into()));
+        }
+
+        let lower_inclusive = match source.chars().next() {
+            Some('[') => true,
+            Some('(') => false,
+            _ => return Ok(Self::Exact(source.into())),
+        };
+        let upper_inclusive = match source.chars().last() {
+            Some(']') => true,
+            Some(')') => false,
+            _ => bail!("unclosed range: {}", s),
+        };
+        let (lower, upper) = source[1..source.len() - 1]
+            .split_once(',')
+            .with_context(|| format!("missing `,` of range: {}", s))?;
+        let bound = |data: &str, inclusive: bool| {
+            let data = data.trim();
+            (!data.is_empty()).then(|| (data.to_owned(), inclusive))
+        };
+
+        Ok(Self::Range {
+            lower: bound(lower, lower_inclusive),
+            upper: bound(upper, upper_inclusive),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn versions() -> Vec<String> {
+        [
+            "1.5.0", "1.6.0", "1.6.10", "1.7.0-RC", "1.7.0", "1.7.10", "1.8.0",
+        ]
+        .map(Into::into)
+        .to_vec()
+    }
+
+    #[test]
+    fn from_str_rich_version() {
+        let actual = "{strictly [1.6, 1.8); prefer 1.6.10; reject 1.7.0 & 1.7.0-RC}"
+            .parse::<RichVersion>()
+            .unwrap();
+        let expected = RichVersion {
+            strictly: Some(VersionConstraint::Range {
+                lower: Some(("1.6".into(), true)),
+                upper: Some(("1.8".into(), false)),
+            }),
+            require: None,
+            prefer: Some("1.6.10".into()),
+            reject: vec![
+                VersionConstraint::Exact("1.7.0".into()),
+                VersionConstraint::Exact("1.7.0-RC".into()),
+            ],
+        };
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn from_str_plain() {
+        let actual = "1.6.10".parse::<RichVersion>().unwrap();
+        assert_eq!(
+            Some(VersionConstraint::Exact("1.6.10".into())),
+            actual.require,
+        );
+
+        assert!("{unknown 1.0}".parse::<RichVersion>().is_err());
+        assert!("[1.0".parse::<RichVersion>().is_err());
+    }
+
+    #[test]
+    fn resolve_strictly() {
+        let rich_version = "{strictly 1.6.10}".parse::<RichVersion>().unwrap();
+        assert_eq!(Some("1.6.10".into()), rich_version.resolve(&versions()));
+
+        let rich_version = "{strictly 1.6.11}".parse::<RichVersion>().unwrap();
+        assert_eq!(None, rich_version.resolve(&versions()));
+    }
+
+    #[test]
+    fn resolve_range() {
+        let rich_version = "[1.6, 1.8)".parse::<RichVersion>().unwrap();
+        assert_eq!(Some("1.7.10".into()), rich_version.resolve(&versions()));
+
+        let rich_version = "{require [1.6, 1.8); reject 1.7.+}"
+            .parse::<RichVersion>()
+            .unwrap();
+        assert_eq!(Some("1.6.10".into()), rich_version.resolve(&versions()));
+
+        let rich_version = "{strictly [1.6, 1.8); prefer 1.6.0}"
+            .parse::<RichVersion>()
+            .unwrap();
+        assert_eq!(Some("1.6.0".into()), rich_version.resolve(&versions()));
+    }
+
+    #[test]
+    fn resolve_prefer_out_of_range() {
+        let rich_version = "{strictly (,1.7]; prefer 1.8.0}"
+            .parse::<RichVersion>()
+            .unwrap();
+        assert_eq!(Some("1.7.0".into()), rich_version.resolve(&versions()));
+    }
+
+    #[test]
+    fn resolve_dynamic() {
+        let rich_version = "1.+".parse::<RichVersion>().unwrap();
+        assert_eq!(Some("1.8.0".into()), rich_version.resolve(&versions()));
+
+        let rich_version = "{prefer 1.5.0}".parse::<RichVersion>().unwrap();
+        assert_eq!(Some("1.5.0".into()), rich_version.resolve(&versions()));
+    }
+}
@@ -16,20 +16,197 @@
 
 //! https://spdx.org/licenses/
 
+use crate::prelude::*;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use std::convert::Infallible;
 use std::fmt::{Display, Formatter};
 use std::str::FromStr;
 
-#[derive(Debug, Eq, PartialEq)]
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
 pub enum SPDX {
     Apache20,
     BSD2,
     BSD3,
     ISC,
     MIT,
+
+    /// Android Software Development Kit License of the Google Maven artifacts such as
+    /// `com.google.android.gms:play-services-*` that is not an open source license.
+    ///
+    /// https://developer.android.com/studio/terms
+    AndroidSdk,
+
     Other(String),
 }
 
+impl SPDX {
+    /// Recognize the license from the URL of the `<license>` that has the unrecognized name.
+    ///
+    /// The scheme, `www.`, the trailing `/` and the extension such as `.txt` are ignored.
+    pub fn from_url(url: &str) -> Option<Self> {
+        let url = url.trim().to_ascii_lowercase();
+        let url = url
+            .trim_start_matches("https://")
+            .trim_start_matches("http://")
+            .trim_start_matches("www.")
+            .trim_end_matches('/');
+        let url = [".html", ".htm", ".txt", ".php", ".md"]
+            .into_iter()
+            .find_map(|data| url.strip_suffix(data))
+            .unwrap_or(url);
+
+        if let Some(id) = url.strip_prefix("spdx.org/licenses/") {
+            return match id.parse() {
+                Ok(Self::Other(_)) => None,
+                Ok(data) => Some(data),
+                Err(e) => match e {},
+            };
+        }
+
+        match url {
+            "apache.org/licenses/license-2.0"
+            | "opensource.org/licenses/apache-2.0"
+            | "opensource.org/license/apache-2-0"
+            | "choosealicense.com/licenses/apache-2.0" => Some(Self::Apache20),
+            "opensource.org/licenses/bsd-license"
+            | "opensource.org/licenses/bsd-2-clause"
+            | "opensource.org/license/bsd-2-clause"
+            | "choosealicense.com/licenses/bsd-2-clause" => Some(Self::BSD2),
+            "opensource.org/licenses/bsd-3-clause"
+            | "opensource.org/license/bsd-3-clause"
+            | "choosealicense.com/licenses/bsd-3-clause" => Some(Self::BSD3),
+            "opensource.org/licenses/isc-license"
+            | "opensource.org/licenses/isc"
+            | "opensource.org/license/isc-license" => Some(Self::ISC),
+            "opensource.org/licenses/mit-license"
+            | "opensource.org/licenses/mit"
+            | "opensource.org/license/mit"
+            | "choosealicense.com/licenses/mit" => Some(Self::MIT),
+            "developer.android.com/studio/terms" | "developer.android.com/sdk/terms" => {
+                Some(Self::AndroidSdk)
+            }
+            _ => None,
+        }
+    }
+
+    /// Identifier of the license expression such as `Apache-2.0`.
+    ///
+    /// The name that is not the SPDX identifier such as `Custom License` is the `LicenseRef-`
+    /// identifier such as `LicenseRef-Custom-License`.
+    pub fn expression_id(&self) -> String {
+        let is_id = |data: &str| {
+            !data.is_empty()
+                && data
+                    .chars()
+                    .all(|data| data.is_ascii_alphanumeric() || "-.+".contains(data))
+        };
+        match self {
+            Self::AndroidSdk => "LicenseRef-Android-SDK".into(),
+            Self::Other(data) => match data.split_once(" WITH ") {
+                Some((license, exception)) if is_id(license) && is_id(exception) => data.clone(),
+                None if is_id(data) => data.clone(),
+                _ => {
+                    let mut id = "LicenseRef-".to_string();
+                    for c in data.chars() {
+                        if c.is_ascii_alphanumeric() || c == '.' {
+                            id.push(c);
+                        } else if !id.ends_with('-') {
+                            id.push('-');
+                        }
+                    }
+                    id.trim_end_matches('-').into()
+                }
+            },
+            _ => self.to_string(),
+        }
+    }
+
+    /// Whether the license is not open source and needs the review of the terms.
+    pub fn is_proprietary(&self) -> bool {
+        matches!(self, Self::AndroidSdk)
+    }
+
+    /// First-pass compliance obligations from the built-in table.
+    ///
+    /// Returns `None` if the license is not in the table. This is not legal advice.
+    pub fn obligations(&self) -> Option<Obligations> {
+        let permissive = Obligations {
+            attribution: true,
+            source_offer: false,
+            modification_disclosure: false,
+            patent: "No express patent grant.",
+        };
+
+        match self {
+            Self::Apache20 => Some(Obligations {
+                attribution: true,
+                source_offer: false,
+                modification_disclosure: true,
+                patent: "Express patent grant, terminated if you initiate patent litigation.",
+            }),
+            Self::BSD2 | Self::BSD3 | Self::ISC | Self::MIT => Some(permissive),
+            Self::AndroidSdk | Self::Other(_) => None,
+        }
+    }
+}
+
+/// Operator of the license expression that combines the multiple licenses of a POM.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum LicenseConjunction {
+    /// One of the licenses can be chosen. Most POMs that list multiple licenses mean this.
+    #[default]
+    Or,
+
+    /// All of the licenses apply.
+    And,
+}
+
+impl LicenseConjunction {
+    /// License expression such as `BSD-2-Clause OR Apache-2.0` of [SPDX::expression_id].
+    pub fn join(&self, licenses: &[SPDX]) -> String {
+        licenses
+            .iter()
+            .map(SPDX::expression_id)
+            .collect::<Vec<_>>()
+            .join(&format!(" {} ", self))
+    }
+}
+
+impl Display for LicenseConjunction {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Or => f.write_str("OR"),
+            Self::And => f.write_str("AND"),
+        }
+    }
+}
+
+impl FromStr for LicenseConjunction {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "or" => Ok(Self::Or),
+            "and" => Ok(Self::And),
+            _ => bail!("unexpected conjunction: {}", s),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Obligations {
+    /// Retain the copyright notice and the license text.
+    pub attribution: bool,
+
+    /// Offer the source code of the component.
+    pub source_offer: bool,
+
+    /// Disclose (state) the modifications to the component.
+    pub modification_disclosure: bool,
+
+    pub patent: &'static str,
+}
+
 impl Display for SPDX {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -38,23 +215,140 @@ impl Display for SPDX {
             Self::BSD3 => f.write_str("BSD-3-Clause"),
             Self::MIT => f.write_str("MIT"),
             Self::ISC => f.write_str("ISC"),
+            Self::AndroidSdk => f.write_str("Android Software Development Kit License"),
             Self::Other(data) => f.write_str(data),
         }
     }
 }
 
+impl Serialize for SPDX {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for SPDX {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = String::deserialize(deserializer)?;
+        Ok(value.parse().unwrap_or_else(|e: Infallible| match e {}))
+    }
+}
+
 impl FromStr for SPDX {
     type Err = Infallible;
 
+    /// The name is compared case-insensitively after collapsing the whitespaces.
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        Ok(match s {
-            "The Apache Software License, Version 2.0"
-            | "The Apache License, Version 2.0"
-            | "Apache 2.0" => Self::Apache20,
-            "Simplified BSD License" => Self::BSD2,
-            "ISC License" => Self::ISC,
-            "MIT License" => Self::MIT,
+        let normalized = s
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ")
+            .to_ascii_lowercase();
+        Ok(match normalized.as_str() {
+            "the apache software license, version 2.0"
+            | "the apache license, version 2.0"
+            | "apache license, version 2.0"
+            | "apache license 2.0"
+            | "apache software license - version 2.0"
+            | "apache 2.0"
+            | "apache 2"
+            | "apache-2.0" => Self::Apache20,
+            "simplified bsd license" | "bsd 2-clause license" | "bsd-2-clause" => Self::BSD2,
+            "new bsd license" | "bsd 3-clause license" | "bsd-3-clause" => Self::BSD3,
+            "isc license" | "isc" => Self::ISC,
+            "the mit license" | "mit license" | "mit" => Self::MIT,
+            "android software development kit license" | "android sdk license" => Self::AndroidSdk,
             _ => Self::Other(s.into()),
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn android_sdk() {
+        assert_eq!(
+            SPDX::AndroidSdk,
+            "Android Software Development Kit License".parse().unwrap(),
+        );
+        assert_eq!(
+            Some(SPDX::AndroidSdk),
+            SPDX::from_url("https://developer.android.com/studio/terms.html"),
+        );
+        assert_eq!(
+            Some(SPDX::AndroidSdk),
+            SPDX::from_url("http://developer.android.com/sdk/terms/"),
+        );
+        assert_eq!(
+            Some(SPDX::Apache20),
+            SPDX::from_url("https://www.apache.org/licenses/LICENSE-2.0.txt")
+        );
+        assert!(SPDX::AndroidSdk.is_proprietary());
+        assert!(!SPDX::Apache20.is_proprietary());
+    }
+
+    #[test]
+    fn from_str_normalized() {
+        assert_eq!(
+            SPDX::Apache20,
+            "Apache License,  Version 2.0".parse().unwrap()
+        );
+        assert_eq!(SPDX::MIT, "The MIT License".parse().unwrap());
+        assert_eq!(SPDX::BSD3, "New BSD License".parse().unwrap());
+        assert_eq!(
+            SPDX::Other("Custom  License".into()),
+            "Custom  License".parse().unwrap()
+        );
+    }
+
+    #[test]
+    fn from_url_table() {
+        assert_eq!(
+            Some(SPDX::Apache20),
+            SPDX::from_url("http://www.apache.org/licenses/LICENSE-2.0.txt"),
+        );
+        assert_eq!(
+            Some(SPDX::BSD2),
+            SPDX::from_url("http://www.opensource.org/licenses/bsd-license"),
+        );
+        assert_eq!(
+            Some(SPDX::MIT),
+            SPDX::from_url("https://opensource.org/licenses/MIT"),
+        );
+        assert_eq!(
+            Some(SPDX::BSD3),
+            SPDX::from_url("https://spdx.org/licenses/BSD-3-Clause.html"),
+        );
+        assert_eq!(
+            None,
+            SPDX::from_url("https://spdx.org/licenses/GPL-2.0.html")
+        );
+        assert_eq!(None, SPDX::from_url("https://example.com/LICENSE.txt"));
+    }
+
+    #[test]
+    fn license_conjunction_join() {
+        let licenses = [SPDX::BSD2, SPDX::Apache20];
+        assert_eq!(
+            "BSD-2-Clause OR Apache-2.0",
+            LicenseConjunction::Or.join(&licenses)
+        );
+        assert_eq!(
+            "BSD-2-Clause AND Apache-2.0",
+            "AND".parse::<LicenseConjunction>().unwrap().join(&licenses)
+        );
+        assert_eq!("MIT", LicenseConjunction::Or.join(&[SPDX::MIT]));
+        assert_eq!("", LicenseConjunction::Or.join(&[]));
+        assert_eq!(
+            "LicenseRef-Custom-License-v1.0 OR LicenseRef-Android-SDK OR GPL-2.0-only WITH Classpath-exception-2.0",
+            LicenseConjunction::Or.join(&[
+                SPDX::Other("Custom License, v1.0".into()),
+                SPDX::AndroidSdk,
+                SPDX::Other("GPL-2.0-only WITH Classpath-exception-2.0".into()),
+            ]),
+        );
+        assert!("/".parse::<LicenseConjunction>().is_err());
+    }
+}
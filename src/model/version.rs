@@ -0,0 +1,242 @@
+/*
+ * Copyright 2023 sukawasatoru
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::cmp::Ordering;
+use std::fmt::{Display, Formatter};
+
+/// Version string that is ordered like Maven's `ComparableVersion`.
+///
+/// `1.0-alpha01 < 1.0-beta01 < 1.0-rc01 < 1.0-SNAPSHOT < 1.0 = 1.0.0 < 1.0-sp1 < 1.0.1`
+///
+/// https://maven.apache.org/pom.html#version-order-specification
+#[derive(Clone, Debug)]
+pub struct Version(String);
+
+impl Version {
+    pub fn new(version: impl Into<String>) -> Self {
+        Self(version.into())
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Returns `true` if the version has a qualifier that is ordered before the release such as
+    /// `alpha`, `beta`, `rc` and `SNAPSHOT`.
+    pub fn is_pre_release(&self) -> bool {
+        items(&self.0).iter().any(|data| match data {
+            Item::Int(_) => false,
+            Item::Qualifier(qualifier) => qualifier_rank(qualifier) < RELEASE_RANK,
+        })
+    }
+}
+
+impl Display for Version {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl From<&str> for Version {
+    fn from(value: &str) -> Self {
+        Self::new(value)
+    }
+}
+
+impl PartialEq for Version {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Version {}
+
+impl PartialOrd for Version {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Version {
+    fn cmp(&self, other: &Self) -> Ordering {
+        let a = items(&self.0);
+        let b = items(&other.0);
+        for index in 0..a.len().max(b.len()) {
+            let ordering = match (a.get(index), b.get(index)) {
+                (Some(a), Some(b)) => a.cmp(b),
+                (Some(a), None) => a.cmp_null(),
+                (None, Some(b)) => b.cmp_null().reverse(),
+                (None, None) => unreachable!(),
+            };
+            if ordering != Ordering::Equal {
+                return ordering;
+            }
+        }
+        Ordering::Equal
+    }
+}
+
+#[derive(Debug, Eq, PartialEq)]
+enum Item {
+    Int(u64),
+    Qualifier(String),
+}
+
+const RELEASE_RANK: usize = 5;
+
+impl Item {
+    /// Compare with the missing item, which is `0` or the release.
+    fn cmp_null(&self) -> Ordering {
+        match self {
+            Self::Int(data) => data.cmp(&0),
+            Self::Qualifier(data) => qualifier_rank(data).cmp(&RELEASE_RANK),
+        }
+    }
+}
+
+impl PartialOrd for Item {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Item {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self, other) {
+            (Self::Int(a), Self::Int(b)) => a.cmp(b),
+            (Self::Int(_), Self::Qualifier(_)) => Ordering::Greater,
+            (Self::Qualifier(_), Self::Int(_)) => Ordering::Less,
+            (Self::Qualifier(a), Self::Qualifier(b)) => qualifier_rank(a)
+                .cmp(&qualifier_rank(b))
+                .then_with(|| match qualifier_rank(a) {
+                    // unknown qualifiers are ordered lexically.
+                    OTHER_RANK => a.cmp(b),
+                    _ => Ordering::Equal,
+                }),
+        }
+    }
+}
+
+const OTHER_RANK: usize = 7;
+
+/// `alpha < beta < milestone < rc < snapshot < release < sp < other`
+fn qualifier_rank(qualifier: &str) -> usize {
+    match qualifier {
+        "alpha" | "a" => 0,
+        "beta" | "b" => 1,
+        "milestone" | "m" => 2,
+        "rc" | "cr" => 3,
+        "snapshot" => 4,
+        "" | "ga" | "final" | "release" => RELEASE_RANK,
+        "sp" => 6,
+        _ => OTHER_RANK,
+    }
+}
+
+/// Split into the numbers and the lowercase qualifiers at `.`, `-`, `_` and the transition
+/// between digits and letters. The trailing null items (`0` and the release) are removed.
+fn items(version: &str) -> Vec<Item> {
+    let mut items = vec![];
+    let mut current = String::new();
+    let push = |current: &mut String, items: &mut Vec<Item>| {
+        if current.is_empty() {
+            return;
+        }
+        let token = std::mem::take(current).to_ascii_lowercase();
+        items.push(match token.parse() {
+            Ok(data) => Item::Int(data),
+            Err(_) => Item::Qualifier(token),
+        });
+    };
+
+    for c in version.trim().chars() {
+        if matches!(c, '.' | '-' | '_') {
+            push(&mut current, &mut items);
+            continue;
+        }
+        if let Some(last) = current.chars().last() {
+            if last.is_ascii_digit() != c.is_ascii_digit() {
+                push(&mut current, &mut items);
+            }
+        }
+        current.push(c);
+    }
+    push(&mut current, &mut items);
+
+    while let Some(last) = items.last() {
+        if last.cmp_null() != Ordering::Equal {
+            break;
+        }
+        items.pop();
+    }
+
+    items
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ordering() {
+        let mut actual = [
+            "1.0.1",
+            "1.0-sp1",
+            "1.0",
+            "1.0-SNAPSHOT",
+            "1.0-rc01",
+            "1.0-beta01",
+            "1.0-alpha10",
+            "1.0-alpha02",
+            "0.9",
+            "1.10",
+            "1.9",
+        ]
+        .map(Version::from);
+        actual.sort();
+
+        let expected = [
+            "0.9",
+            "1.0-alpha02",
+            "1.0-alpha10",
+            "1.0-beta01",
+            "1.0-rc01",
+            "1.0-SNAPSHOT",
+            "1.0",
+            "1.0-sp1",
+            "1.0.1",
+            "1.9",
+            "1.10",
+        ];
+        assert_eq!(expected, actual.map(|data| data.to_string()));
+    }
+
+    #[test]
+    fn equal_trailing_zero() {
+        assert_eq!(Version::from("1.0"), Version::from("1.0.0"));
+        assert_eq!(Version::from("1"), Version::from("1-final"));
+        assert_ne!(Version::from("1.0"), Version::from("1.0.1"));
+    }
+
+    #[test]
+    fn is_pre_release() {
+        assert!(Version::from("1.13.0-alpha01").is_pre_release());
+        assert!(Version::from("2.0.0-RC1").is_pre_release());
+        assert!(Version::from("1.0-SNAPSHOT").is_pre_release());
+        assert!(!Version::from("1.12.0").is_pre_release());
+        assert!(!Version::from("32.1.3-jre").is_pre_release());
+    }
+}